@@ -0,0 +1,181 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{authorization, module_options, string_option};
+
+pub fn allowlist_role(meta: &Meta, has_access: bool) -> Result<Option<String>, String> {
+    let options = module_options(meta, "Allowlist", &["role"])?;
+
+    if !has_access {
+        if !options.is_empty() {
+            return Err("Allowlist role can only be configured together with Access".to_string());
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some(string_option(&options, "role", "allowlist_admin")?))
+}
+
+pub fn allowlist(
+    item_ident: Ident,
+    role: Option<String>,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let admin_check = authorization(role.as_deref(), has_ownable);
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyAllowlist {
+            Allowed,
+        }
+
+        impl StorageKeyAllowlist {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyAllowlist::Allowed => "alw".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait Allowlist {
+            fn add_to_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>);
+
+            fn remove_from_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>);
+
+            fn set_allowlist_root(&mut self, root: Option<String>);
+
+            fn allowlist_root(&self) -> Option<String>;
+
+            fn is_allowlisted(&self, account_id: near_sdk::AccountId) -> bool;
+
+            fn verify_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>) -> bool;
+
+            fn assert_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>);
+        }
+
+
+            #[near_bindgen]
+            impl Allowlist for #item_ident {
+
+                fn add_to_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>) {
+                    #admin_check
+
+                    for account_id in account_ids.iter() {
+                        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                        }
+
+                        if self.allowlist.insert(account_id) {
+                            near_sdk::env::log(format!("Account {} is allowlisted", account_id).as_bytes())
+                        }
+                    }
+                }
+
+                fn remove_from_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>) {
+                    #admin_check
+
+                    for account_id in account_ids.iter() {
+                        if self.allowlist.remove(account_id) {
+                            near_sdk::env::log(
+                                format!("Account {} is removed from the allowlist", account_id).as_bytes(),
+                            )
+                        }
+                    }
+                }
+
+                fn set_allowlist_root(&mut self, root: Option<String>) {
+                    #admin_check
+
+                    let root = root.map(|root| {
+                        Self::allowlist_decode_hash(&root);
+                        root.to_lowercase()
+                    });
+
+                    near_sdk::env::log(
+                        format!(
+                            "Allowlist root is set to {} by {}",
+                            root.as_deref().unwrap_or("none"),
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.allowlist_root = root;
+                }
+
+                fn allowlist_root(&self) -> Option<String> {
+                    self.allowlist_root.clone()
+                }
+
+                fn is_allowlisted(&self, account_id: near_sdk::AccountId) -> bool {
+                    self.allowlist.contains(&account_id)
+                }
+
+                fn verify_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>) -> bool {
+                    if self.allowlist.contains(&account_id) {
+                        return true;
+                    }
+
+                    let root = match &self.allowlist_root {
+                        Some(root) => root,
+                        None => return false,
+                    };
+
+                    // Leaves and nodes are prefixed differently, so a node can never pass for a leaf
+                    let mut hash = near_sdk::env::sha256(&[&[0u8], account_id.as_bytes()].concat());
+
+                    for sibling in proof.iter() {
+                        let sibling = Self::allowlist_decode_hash(sibling);
+
+                        hash = if hash <= sibling {
+                            near_sdk::env::sha256(&[&[1u8], &hash[..], &sibling[..]].concat())
+                        } else {
+                            near_sdk::env::sha256(&[&[1u8], &sibling[..], &hash[..]].concat())
+                        };
+                    }
+
+                    Self::allowlist_encode_hash(&hash) == *root
+                }
+
+                fn assert_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>) {
+                    if !self.verify_allowlisted(account_id.clone(), proof) {
+                        near_sdk::env::panic(format!("Account {} is not allowlisted", account_id).as_bytes())
+                    }
+                }
+            }
+
+            impl #item_ident {
+                fn allowlist_encode_hash(hash: &[u8]) -> String {
+                    hash.iter().map(|b| format!("{:02x}", b)).collect()
+                }
+
+                fn allowlist_decode_hash(hash: &str) -> std::vec::Vec<u8> {
+                    if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        near_sdk::env::panic(format!("Hash {} is not a hex-encoded sha256", hash).as_bytes())
+                    }
+
+                    (0..32)
+                        .map(|i| u8::from_str_radix(&hash[2 * i..2 * i + 2], 16).unwrap())
+                        .collect()
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            allowlist: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+            allowlist_root: Option<String>,
+        }
+        .into(),
+    )
+}
@@ -0,0 +1 @@
+pub mod allowlist_near;
@@ -0,0 +1,136 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{parse_macro_input, Ident, ImplItemMethod, Meta};
+
+use crate::utils::{authorization, module_options, string_option};
+
+pub fn blocklist_role(meta: &Meta, has_access: bool) -> Result<Option<String>, String> {
+    let options = module_options(meta, "Blocklist", &["role"])?;
+
+    if !has_access {
+        if !options.is_empty() {
+            return Err("Blocklist role can only be configured together with Access".to_string());
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some(string_option(&options, "role", "blocklist_admin")?))
+}
+
+pub fn blocklist(
+    item_ident: Ident,
+    role: Option<String>,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let admin_check = authorization(role.as_deref(), has_ownable);
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyBlocklist {
+            Blocked,
+        }
+
+        impl StorageKeyBlocklist {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyBlocklist::Blocked => "blk".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait Blocklist {
+            fn block(&mut self, account_id: near_sdk::AccountId);
+
+            fn unblock(&mut self, account_id: near_sdk::AccountId);
+
+            fn is_blocked(&self, account_id: near_sdk::AccountId) -> bool;
+
+            fn assert_not_blocked(&self, account_id: near_sdk::AccountId);
+        }
+
+
+            #[near_bindgen]
+            impl Blocklist for #item_ident {
+
+                fn block(&mut self, account_id: near_sdk::AccountId) {
+                    #admin_check
+
+                    if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                    }
+
+                    if !self.blocklist.insert(&account_id) {
+                        near_sdk::env::panic(format!("Account {} is already blocked", account_id).as_bytes())
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "Account {} is blocked by {}",
+                            account_id,
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    )
+                }
+
+                fn unblock(&mut self, account_id: near_sdk::AccountId) {
+                    #admin_check
+
+                    if !self.blocklist.remove(&account_id) {
+                        near_sdk::env::panic(format!("Account {} is not blocked", account_id).as_bytes())
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "Account {} is unblocked by {}",
+                            account_id,
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    )
+                }
+
+                fn is_blocked(&self, account_id: near_sdk::AccountId) -> bool {
+                    self.blocklist.contains(&account_id)
+                }
+
+                fn assert_not_blocked(&self, account_id: near_sdk::AccountId) {
+                    if self.blocklist.contains(&account_id) {
+                        near_sdk::env::panic(format!("Account {} is blocked", account_id).as_bytes())
+                    }
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {blocklist: near_sdk::collections::LookupSet<near_sdk::AccountId>,}.into(),
+    )
+}
+
+pub fn blocklist_guard(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if !args.is_empty() {
+        return quote! {compile_error!("not_blocked does not accept arguments");}.into();
+    }
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("Blocklist guards can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    let guard = quote! {self.assert_not_blocked(near_sdk::env::predecessor_account_id());};
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
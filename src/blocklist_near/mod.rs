@@ -0,0 +1 @@
+pub mod blocklist_near;
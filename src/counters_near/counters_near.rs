@@ -0,0 +1,110 @@
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::{Ident, Meta, NestedMeta};
+
+// Collects the counter names, e.g. `Counters(orders, proposals)`
+pub fn counter_names(meta: &Meta) -> Result<Vec<Ident>, String> {
+    let list = match meta {
+        Meta::List(list) if !list.nested.is_empty() => list,
+        _ => return Err("Counters requires at least one counter name".to_string()),
+    };
+
+    let mut names: Vec<Ident> = Vec::new();
+
+    for nested in list.nested.iter() {
+        let name = match nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                path.get_ident().unwrap().clone()
+            }
+            _ => {
+                return Err("Counters only accepts counter names, e.g. Counters(orders)".to_string())
+            }
+        };
+
+        if names.contains(&name) {
+            return Err(format!("Counter {} is set twice", name));
+        }
+
+        names.push(name);
+    }
+
+    Ok(names)
+}
+
+pub fn counters(item_ident: Ident, names: Vec<Ident>) -> (TokenStream, TokenStream) {
+    let keys: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let accessors = names.iter().map(|name| format_ident!("next_{}_id", name));
+    let accessor_keys = keys.clone();
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyCounters {
+            Values,
+        }
+
+        impl StorageKeyCounters {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyCounters::Values => "cnt".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait Counters {
+            fn counters(&self) -> std::vec::Vec<(String, u64)>;
+        }
+
+
+            #[near_bindgen]
+            impl Counters for #item_ident {
+
+                // The next id of each counter, which is also the number of ids issued so far
+                fn counters(&self) -> std::vec::Vec<(String, u64)> {
+                    [#(#keys),*]
+                        .iter()
+                        .map(|name| (name.to_string(), self.counter_value(name)))
+                        .collect()
+                }
+            }
+
+            impl #item_ident {
+                #(
+                    pub fn #accessors(&mut self) -> u64 {
+                        self.counter_next(#accessor_keys)
+                    }
+                )*
+
+                pub fn counter_value(&self, name: &str) -> u64 {
+                    self.counter_values.get(&name.to_string()).unwrap_or(0)
+                }
+
+                // Returns the current value of the counter and increments it
+                fn counter_next(&mut self, name: &str) -> u64 {
+                    let id = self.counter_value(name);
+
+                    let next = id.checked_add(1).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Counter {} overflowed", name).as_bytes())
+                    });
+
+                    self.counter_values.insert(&name.to_string(), &next);
+
+                    id
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            counter_values: near_sdk::collections::LookupMap<String, u64>,
+        }
+        .into(),
+    )
+}
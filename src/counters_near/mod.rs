@@ -0,0 +1 @@
+pub mod counters_near;
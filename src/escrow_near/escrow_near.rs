@@ -0,0 +1,339 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::Ident;
+
+pub fn escrow(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyEscrow {
+            Records,
+        }
+
+        impl StorageKeyEscrow {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyEscrow::Records => "esc".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub enum EscrowStatus {
+            Active,
+            Releasing,
+            Refunding,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct EscrowRecord {
+            pub depositor: near_sdk::AccountId,
+            pub beneficiary: near_sdk::AccountId,
+            pub amount: near_sdk::json_types::U128,
+            // `None` for native NEAR
+            pub token_id: Option<near_sdk::AccountId>,
+            pub deadline: u64,
+            pub arbiter: Option<near_sdk::AccountId>,
+            pub disputed: bool,
+            pub status: EscrowStatus,
+        }
+
+        // `msg` of `ft_transfer_call`
+        #[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct EscrowArgs {
+            pub beneficiary: near_sdk::AccountId,
+            pub duration_secs: u64,
+            pub arbiter: Option<near_sdk::AccountId>,
+        }
+
+
+        pub trait Escrow {
+            fn escrow_deposit(&mut self, beneficiary: near_sdk::AccountId, duration_secs: u64, arbiter: Option<near_sdk::AccountId>) -> u64;
+
+            fn escrow_release(&mut self, id: u64) -> near_sdk::Promise;
+
+            fn refund_after_deadline(&mut self, id: u64) -> near_sdk::Promise;
+
+            fn escrow_dispute(&mut self, id: u64);
+
+            fn resolve_dispute(&mut self, id: u64, release: bool) -> near_sdk::Promise;
+
+            fn escrow_on_payout(&mut self, id: u64) -> bool;
+
+            fn escrow_get(&self, id: u64) -> Option<EscrowRecord>;
+
+            fn escrows(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, EscrowRecord)>;
+        }
+
+
+            #[near_bindgen]
+            impl Escrow for #item_ident {
+
+                #[payable]
+                fn escrow_deposit(&mut self, beneficiary: near_sdk::AccountId, duration_secs: u64, arbiter: Option<near_sdk::AccountId>) -> u64 {
+                    self.escrow_create(
+                        near_sdk::env::predecessor_account_id(),
+                        None,
+                        near_sdk::env::attached_deposit(),
+                        EscrowArgs {
+                            beneficiary,
+                            duration_secs,
+                            arbiter,
+                        },
+                    )
+                }
+
+                fn escrow_release(&mut self, id: u64) -> near_sdk::Promise {
+                    let record = self.escrow_active(id);
+
+                    if near_sdk::env::predecessor_account_id() != record.depositor {
+                        near_sdk::env::panic(format!("Only the depositor can release escrow {}", id).as_bytes())
+                    }
+
+                    if record.disputed {
+                        near_sdk::env::panic(format!("Escrow {} is disputed", id).as_bytes())
+                    }
+
+                    self.escrow_payout(id, record, true)
+                }
+
+                fn refund_after_deadline(&mut self, id: u64) -> near_sdk::Promise {
+                    let record = self.escrow_active(id);
+
+                    if record.disputed {
+                        near_sdk::env::panic(format!("Escrow {} is disputed", id).as_bytes())
+                    }
+
+                    if near_sdk::env::block_timestamp() < record.deadline {
+                        near_sdk::env::panic(
+                            format!("Escrow {} is locked until {}", id, record.deadline).as_bytes(),
+                        )
+                    }
+
+                    self.escrow_payout(id, record, false)
+                }
+
+                fn escrow_dispute(&mut self, id: u64) {
+                    let mut record = self.escrow_active(id);
+                    let caller = near_sdk::env::predecessor_account_id();
+
+                    if caller != record.depositor && caller != record.beneficiary {
+                        near_sdk::env::panic(
+                            format!("Only the depositor or the beneficiary can dispute escrow {}", id).as_bytes(),
+                        )
+                    }
+
+                    if record.arbiter.is_none() {
+                        near_sdk::env::panic(format!("Escrow {} has no arbiter", id).as_bytes())
+                    }
+
+                    if record.disputed {
+                        near_sdk::env::panic(format!("Escrow {} is already disputed", id).as_bytes())
+                    }
+
+                    record.disputed = true;
+                    self.escrows.insert(&id, &record);
+
+                    near_sdk::env::log(format!("Escrow {} is disputed by {}", id, caller).as_bytes());
+                }
+
+                fn resolve_dispute(&mut self, id: u64, release: bool) -> near_sdk::Promise {
+                    let record = self.escrow_active(id);
+
+                    if Some(near_sdk::env::predecessor_account_id()) != record.arbiter {
+                        near_sdk::env::panic(format!("Only the arbiter can resolve escrow {}", id).as_bytes())
+                    }
+
+                    if !record.disputed {
+                        near_sdk::env::panic(format!("Escrow {} is not disputed", id).as_bytes())
+                    }
+
+                    self.escrow_payout(id, record, release)
+                }
+
+                // The record stays locked until the payout is done, and is unlocked again if the payout fails
+                #[private]
+                fn escrow_on_payout(&mut self, id: u64) -> bool {
+                    let mut record = self.escrows.get(&id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Escrow {} does not exist", id).as_bytes())
+                    });
+
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        let receiver = match record.status {
+                            EscrowStatus::Releasing => &record.beneficiary,
+                            _ => &record.depositor,
+                        };
+
+                        near_sdk::env::log(format!("Escrow {} is paid out to {}", id, receiver).as_bytes());
+
+                        self.escrows.remove(&id);
+
+                        return true;
+                    }
+
+                    near_sdk::env::log(format!("Payout of escrow {} failed", id).as_bytes());
+
+                    record.status = EscrowStatus::Active;
+                    self.escrows.insert(&id, &record);
+
+                    false
+                }
+
+                fn escrow_get(&self, id: u64) -> Option<EscrowRecord> {
+                    self.escrows.get(&id)
+                }
+
+                fn escrows(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, EscrowRecord)> {
+                    self.escrows
+                        .iter()
+                        .skip(from_index as usize)
+                        .take(limit as usize)
+                        .collect()
+                }
+            }
+
+            impl #item_ident {
+                pub const ESCROW_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+                pub const ESCROW_GAS_FOR_PAYOUT_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+
+                // Creates an escrow of tokens received by the contract. Call it from `ft_on_transfer`, the token
+                // contract is the caller. Panicking makes it refund the whole amount
+                pub fn escrow_on_ft_transfer(&mut self, sender_id: near_sdk::AccountId, amount: near_sdk::Balance, msg: String) -> u64 {
+                    let args = near_sdk::serde_json::from_str::<EscrowArgs>(&msg)
+                        .unwrap_or_else(|_| near_sdk::env::panic(b"msg has to be a JSON object with beneficiary, duration_secs and arbiter"));
+
+                    self.escrow_create(sender_id, Some(near_sdk::env::predecessor_account_id()), amount, args)
+                }
+
+                fn escrow_create(&mut self, depositor: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>, amount: near_sdk::Balance, args: EscrowArgs) -> u64 {
+                    if amount == 0 {
+                        near_sdk::env::panic(b"The amount should be a positive number")
+                    }
+
+                    if !near_sdk::env::is_valid_account_id(args.beneficiary.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", args.beneficiary).as_bytes())
+                    }
+
+                    if args.beneficiary == depositor {
+                        near_sdk::env::panic(b"The depositor and the beneficiary should be different")
+                    }
+
+                    if let Some(arbiter) = &args.arbiter {
+                        if !near_sdk::env::is_valid_account_id(arbiter.as_bytes()) {
+                            near_sdk::env::panic(format!("Account {} is invalid", arbiter).as_bytes())
+                        }
+
+                        if *arbiter == depositor || *arbiter == args.beneficiary {
+                            near_sdk::env::panic(b"The arbiter cannot be a party of the escrow")
+                        }
+                    }
+
+                    if args.duration_secs == 0 {
+                        near_sdk::env::panic(b"duration_secs has to be positive")
+                    }
+
+                    let deadline = args
+                        .duration_secs
+                        .checked_mul(1_000_000_000)
+                        .and_then(|duration| near_sdk::env::block_timestamp().checked_add(duration))
+                        .unwrap_or_else(|| near_sdk::env::panic(b"duration_secs is too large"));
+
+                    let id = self.escrow_next_id;
+                    self.escrow_next_id += 1;
+
+                    near_sdk::env::log(
+                        format!(
+                            "Escrow {} of {} {} is created by {} for {}",
+                            id,
+                            amount,
+                            token_id.as_deref().unwrap_or("yoctoNEAR"),
+                            depositor,
+                            args.beneficiary
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.escrows.insert(
+                        &id,
+                        &EscrowRecord {
+                            depositor,
+                            beneficiary: args.beneficiary,
+                            amount: amount.into(),
+                            token_id,
+                            deadline,
+                            arbiter: args.arbiter,
+                            disputed: false,
+                            status: EscrowStatus::Active,
+                        },
+                    );
+
+                    id
+                }
+
+                fn escrow_active(&self, id: u64) -> EscrowRecord {
+                    let record = self.escrows.get(&id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Escrow {} does not exist", id).as_bytes())
+                    });
+
+                    if record.status != EscrowStatus::Active {
+                        near_sdk::env::panic(format!("Escrow {} is being paid out", id).as_bytes())
+                    }
+
+                    record
+                }
+
+                fn escrow_payout(&mut self, id: u64, mut record: EscrowRecord, release: bool) -> near_sdk::Promise {
+                    let receiver = if release {
+                        record.status = EscrowStatus::Releasing;
+                        record.beneficiary.clone()
+                    } else {
+                        record.status = EscrowStatus::Refunding;
+                        record.depositor.clone()
+                    };
+
+                    self.escrows.insert(&id, &record);
+
+                    let payout = match &record.token_id {
+                        Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(
+                            b"ft_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "receiver_id": receiver,
+                                "amount": record.amount,
+                                "memo": format!("Escrow {}", id),
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            1,
+                            Self::ESCROW_GAS_FOR_FT_TRANSFER,
+                        ),
+                        None => near_sdk::Promise::new(receiver).transfer(record.amount.0),
+                    };
+
+                    payout.then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                        b"escrow_on_payout".to_vec(),
+                        near_sdk::serde_json::json!({"id": id}).to_string().into_bytes(),
+                        0,
+                        Self::ESCROW_GAS_FOR_PAYOUT_CALLBACK,
+                    ))
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            escrows: near_sdk::collections::UnorderedMap<u64, EscrowRecord>,
+            escrow_next_id: u64,
+        }
+        .into(),
+    )
+}
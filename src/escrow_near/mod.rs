@@ -0,0 +1 @@
+pub mod escrow_near;
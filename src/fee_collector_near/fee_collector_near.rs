@@ -0,0 +1,277 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{authorization, module_options, string_option, u64_option};
+
+pub struct FeeCollectorConfig {
+    role: Option<String>,
+    max_bps: u16,
+}
+
+pub fn fee_collector_config(meta: &Meta, has_access: bool) -> Result<FeeCollectorConfig, String> {
+    let options = module_options(meta, "FeeCollector", &["role", "max_bps"])?;
+
+    let role = if has_access {
+        Some(string_option(&options, "role", "fee_admin")?)
+    } else if options.contains_key("role") {
+        return Err("FeeCollector role can only be configured together with Access".to_string());
+    } else {
+        None
+    };
+
+    let max_bps = u64_option(&options, "max_bps", 1_000)?;
+
+    if max_bps > 10_000 {
+        return Err("max_bps cannot be above 10000".to_string());
+    }
+
+    Ok(FeeCollectorConfig {
+        role,
+        max_bps: max_bps as u16,
+    })
+}
+
+pub fn fee_collector(
+    item_ident: Ident,
+    config: FeeCollectorConfig,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let admin_check = authorization(config.role.as_deref(), has_ownable);
+    let max_bps = config.max_bps;
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyFeeCollector {
+            Accrued,
+        }
+
+        impl StorageKeyFeeCollector {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyFeeCollector::Accrued => "fee".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait FeeCollector {
+            fn set_fee_bps(&mut self, fee_bps: u16);
+
+            fn set_fee_treasury(&mut self, treasury: near_sdk::AccountId);
+
+            fn withdraw_fees(&mut self, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise;
+
+            fn fee_on_withdraw(&mut self, token_id: Option<near_sdk::AccountId>, amount: near_sdk::json_types::U128) -> bool;
+
+            fn fee_bps(&self) -> u16;
+
+            fn fee_max_bps(&self) -> u16;
+
+            fn fee_treasury(&self) -> Option<near_sdk::AccountId>;
+
+            fn accrued_fee(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+
+            fn accrued_fees(&self) -> std::vec::Vec<(Option<near_sdk::AccountId>, near_sdk::json_types::U128)>;
+        }
+
+
+            #[near_bindgen]
+            impl FeeCollector for #item_ident {
+
+                fn set_fee_bps(&mut self, fee_bps: u16) {
+                    #admin_check
+
+                    Self::fee_assert_bps(fee_bps);
+
+                    near_sdk::env::log(format!("Fee is changed from {} to {} basis points", self.fee_bps, fee_bps).as_bytes());
+
+                    self.fee_bps = fee_bps;
+                }
+
+                fn set_fee_treasury(&mut self, treasury: near_sdk::AccountId) {
+                    #admin_check
+
+                    Self::fee_assert_treasury(&treasury);
+
+                    near_sdk::env::log(format!("Treasury is set to {}", treasury).as_bytes());
+
+                    self.fee_treasury = Some(treasury);
+                }
+
+                // Accrued fees are cleared before the transfer and restored by the callback if it fails
+                fn withdraw_fees(&mut self, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise {
+                    #admin_check
+
+                    let treasury = self
+                        .fee_treasury
+                        .clone()
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Treasury is not set"));
+
+                    let amount = self.fee_accrued.remove(&token_id).unwrap_or(0);
+
+                    if amount == 0 {
+                        near_sdk::env::panic(b"There are no fees to withdraw")
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "{} {} of fees are withdrawn to {}",
+                            amount,
+                            token_id.as_deref().unwrap_or("yoctoNEAR"),
+                            treasury
+                        )
+                        .as_bytes(),
+                    );
+
+                    let transfer = match &token_id {
+                        Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(
+                            b"ft_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "receiver_id": treasury,
+                                "amount": near_sdk::json_types::U128(amount),
+                                "memo": "Fees",
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            1,
+                            Self::FEE_GAS_FOR_FT_TRANSFER,
+                        ),
+                        None => near_sdk::Promise::new(treasury).transfer(amount),
+                    };
+
+                    transfer.then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                        b"fee_on_withdraw".to_vec(),
+                        near_sdk::serde_json::json!({
+                            "token_id": token_id,
+                            "amount": near_sdk::json_types::U128(amount),
+                        })
+                        .to_string()
+                        .into_bytes(),
+                        0,
+                        Self::FEE_GAS_FOR_WITHDRAW_CALLBACK,
+                    ))
+                }
+
+                #[private]
+                fn fee_on_withdraw(&mut self, token_id: Option<near_sdk::AccountId>, amount: near_sdk::json_types::U128) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        return true;
+                    }
+
+                    self.fee_accrue(&token_id, amount.0);
+
+                    near_sdk::env::log(format!("Withdrawal of {} of fees failed", amount.0).as_bytes());
+
+                    false
+                }
+
+                fn fee_bps(&self) -> u16 {
+                    self.fee_bps
+                }
+
+                fn fee_max_bps(&self) -> u16 {
+                    Self::FEE_MAX_BPS
+                }
+
+                fn fee_treasury(&self) -> Option<near_sdk::AccountId> {
+                    self.fee_treasury.clone()
+                }
+
+                fn accrued_fee(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+                    self.fee_accrued.get(&token_id).unwrap_or(0).into()
+                }
+
+                fn accrued_fees(&self) -> std::vec::Vec<(Option<near_sdk::AccountId>, near_sdk::json_types::U128)> {
+                    self.fee_accrued
+                        .iter()
+                        .map(|(token_id, amount)| (token_id, amount.into()))
+                        .collect()
+                }
+            }
+
+            impl #item_ident {
+                pub const FEE_MAX_BPS: u16 = #max_bps;
+                pub const FEE_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+                pub const FEE_GAS_FOR_WITHDRAW_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+
+                pub fn setup_fee_collector(&mut self, treasury: near_sdk::AccountId, fee_bps: u16) {
+                    Self::fee_assert_treasury(&treasury);
+                    Self::fee_assert_bps(fee_bps);
+
+                    self.fee_treasury = Some(treasury);
+                    self.fee_bps = fee_bps;
+                }
+
+                // Splits an amount of native NEAR into the net amount and the fee, and accrues the fee
+                pub fn take_fee(&mut self, amount: near_sdk::Balance) -> (near_sdk::Balance, near_sdk::Balance) {
+                    self.fee_take(None, amount)
+                }
+
+                // Same as `take_fee` for an amount of the NEP-141 token `token_id`
+                pub fn take_token_fee(&mut self, token_id: near_sdk::AccountId, amount: near_sdk::Balance) -> (near_sdk::Balance, near_sdk::Balance) {
+                    self.fee_take(Some(token_id), amount)
+                }
+
+                // The fee is rounded up, so splitting an amount does not avoid it
+                fn fee_take(&mut self, token_id: Option<near_sdk::AccountId>, amount: near_sdk::Balance) -> (near_sdk::Balance, near_sdk::Balance) {
+                    let bps = self.fee_bps as near_sdk::Balance;
+
+                    // Split to avoid overflowing `amount * bps`
+                    let fee = amount / 10_000 * bps + (amount % 10_000 * bps + 9_999) / 10_000;
+
+                    if fee > 0 {
+                        self.fee_accrue(&token_id, fee);
+                    }
+
+                    (amount - fee, fee)
+                }
+
+                fn fee_assert_bps(fee_bps: u16) {
+                    if fee_bps > Self::FEE_MAX_BPS {
+                        near_sdk::env::panic(
+                            format!(
+                                "Fee of {} basis points is above the maximum of {}",
+                                fee_bps,
+                                Self::FEE_MAX_BPS
+                            )
+                            .as_bytes(),
+                        )
+                    }
+                }
+
+                fn fee_assert_treasury(treasury: &near_sdk::AccountId) {
+                    if !near_sdk::env::is_valid_account_id(treasury.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", treasury).as_bytes())
+                    }
+                }
+
+                fn fee_accrue(&mut self, token_id: &Option<near_sdk::AccountId>, amount: near_sdk::Balance) {
+                    let accrued = self
+                        .fee_accrued
+                        .get(token_id)
+                        .unwrap_or(0)
+                        .checked_add(amount)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Accrued fees overflow"));
+
+                    self.fee_accrued.insert(token_id, &accrued);
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            fee_bps: u16,
+            fee_treasury: Option<near_sdk::AccountId>,
+            fee_accrued: near_sdk::collections::UnorderedMap<Option<near_sdk::AccountId>, near_sdk::Balance>,
+        }
+        .into(),
+    )
+}
@@ -0,0 +1 @@
+pub mod fee_collector_near;
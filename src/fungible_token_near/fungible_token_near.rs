@@ -0,0 +1,448 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{module_options, string_option};
+
+pub fn fungible_token_minter(meta: &Meta, has_access: bool) -> Result<Option<String>, String> {
+    let options = module_options(meta, "FungibleToken", &["minter"])?;
+
+    if !has_access {
+        if !options.is_empty() {
+            return Err(
+                "FungibleToken roles can only be configured together with Access".to_string(),
+            );
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some(string_option(&options, "minter", "minter")?))
+}
+
+pub fn fungible_token(
+    item_ident: Ident,
+    minter: Option<String>,
+    has_pausable: bool,
+    has_storage_management: bool,
+) -> (TokenStream, TokenStream) {
+    let pause_guard = if has_pausable {
+        quote! {self.when_not_paused();}
+    } else {
+        quote! {}
+    };
+
+    // Only registered accounts can hold tokens. With StorageManagement they pay for their storage with
+    // `storage_deposit`, otherwise the contract registers them explicitly with `ft_register_account`, so that
+    // transfers cannot create new entries at the expense of the contract
+    let (registration_check, registration) = if has_storage_management {
+        (
+            quote! {
+                if self.storage_accounts.get(account_id).is_none() {
+                    near_sdk::env::panic(format!("Account {} is not registered", account_id).as_bytes())
+                }
+            },
+            quote! {},
+        )
+    } else {
+        (
+            quote! {
+                if self.ft_accounts.get(account_id).is_none() {
+                    near_sdk::env::panic(format!("Account {} is not registered", account_id).as_bytes())
+                }
+            },
+            quote! {
+                // Registers `account_id` with an empty balance. Returns `false` if it is already registered
+                pub fn ft_register_account(&mut self, account_id: &near_sdk::AccountId) -> bool {
+                    if self.ft_accounts.get(account_id).is_some() {
+                        return false;
+                    }
+
+                    if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                    }
+
+                    self.ft_accounts.insert(account_id, &0);
+
+                    near_sdk::env::log(format!("Account {} is registered", account_id).as_bytes());
+
+                    true
+                }
+            },
+        )
+    };
+
+    // Without StorageManagement, the initial holder is registered by the setup
+    let owner_registration = if has_storage_management {
+        quote! {}
+    } else {
+        quote! {self.ft_register_account(&owner_id);}
+    };
+
+    // The sender of a pending transfer can unregister before the refund comes back. Like in the NEP-141
+    // reference, the refund is burned then
+    let refund_burn = if has_storage_management {
+        quote! {
+            if self.storage_accounts.get(&sender_id).is_none() {
+                self.ft_total_supply -= refund;
+
+                near_sdk::env::log(
+                    format!("Account {} is not registered, {} refunded tokens are burned", sender_id, refund)
+                        .as_bytes(),
+                );
+
+                Self::ft_event("ft_burn", near_sdk::serde_json::json!({
+                    "owner_id": receiver_id,
+                    "amount": near_sdk::json_types::U128(refund),
+                    "memo": "refund",
+                }));
+
+                return amount.into();
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let minter_ts = match minter {
+        Some(minter) => quote! {
+            #[near_bindgen]
+            impl #item_ident {
+                pub fn ft_mint_tokens(&mut self, account_id: near_sdk::AccountId, amount: near_sdk::json_types::U128, memo: Option<String>) {
+                    self.assert_role(&#minter.to_string());
+                    #pause_guard
+
+                    self.ft_mint(&account_id, amount.0, memo)
+                }
+
+                pub fn ft_burn_tokens(&mut self, amount: near_sdk::json_types::U128, memo: Option<String>) {
+                    self.assert_role(&#minter.to_string());
+                    #pause_guard
+
+                    self.ft_burn(&near_sdk::env::predecessor_account_id(), amount.0, memo)
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyFungibleToken {
+            Accounts,
+            Metadata,
+        }
+
+        impl StorageKeyFungibleToken {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyFungibleToken::Accounts => "fta".to_string(),
+                    StorageKeyFungibleToken::Metadata => "ftm".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct FungibleTokenMetadata {
+            pub spec: String,
+            pub name: String,
+            pub symbol: String,
+            pub icon: Option<String>,
+            pub reference: Option<String>,
+            pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+            pub decimals: u8,
+        }
+
+
+        pub trait FungibleToken {
+            fn ft_transfer(&mut self, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128, memo: Option<String>);
+
+            fn ft_transfer_call(&mut self, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128, memo: Option<String>, msg: String) -> near_sdk::PromiseOrValue<near_sdk::json_types::U128>;
+
+            fn ft_total_supply(&self) -> near_sdk::json_types::U128;
+
+            fn ft_balance_of(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+
+            fn ft_resolve_transfer(&mut self, sender_id: near_sdk::AccountId, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128) -> near_sdk::json_types::U128;
+
+            fn ft_metadata(&self) -> FungibleTokenMetadata;
+        }
+
+
+            #[near_bindgen]
+            impl FungibleToken for #item_ident {
+
+                #[payable]
+                fn ft_transfer(&mut self, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128, memo: Option<String>) {
+                    Self::ft_assert_one_yocto();
+                    #pause_guard
+
+                    let sender_id = near_sdk::env::predecessor_account_id();
+
+                    self.ft_transfer_internal(&sender_id, &receiver_id, amount.0, memo)
+                }
+
+                #[payable]
+                fn ft_transfer_call(&mut self, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128, memo: Option<String>, msg: String) -> near_sdk::PromiseOrValue<near_sdk::json_types::U128> {
+                    Self::ft_assert_one_yocto();
+                    #pause_guard
+
+                    let sender_id = near_sdk::env::predecessor_account_id();
+
+                    self.ft_transfer_internal(&sender_id, &receiver_id, amount.0, memo);
+
+                    let receiver_gas = near_sdk::env::prepaid_gas()
+                        .checked_sub(Self::FT_GAS_FOR_TRANSFER_CALL)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Prepaid gas is not enough for ft_transfer_call"));
+
+                    near_sdk::Promise::new(receiver_id.clone())
+                        .function_call(
+                            b"ft_on_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "sender_id": sender_id,
+                                "amount": amount,
+                                "msg": msg,
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            receiver_gas,
+                        )
+                        .then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                            b"ft_resolve_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "sender_id": sender_id,
+                                "receiver_id": receiver_id,
+                                "amount": amount,
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            Self::FT_GAS_FOR_RESOLVE_TRANSFER,
+                        ))
+                        .into()
+                }
+
+                fn ft_total_supply(&self) -> near_sdk::json_types::U128 {
+                    self.ft_total_supply.into()
+                }
+
+                fn ft_balance_of(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+                    self.ft_accounts.get(&account_id).unwrap_or(0).into()
+                }
+
+                // Returns the amount used by the receiver. Not paused, so that refunds always go through
+                #[private]
+                fn ft_resolve_transfer(&mut self, sender_id: near_sdk::AccountId, receiver_id: near_sdk::AccountId, amount: near_sdk::json_types::U128) -> near_sdk::json_types::U128 {
+                    let amount = amount.0;
+
+                    let unused = match near_sdk::env::promise_result(0) {
+                        near_sdk::PromiseResult::Successful(value) => {
+                            match near_sdk::serde_json::from_slice::<near_sdk::json_types::U128>(&value) {
+                                Ok(unused) => std::cmp::min(amount, unused.0),
+                                Err(_) => amount,
+                            }
+                        }
+                        _ => amount,
+                    };
+
+                    if unused == 0 {
+                        return amount.into();
+                    }
+
+                    // The receiver may have spent the tokens already
+                    let refund = std::cmp::min(unused, self.ft_accounts.get(&receiver_id).unwrap_or(0));
+
+                    if refund == 0 {
+                        return amount.into();
+                    }
+
+                    self.ft_withdraw(&receiver_id, refund);
+
+                    #refund_burn
+
+                    self.ft_deposit(&sender_id, refund);
+
+                    Self::ft_event("ft_transfer", near_sdk::serde_json::json!({
+                        "old_owner_id": receiver_id,
+                        "new_owner_id": sender_id,
+                        "amount": near_sdk::json_types::U128(refund),
+                        "memo": "refund",
+                    }));
+
+                    (amount - refund).into()
+                }
+
+                fn ft_metadata(&self) -> FungibleTokenMetadata {
+                    self.ft_metadata
+                        .get()
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Token metadata is not set"))
+                }
+            }
+
+            impl #item_ident {
+                pub const FT_GAS_FOR_RESOLVE_TRANSFER: near_sdk::Gas = 5_000_000_000_000;
+                pub const FT_GAS_FOR_TRANSFER_CALL: near_sdk::Gas = 25_000_000_000_000 + Self::FT_GAS_FOR_RESOLVE_TRANSFER;
+
+                pub fn setup_fungible_token(&mut self, owner_id: near_sdk::AccountId, total_supply: near_sdk::json_types::U128, metadata: FungibleTokenMetadata) {
+                    if metadata.spec != "ft-1.0.0" {
+                        near_sdk::env::panic(b"Token metadata spec has to be ft-1.0.0")
+                    }
+
+                    if metadata.reference.is_some() != metadata.reference_hash.is_some() {
+                        near_sdk::env::panic(b"Token metadata reference and reference_hash have to be set together")
+                    }
+
+                    if let Some(reference_hash) = &metadata.reference_hash {
+                        if reference_hash.0.len() != 32 {
+                            near_sdk::env::panic(b"Token metadata reference_hash has to be 32 bytes")
+                        }
+                    }
+
+                    self.ft_metadata.set(&metadata);
+
+                    #owner_registration
+
+                    if total_supply.0 > 0 {
+                        self.ft_mint(&owner_id, total_supply.0, Some("Initial supply".to_string()));
+                    }
+                }
+
+                pub fn ft_mint(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance, memo: Option<String>) {
+                    if amount == 0 {
+                        near_sdk::env::panic(b"The amount should be a positive number")
+                    }
+
+                    self.ft_total_supply = self
+                        .ft_total_supply
+                        .checked_add(amount)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Total supply overflow"));
+
+                    self.ft_deposit(account_id, amount);
+
+                    Self::ft_event("ft_mint", near_sdk::serde_json::json!({
+                        "owner_id": account_id,
+                        "amount": near_sdk::json_types::U128(amount),
+                        "memo": memo,
+                    }));
+                }
+
+                pub fn ft_burn(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance, memo: Option<String>) {
+                    if amount == 0 {
+                        near_sdk::env::panic(b"The amount should be a positive number")
+                    }
+
+                    self.ft_withdraw(account_id, amount);
+                    self.ft_total_supply -= amount;
+
+                    Self::ft_event("ft_burn", near_sdk::serde_json::json!({
+                        "owner_id": account_id,
+                        "amount": near_sdk::json_types::U128(amount),
+                        "memo": memo,
+                    }));
+                }
+
+                #registration
+
+                fn ft_assert_one_yocto() {
+                    if near_sdk::env::attached_deposit() != 1 {
+                        near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+                    }
+                }
+
+                fn ft_deposit(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance) {
+                    #registration_check
+
+                    let balance = self
+                        .ft_accounts
+                        .get(account_id)
+                        .unwrap_or(0)
+                        .checked_add(amount)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Balance overflow"));
+
+                    self.ft_accounts.insert(account_id, &balance);
+                }
+
+                fn ft_withdraw(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance) {
+                    let balance = self
+                        .ft_accounts
+                        .get(account_id)
+                        .unwrap_or(0)
+                        .checked_sub(amount)
+                        .unwrap_or_else(|| {
+                            near_sdk::env::panic(
+                                format!("Account {} does not have enough balance", account_id).as_bytes(),
+                            )
+                        });
+
+                    self.ft_accounts.insert(account_id, &balance);
+                }
+
+                fn ft_transfer_internal(&mut self, sender_id: &near_sdk::AccountId, receiver_id: &near_sdk::AccountId, amount: near_sdk::Balance, memo: Option<String>) {
+                    if sender_id == receiver_id {
+                        near_sdk::env::panic(b"Sender and receiver should be different")
+                    }
+
+                    if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+                    }
+
+                    if amount == 0 {
+                        near_sdk::env::panic(b"The amount should be a positive number")
+                    }
+
+                    self.ft_withdraw(sender_id, amount);
+                    self.ft_deposit(receiver_id, amount);
+
+                    Self::ft_event("ft_transfer", near_sdk::serde_json::json!({
+                        "old_owner_id": sender_id,
+                        "new_owner_id": receiver_id,
+                        "amount": near_sdk::json_types::U128(amount),
+                        "memo": memo,
+                    }));
+                }
+
+                // Logs a NEP-297 event, leaving out an empty memo
+                fn ft_event(event: &str, mut data: near_sdk::serde_json::Value) {
+                    if let Some(data) = data.as_object_mut() {
+                        if data.get("memo").map_or(false, |memo| memo.is_null()) {
+                            data.remove("memo");
+                        }
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "EVENT_JSON:{}",
+                            near_sdk::serde_json::json!({
+                                "standard": "nep141",
+                                "version": "1.0.0",
+                                "event": event,
+                                "data": [data],
+                            })
+                        )
+                        .as_bytes(),
+                    )
+                }
+            }
+
+            #minter_ts
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            ft_accounts: near_sdk::collections::LookupMap<near_sdk::AccountId, near_sdk::Balance>,
+            ft_total_supply: near_sdk::Balance,
+            ft_metadata: near_sdk::collections::LazyOption<FungibleTokenMetadata>,
+        }
+        .into(),
+    )
+}
@@ -0,0 +1 @@
+pub mod fungible_token_near;
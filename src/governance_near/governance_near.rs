@@ -0,0 +1,394 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{bool_option, module_options, string_option};
+
+pub struct GovernanceConfig {
+    // `None` when the voting power comes from the `GovernanceVotingPower` hook of the contract
+    voter: Option<String>,
+}
+
+pub fn governance_config(meta: &Meta, has_access: bool) -> Result<GovernanceConfig, String> {
+    let options = module_options(meta, "Governance", &["voter", "token_weighted"])?;
+
+    if bool_option(&options, "token_weighted", false)? {
+        if options.contains_key("voter") {
+            return Err(
+                "Governance voter role cannot be configured together with token_weighted"
+                    .to_string(),
+            );
+        }
+
+        return Ok(GovernanceConfig { voter: None });
+    }
+
+    if !has_access {
+        return Err("Governance requires Access or token_weighted = true".to_string());
+    }
+
+    Ok(GovernanceConfig {
+        voter: Some(string_option(&options, "voter", "voter")?),
+    })
+}
+
+pub fn governance(item_ident: Ident, config: GovernanceConfig) -> (TokenStream, TokenStream) {
+    // Role members vote with a weight of one. `Access` keeps no history of its members, so the role is checked
+    // when the vote is cast, not when the proposal was created
+    let role_voting_power = match config.voter {
+        Some(voter) => quote! {
+            impl GovernanceVotingPower for #item_ident {
+                fn voting_power(&self, account_id: &near_sdk::AccountId, _created_at: u64) -> near_sdk::Balance {
+                    if self.has_role(&#voter.to_string(), account_id) {
+                        1
+                    } else {
+                        0
+                    }
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyGovernance {
+            Proposals,
+            Votes,
+        }
+
+        impl StorageKeyGovernance {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyGovernance::Proposals => "gvp".to_string(),
+                    StorageKeyGovernance::Votes => "gvv".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub enum GovernanceAction {
+            // Only records the outcome of the vote
+            Text,
+            FunctionCall {
+                receiver_id: near_sdk::AccountId,
+                method_name: String,
+                args: String,
+                deposit: near_sdk::json_types::U128,
+                gas: u64,
+            },
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct GovernanceProposal {
+            pub proposer: near_sdk::AccountId,
+            pub description: String,
+            pub action: GovernanceAction,
+            pub created_at: u64,
+            pub voting_ends_at: u64,
+            pub votes_for: near_sdk::json_types::U128,
+            pub votes_against: near_sdk::json_types::U128,
+            pub votes_abstain: near_sdk::json_types::U128,
+            pub executed: bool,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub enum GovernanceVote {
+            For,
+            Against,
+            Abstain,
+        }
+
+        #[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub enum GovernanceStatus {
+            Active,
+            Defeated,
+            Succeeded,
+            Executed,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct GovernanceSettings {
+            // Minimum voting power cast, abstentions included
+            pub quorum: near_sdk::json_types::U128,
+            // Share of the `For` votes among `For` and `Against` votes that has to be exceeded
+            pub threshold_bps: u16,
+            pub voting_period_secs: u64,
+        }
+
+        // Voting power of an account for a proposal created at `created_at`. Implemented by the macro for role
+        // voting and by the contract for token-weighted voting
+        pub trait GovernanceVotingPower {
+            fn voting_power(&self, account_id: &near_sdk::AccountId, created_at: u64) -> near_sdk::Balance;
+        }
+
+        #role_voting_power
+
+
+        pub trait Governance {
+            fn create_proposal(&mut self, description: String, action: GovernanceAction) -> u64;
+
+            fn vote(&mut self, id: u64, vote: GovernanceVote);
+
+            fn execute_proposal(&mut self, id: u64) -> near_sdk::PromiseOrValue<bool>;
+
+            fn governance_on_execute(&mut self, id: u64) -> bool;
+
+            fn set_governance_settings(&mut self, settings: GovernanceSettings);
+
+            fn proposal(&self, id: u64) -> Option<GovernanceProposal>;
+
+            fn proposals(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, GovernanceProposal)>;
+
+            fn proposal_status(&self, id: u64) -> GovernanceStatus;
+
+            fn governance_vote(&self, id: u64, account_id: near_sdk::AccountId) -> Option<GovernanceVote>;
+
+            fn governance_settings(&self) -> GovernanceSettings;
+        }
+
+
+            #[near_bindgen]
+            impl Governance for #item_ident {
+
+                fn create_proposal(&mut self, description: String, action: GovernanceAction) -> u64 {
+                    let proposer = near_sdk::env::predecessor_account_id();
+                    let now = near_sdk::env::block_timestamp();
+
+                    if self.voting_power(&proposer, now) == 0 {
+                        near_sdk::env::panic(format!("Account {} has no voting power", proposer).as_bytes())
+                    }
+
+                    if let GovernanceAction::FunctionCall { receiver_id, .. } = &action {
+                        if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+                            near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+                        }
+                    }
+
+                    let id = self.governance_next_id;
+
+                    let proposal = GovernanceProposal {
+                        proposer: proposer.clone(),
+                        description,
+                        action,
+                        created_at: now,
+                        voting_ends_at: self
+                            .governance_settings
+                            .voting_period_secs
+                            .checked_mul(1_000_000_000)
+                            .and_then(|period| now.checked_add(period))
+                            .unwrap_or_else(|| near_sdk::env::panic(b"Voting period overflows the block timestamp")),
+                        votes_for: 0.into(),
+                        votes_against: 0.into(),
+                        votes_abstain: 0.into(),
+                        executed: false,
+                    };
+
+                    self.governance_proposals.insert(&id, &proposal);
+                    self.governance_next_id += 1;
+
+                    near_sdk::env::log(format!("Proposal {} is created by {}", id, proposer).as_bytes());
+
+                    id
+                }
+
+                fn vote(&mut self, id: u64, vote: GovernanceVote) {
+                    let voter = near_sdk::env::predecessor_account_id();
+                    let mut proposal = self.governance_proposal(id);
+
+                    if near_sdk::env::block_timestamp() >= proposal.voting_ends_at {
+                        near_sdk::env::panic(format!("Voting on proposal {} has ended", id).as_bytes())
+                    }
+
+                    // Read at the creation of the proposal, so tokens moved since then cannot vote again
+                    let power = self.voting_power(&voter, proposal.created_at);
+
+                    if power == 0 {
+                        near_sdk::env::panic(format!("Account {} has no voting power", voter).as_bytes())
+                    }
+
+                    let key = (id, voter.clone());
+
+                    if self.governance_votes.insert(&key, &vote).is_some() {
+                        near_sdk::env::panic(format!("Account {} has already voted on proposal {}", voter, id).as_bytes())
+                    }
+
+                    let votes = match vote {
+                        GovernanceVote::For => &mut proposal.votes_for,
+                        GovernanceVote::Against => &mut proposal.votes_against,
+                        GovernanceVote::Abstain => &mut proposal.votes_abstain,
+                    };
+
+                    votes.0 = votes
+                        .0
+                        .checked_add(power)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Votes overflow"));
+
+                    self.governance_proposals.insert(&id, &proposal);
+
+                    near_sdk::env::log(format!("Account {} votes {:?} on proposal {} with {}", voter, vote, id, power).as_bytes());
+                }
+
+                // Anyone can execute a succeeded proposal. It is marked as executed before the call
+                // and the callback reverts it if the call fails, so it can be executed again
+                fn execute_proposal(&mut self, id: u64) -> near_sdk::PromiseOrValue<bool> {
+                    let mut proposal = self.governance_proposal(id);
+
+                    let status = self.governance_status(&proposal);
+
+                    if status != GovernanceStatus::Succeeded {
+                        near_sdk::env::panic(format!("Proposal {} is {:?}", id, status).as_bytes())
+                    }
+
+                    proposal.executed = true;
+                    self.governance_proposals.insert(&id, &proposal);
+
+                    near_sdk::env::log(format!("Proposal {} is executed", id).as_bytes());
+
+                    match proposal.action {
+                        GovernanceAction::Text => near_sdk::PromiseOrValue::Value(true),
+                        GovernanceAction::FunctionCall { receiver_id, method_name, args, deposit, gas } => {
+                            near_sdk::Promise::new(receiver_id)
+                                .function_call(method_name.into_bytes(), args.into_bytes(), deposit.0, gas)
+                                .then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                                    b"governance_on_execute".to_vec(),
+                                    near_sdk::serde_json::json!({ "id": id }).to_string().into_bytes(),
+                                    0,
+                                    Self::GOVERNANCE_GAS_FOR_EXECUTE_CALLBACK,
+                                ))
+                                .into()
+                        }
+                    }
+                }
+
+                #[private]
+                fn governance_on_execute(&mut self, id: u64) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        return true;
+                    }
+
+                    let mut proposal = self.governance_proposal(id);
+                    proposal.executed = false;
+                    self.governance_proposals.insert(&id, &proposal);
+
+                    near_sdk::env::log(format!("Execution of proposal {} failed", id).as_bytes());
+
+                    false
+                }
+
+                // Only the contract itself can change the settings, i.e. through a proposal
+                #[private]
+                fn set_governance_settings(&mut self, settings: GovernanceSettings) {
+                    Self::governance_assert_settings(&settings);
+
+                    self.governance_settings = settings;
+                }
+
+                fn proposal(&self, id: u64) -> Option<GovernanceProposal> {
+                    self.governance_proposals.get(&id)
+                }
+
+                fn proposals(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, GovernanceProposal)> {
+                    self.governance_proposals
+                        .iter()
+                        .skip(from_index as usize)
+                        .take(limit as usize)
+                        .collect()
+                }
+
+                fn proposal_status(&self, id: u64) -> GovernanceStatus {
+                    self.governance_status(&self.governance_proposal(id))
+                }
+
+                fn governance_vote(&self, id: u64, account_id: near_sdk::AccountId) -> Option<GovernanceVote> {
+                    self.governance_votes.get(&(id, account_id))
+                }
+
+                fn governance_settings(&self) -> GovernanceSettings {
+                    self.governance_settings.clone()
+                }
+            }
+
+            impl #item_ident {
+                pub const GOVERNANCE_GAS_FOR_EXECUTE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+                pub const GOVERNANCE_MAX_VOTING_PERIOD_SECS: u64 = 365 * 24 * 60 * 60;
+
+                pub fn setup_governance(&mut self, settings: GovernanceSettings) {
+                    Self::governance_assert_settings(&settings);
+
+                    self.governance_settings = settings;
+                }
+
+                fn governance_assert_settings(settings: &GovernanceSettings) {
+                    if settings.quorum.0 == 0 {
+                        near_sdk::env::panic(b"Quorum must be positive")
+                    }
+
+                    if settings.threshold_bps >= 10_000 {
+                        near_sdk::env::panic(b"Threshold must be below 10000 basis points")
+                    }
+
+                    if settings.voting_period_secs == 0 {
+                        near_sdk::env::panic(b"Voting period must be positive")
+                    }
+
+                    if settings.voting_period_secs > Self::GOVERNANCE_MAX_VOTING_PERIOD_SECS {
+                        near_sdk::env::panic(b"Voting period must be at most 365 days")
+                    }
+                }
+
+                fn governance_proposal(&self, id: u64) -> GovernanceProposal {
+                    self.governance_proposals
+                        .get(&id)
+                        .unwrap_or_else(|| near_sdk::env::panic(format!("Proposal {} does not exist", id).as_bytes()))
+                }
+
+                fn governance_status(&self, proposal: &GovernanceProposal) -> GovernanceStatus {
+                    if proposal.executed {
+                        return GovernanceStatus::Executed;
+                    }
+
+                    if near_sdk::env::block_timestamp() < proposal.voting_ends_at {
+                        return GovernanceStatus::Active;
+                    }
+
+                    let settings = &self.governance_settings;
+                    let decisive = proposal.votes_for.0.saturating_add(proposal.votes_against.0);
+                    let cast = decisive.saturating_add(proposal.votes_abstain.0);
+
+                    // `For` has to exceed `decisive * threshold_bps / 10000`, split to avoid overflowing
+                    let threshold = settings.threshold_bps as near_sdk::Balance;
+                    let required = decisive / 10_000 * threshold + decisive % 10_000 * threshold / 10_000;
+
+                    if cast >= settings.quorum.0 && proposal.votes_for.0 > required {
+                        GovernanceStatus::Succeeded
+                    } else {
+                        GovernanceStatus::Defeated
+                    }
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            governance_proposals: near_sdk::collections::UnorderedMap<u64, GovernanceProposal>,
+            governance_votes: near_sdk::collections::LookupMap<(u64, near_sdk::AccountId), GovernanceVote>,
+            governance_next_id: u64,
+            governance_settings: GovernanceSettings,
+        }
+        .into(),
+    )
+}
@@ -3,15 +3,49 @@ use proc_macro2::TokenStream as TokenStream2;
 use std::collections::LinkedList;
 
 use quote::quote;
-use syn::{parse_macro_input, Expr, ExprLit, Lit};
+use syn::{parse_macro_input, Expr, Lit};
+
+pub const MACROS: [&str; 22] = [
+    "access",
+    "allowlist",
+    "blocklist",
+    "counters",
+    "escrow",
+    "fee_collector",
+    "fungible_token",
+    "governance",
+    "multisig",
+    "non_fungible_token",
+    "nonces",
+    "oracle",
+    "ownable",
+    "pausable",
+    "payment_splitter",
+    "rate_limit",
+    "reentrancy_guard",
+    "state_migration",
+    "storage_management",
+    "timelock",
+    "upgradable",
+    "vesting",
+];
+
+// Maps a module name from `#[require(..)]`, e.g. `ReentrancyGuard`, to its `init_macro!` name
+pub fn module_name(require_name: &str) -> Option<&'static str> {
+    MACROS
+        .iter()
+        .find(|name| name.replace('_', "") == require_name.to_lowercase())
+        .copied()
+}
 
 pub fn init_macro(input: TokenStream) -> TokenStream {
     let array = parse_macro_input!(input as syn::ExprArray);
 
-    let macro_list = LinkedList::from(["access", "pausable"]);
+    let macro_list = LinkedList::from(MACROS);
 
     let mut literals = Vec::new();
     let mut struct_field_tk = TokenStream2::new();
+    let mut struct_field_names = Vec::new();
 
     if array.elems.is_empty() {
         return quote! {compile_error!("Array cannot be empty");}.into();
@@ -23,6 +57,10 @@ pub fn init_macro(input: TokenStream) -> TokenStream {
         }
 
         for field in s.fields.iter() {
+            if let syn::Member::Named(name) = &field.member {
+                struct_field_names.push(name.to_string());
+            }
+
             let mut field_tk = quote! {#field};
             field_tk.extend(quote! {,});
 
@@ -34,7 +72,7 @@ pub fn init_macro(input: TokenStream) -> TokenStream {
 
     for element in array.elems.iter() {
         match element {
-            Expr::Lit(l) => literals.push(ExprLit::from(l.clone())),
+            Expr::Lit(l) => literals.push(l.clone()),
             Expr::Struct(_s) => continue,
             _ => return quote! {compile_error!("Please include only literals")}.into(),
         };
@@ -52,17 +90,7 @@ pub fn init_macro(input: TokenStream) -> TokenStream {
                 return quote! {compile_error!("Value cannot be empty")}.into();
             }
 
-            if l.value() == "pausable" {
-                let new_fields = quote! {pause_status: false,};
-
-                new_fields_tk.extend(new_fields);
-            }
-
-            if l.value() == "access" {
-                let new_fields = quote! {access_control_roles: near_sdk::collections::UnorderedMap::new(StorageKeyAccessControl::Roles.into_bytes()),};
-
-                new_fields_tk.extend(new_fields);
-            }
+            new_fields_tk.extend(module_initializers(&l.value(), &struct_field_names));
         } else {
             return quote! {compile_error!("Only &str type is accepted")}.into();
         }
@@ -76,3 +104,159 @@ pub fn init_macro(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+// Initial values of the fields injected by a module. Fields listed in `struct_field_names` are set by the user
+pub fn module_initializers(module: &str, struct_field_names: &[String]) -> TokenStream2 {
+    let mut new_fields_tk = TokenStream2::new();
+
+    if module == "pausable" {
+        let new_fields = quote! {pause_status: false,};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "payment_splitter" {
+        let new_fields = quote! {splitter_shares: near_sdk::collections::UnorderedMap::new(StorageKeyPaymentSplitter::Shares.into_bytes()), splitter_total_shares: 0, splitter_received: near_sdk::collections::LookupMap::new(StorageKeyPaymentSplitter::Received.into_bytes()), splitter_released: near_sdk::collections::LookupMap::new(StorageKeyPaymentSplitter::Released.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "allowlist" {
+        let new_fields = quote! {allowlist: near_sdk::collections::LookupSet::new(StorageKeyAllowlist::Allowed.into_bytes()), allowlist_root: None,};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "blocklist" {
+        let new_fields = quote! {blocklist: near_sdk::collections::LookupSet::new(StorageKeyBlocklist::Blocked.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "counters" {
+        let new_fields = quote! {counter_values: near_sdk::collections::LookupMap::new(StorageKeyCounters::Values.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "escrow" {
+        let new_fields = quote! {escrows: near_sdk::collections::UnorderedMap::new(StorageKeyEscrow::Records.into_bytes()), escrow_next_id: 0,};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "fee_collector" {
+        let new_fields = quote! {fee_bps: 0, fee_treasury: None, fee_accrued: near_sdk::collections::UnorderedMap::new(StorageKeyFeeCollector::Accrued.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "governance" {
+        let new_fields = quote! {governance_proposals: near_sdk::collections::UnorderedMap::new(StorageKeyGovernance::Proposals.into_bytes()), governance_votes: near_sdk::collections::LookupMap::new(StorageKeyGovernance::Votes.into_bytes()), governance_next_id: 0, governance_settings: GovernanceSettings { quorum: near_sdk::json_types::U128(1), threshold_bps: 5_000, voting_period_secs: 604_800 },};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "fungible_token" {
+        let new_fields = quote! {
+            ft_accounts: near_sdk::collections::LookupMap::new(StorageKeyFungibleToken::Accounts.into_bytes()),
+            ft_total_supply: 0,
+            ft_metadata: near_sdk::collections::LazyOption::new(StorageKeyFungibleToken::Metadata.into_bytes(), None),
+        };
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "multisig" {
+        let new_fields = quote! {multisig_members: near_sdk::collections::UnorderedSet::new(StorageKeyMultisig::Members.into_bytes()), multisig_threshold: 1, multisig_requests: near_sdk::collections::UnorderedMap::new(StorageKeyMultisig::Requests.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "non_fungible_token" {
+        let new_fields = quote! {
+            nft_owner_by_id: near_sdk::collections::LookupMap::new(StorageKeyNonFungibleToken::OwnerById.into_bytes()),
+            nft_token_metadata: near_sdk::collections::LookupMap::new(StorageKeyNonFungibleToken::TokenMetadata.into_bytes()),
+            nft_metadata: near_sdk::collections::LazyOption::new(StorageKeyNonFungibleToken::Metadata.into_bytes(), None),
+            nft_token_ids: near_sdk::collections::UnorderedSet::new(StorageKeyNonFungibleToken::TokenIds.into_bytes()),
+            nft_tokens_per_owner: near_sdk::collections::LookupMap::new(StorageKeyNonFungibleToken::TokensPerOwner.into_bytes()),
+            nft_approvals: near_sdk::collections::LookupMap::new(StorageKeyNonFungibleToken::Approvals.into_bytes()),
+            nft_next_approval_ids: near_sdk::collections::LookupMap::new(StorageKeyNonFungibleToken::NextApprovalIds.into_bytes()),
+        };
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "oracle" {
+        let new_fields = quote! {oracle_assets: near_sdk::collections::UnorderedMap::new(StorageKeyOracle::Assets.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "nonces" {
+        let new_fields = quote! {nonces: near_sdk::collections::LookupMap::new(StorageKeyNonces::Nonces.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "ownable" {
+        // The owner can be passed explicitly, otherwise the caller of the constructor becomes the owner
+        if !struct_field_names.contains(&"owner".to_string()) {
+            new_fields_tk.extend(quote! {owner: near_sdk::env::predecessor_account_id(),});
+        }
+
+        new_fields_tk.extend(quote! {pending_owner: None,});
+    }
+
+    if module == "rate_limit" {
+        let new_fields = quote! {rate_limit_configs: near_sdk::collections::LookupMap::new(StorageKeyRateLimit::Configs.into_bytes()), rate_limit_usages: near_sdk::collections::LookupMap::new(StorageKeyRateLimit::Usages.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "reentrancy_guard" {
+        let new_fields = quote! {reentrancy_lock: false, reentrancy_locks: near_sdk::collections::LookupSet::new(StorageKeyReentrancyGuard::Locks.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "state_migration" {
+        new_fields_tk.extend(quote! {state_version: Self::STATE_VERSION,});
+    }
+
+    if module == "storage_management" {
+        let new_fields = quote! {
+            storage_accounts: near_sdk::collections::LookupMap::new(StorageKeyStorageManagement::Accounts.into_bytes()),
+            storage_min_bytes: Self::STORAGE_MIN_BYTES,
+            storage_max_bytes: Self::STORAGE_MAX_BYTES,
+        };
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "timelock" {
+        let new_fields = quote! {timelock_operations: near_sdk::collections::UnorderedMap::new(StorageKeyTimelock::Operations.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "upgradable" {
+        let new_fields = quote! {staged_code: near_sdk::collections::LazyOption::new(StorageKeyUpgradable::StagedCode.into_bytes(), None), staged_code_hash: None, staged_at: None,};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "vesting" {
+        let new_fields = quote! {vesting_schedules: near_sdk::collections::LookupMap::new(StorageKeyVesting::Schedules.into_bytes()), vesting_token: None, vesting_unallocated: 0,};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    if module == "access" {
+        let new_fields = quote! {access_control_roles: near_sdk::collections::UnorderedMap::new(StorageKeyAccessControl::Roles.into_bytes()),};
+
+        new_fields_tk.extend(new_fields);
+    }
+
+    new_fields_tk
+}
@@ -1,18 +1,65 @@
+#![allow(clippy::module_inception)]
+
 extern crate proc_macro;
 
 mod access_control_near;
+mod allowlist_near;
+mod blocklist_near;
+mod counters_near;
+mod escrow_near;
+mod fee_collector_near;
+mod fungible_token_near;
+mod governance_near;
 mod init_macro;
+mod multisig_near;
+mod non_fungible_token_near;
+mod nonces_near;
+mod oracle_near;
+mod ownable_near;
 mod pausable_near;
+mod payment_splitter_near;
+mod rate_limit_near;
+mod reentrancy_guard_near;
+mod state_migration_near;
+mod storage_management_near;
+mod timelock_near;
+mod upgradable_near;
+mod utils;
+mod vesting_near;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use std::collections::LinkedList;
 use syn::parse::Parser;
-use syn::{parse_macro_input, Ident};
+use syn::parse_macro_input;
 
 use quote::quote;
 
 use access_control_near::access_control_near::access_control;
-use pausable_near::pausable_near::pausable;
+use allowlist_near::allowlist_near::{allowlist, allowlist_role};
+use blocklist_near::blocklist_near::{blocklist, blocklist_guard, blocklist_role};
+use counters_near::counters_near::{counter_names, counters};
+use escrow_near::escrow_near::escrow;
+use fee_collector_near::fee_collector_near::{fee_collector, fee_collector_config};
+use fungible_token_near::fungible_token_near::{fungible_token, fungible_token_minter};
+use governance_near::governance_near::{governance, governance_config};
+use multisig_near::multisig_near::{multisig_expiration, multisig_guard, multisignature};
+use non_fungible_token_near::non_fungible_token_near::{
+    non_fungible_token, non_fungible_token_config,
+};
+use nonces_near::nonces_near::nonces;
+use oracle_near::oracle_near::{oracle, oracle_config};
+use ownable_near::ownable_near::ownable;
+use pausable_near::pausable_near::{pausable, pausable_roles, pause_guard};
+use payment_splitter_near::payment_splitter_near::payment_splitter;
+use rate_limit_near::rate_limit_near::{rate_limit_guard, rate_limiting};
+use reentrancy_guard_near::reentrancy_guard_near::{lock_guard, reentrancy_guard};
+use state_migration_near::state_migration_near::{state_migration, state_migration_config};
+use storage_management_near::storage_management_near::{
+    storage_management, storage_management_bounds,
+};
+use timelock_near::timelock_near::{timelock, timelock_config, timelock_guard};
+use upgradable_near::upgradable_near::{upgradable, upgradable_config};
+use vesting_near::vesting_near::{vesting, vesting_role};
 
 #[proc_macro_attribute]
 pub fn require(args: TokenStream, item: TokenStream) -> TokenStream {
@@ -22,12 +69,35 @@ pub fn require(args: TokenStream, item: TokenStream) -> TokenStream {
     let item_vis = &item.vis;
     let original_fields = &item.fields;
 
-    let allowed_imports = LinkedList::from(["access", "pausable"]);
+    let allowed_imports = LinkedList::from([
+        "access",
+        "allowlist",
+        "blocklist",
+        "counters",
+        "escrow",
+        "feecollector",
+        "fungibletoken",
+        "governance",
+        "multisig",
+        "nonfungibletoken",
+        "nonces",
+        "oracle",
+        "ownable",
+        "pausable",
+        "paymentsplitter",
+        "ratelimit",
+        "reentrancyguard",
+        "statemigration",
+        "storagemanagement",
+        "timelock",
+        "upgradable",
+        "vesting",
+    ]);
 
     let mut fields = TokenStream2::new();
     let mut gen = TokenStream::new();
 
-    let arg_vec: Vec<&Ident> = Vec::new();
+    let mut arg_vec: Vec<String> = Vec::new();
 
     for field in original_fields.iter() {
         let field_tk = quote! {#field,};
@@ -41,37 +111,242 @@ pub fn require(args: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     let args_parsed =
-        syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated.parse(args);
-
-    if args_parsed.is_ok() {
-        let punctuated_array = args_parsed.as_ref().unwrap();
+        syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated.parse(args);
 
+    if let Ok(punctuated_array) = &args_parsed {
         for arg in punctuated_array.iter() {
-            let ident = &arg.segments.first().unwrap().ident;
+            let ident = match arg {
+                syn::Meta::Path(path) => path.get_ident(),
+                syn::Meta::List(list) => list.path.get_ident(),
+                syn::Meta::NameValue(_) => None,
+            };
+
+            let ident = match ident {
+                Some(ident) => ident,
+                None => return quote! {compile_error!("Failed to parse");}.into(),
+            };
 
             if !allowed_imports.contains(&&*ident.to_string().to_lowercase()) {
                 return quote! {compile_error!("There is no such macro");}.into();
             }
 
-            if arg_vec.contains(&&ident.clone()) {
+            if arg_vec.contains(&ident.to_string().to_lowercase()) {
                 return quote! {compile_error!("Please do not enter duplicate macros");}.into();
             }
 
-            if ident.to_string().to_lowercase() == "access" {
-                let (access_control_ts, field_ts) = access_control(item_ident.clone());
+            arg_vec.push(ident.to_string().to_lowercase());
+        }
+
+        let has_access = arg_vec.contains(&"access".to_string());
+        let has_ownable = arg_vec.contains(&"ownable".to_string());
+        let has_pausable = arg_vec.contains(&"pausable".to_string());
+        let has_storage_management = arg_vec.contains(&"storagemanagement".to_string());
 
-                fields.extend(TokenStream2::from(field_ts));
+        let mut module_fields: Vec<(String, TokenStream2)> = Vec::new();
+        let mut state_migration_arg = None;
 
-                gen.extend(access_control_ts);
-            }
+        for arg in punctuated_array.iter() {
+            let name = arg.path().get_ident().unwrap().to_string().to_lowercase();
 
-            if ident.to_string().to_lowercase() == "pausable" {
-                let (pausable_ts, field_ts) = pausable(item_ident.clone());
+            let (module_ts, field_ts) = match name.as_str() {
+                "access" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("Access does not accept arguments");}.into();
+                    }
 
-                fields.extend(TokenStream2::from(field_ts));
+                    access_control(item_ident.clone())
+                }
+                "allowlist" => {
+                    let role = match allowlist_role(arg, has_access) {
+                        Ok(role) => role,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
 
-                gen.extend(pausable_ts);
-            }
+                    allowlist(item_ident.clone(), role, has_ownable)
+                }
+                "blocklist" => {
+                    let role = match blocklist_role(arg, has_access) {
+                        Ok(role) => role,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    blocklist(item_ident.clone(), role, has_ownable)
+                }
+                "counters" => {
+                    let names = match counter_names(arg) {
+                        Ok(names) => names,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    counters(item_ident.clone(), names)
+                }
+                "escrow" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("Escrow does not accept arguments");}.into();
+                    }
+
+                    escrow(item_ident.clone())
+                }
+                "feecollector" => {
+                    let config = match fee_collector_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    fee_collector(item_ident.clone(), config, has_ownable)
+                }
+                "fungibletoken" => {
+                    let minter = match fungible_token_minter(arg, has_access) {
+                        Ok(minter) => minter,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    fungible_token(
+                        item_ident.clone(),
+                        minter,
+                        has_pausable,
+                        has_storage_management,
+                    )
+                }
+                "governance" => {
+                    let config = match governance_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    governance(item_ident.clone(), config)
+                }
+                "multisig" => {
+                    let expiration_ns = match multisig_expiration(arg) {
+                        Ok(expiration_ns) => expiration_ns,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    multisignature(item_ident.clone(), expiration_ns)
+                }
+                "nonfungibletoken" => {
+                    let config = match non_fungible_token_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    non_fungible_token(item_ident.clone(), config, has_pausable)
+                }
+                "nonces" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("Nonces does not accept arguments");}.into();
+                    }
+
+                    nonces(item_ident.clone())
+                }
+                "oracle" => {
+                    let config = match oracle_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    oracle(item_ident.clone(), config)
+                }
+                "ownable" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("Ownable does not accept arguments");}
+                            .into();
+                    }
+
+                    ownable(item_ident.clone())
+                }
+                "pausable" => {
+                    let roles = match pausable_roles(arg, has_access) {
+                        Ok(roles) => roles,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    pausable(item_ident.clone(), roles)
+                }
+                "paymentsplitter" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("PaymentSplitter does not accept arguments");}
+                            .into();
+                    }
+
+                    payment_splitter(item_ident.clone())
+                }
+                "ratelimit" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("RateLimit does not accept arguments");}
+                            .into();
+                    }
+
+                    rate_limiting(item_ident.clone())
+                }
+                "reentrancyguard" => {
+                    if let syn::Meta::List(_) = arg {
+                        return quote! {compile_error!("ReentrancyGuard does not accept arguments");}
+                            .into();
+                    }
+
+                    reentrancy_guard(item_ident.clone())
+                }
+                "storagemanagement" => {
+                    let (min_bytes, max_bytes) = match storage_management_bounds(arg) {
+                        Ok(bounds) => bounds,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    storage_management(item_ident.clone(), min_bytes, max_bytes)
+                }
+                "timelock" => {
+                    let config = match timelock_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    timelock(item_ident.clone(), config, has_ownable)
+                }
+                "upgradable" => {
+                    let config = match upgradable_config(arg, has_access) {
+                        Ok(config) => config,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    upgradable(item_ident.clone(), config, has_ownable)
+                }
+                "vesting" => {
+                    let role = match vesting_role(arg, has_access) {
+                        Ok(role) => role,
+                        Err(err) => return quote! {compile_error!(#err);}.into(),
+                    };
+
+                    vesting(item_ident.clone(), role, has_ownable)
+                }
+                // Migrations need the fields of every other module, so StateMigration goes last
+                "statemigration" => {
+                    state_migration_arg = Some(arg);
+                    continue;
+                }
+                _ => unreachable!(),
+            };
+
+            let field_ts = TokenStream2::from(field_ts);
+
+            fields.extend(field_ts.clone());
+            module_fields.push((name, field_ts));
+
+            gen.extend(module_ts);
+        }
+
+        if let Some(arg) = state_migration_arg {
+            let config = match state_migration_config(arg, &arg_vec) {
+                Ok(config) => config,
+                Err(err) => return quote! {compile_error!(#err);}.into(),
+            };
+
+            let (state_migration_ts, field_ts) =
+                state_migration(item_ident.clone(), config, original_fields, &module_fields);
+
+            fields.extend(TokenStream2::from(field_ts));
+
+            gen.extend(state_migration_ts);
         }
 
         let main_struct = quote! {
@@ -94,3 +369,43 @@ pub fn require(args: TokenStream, item: TokenStream) -> TokenStream {
 pub fn init_macro(input: TokenStream) -> TokenStream {
     init_macro::init_macro(input)
 }
+
+#[proc_macro_attribute]
+pub fn when_not_paused(args: TokenStream, item: TokenStream) -> TokenStream {
+    pause_guard(args, item, false)
+}
+
+#[proc_macro_attribute]
+pub fn when_paused(args: TokenStream, item: TokenStream) -> TokenStream {
+    pause_guard(args, item, true)
+}
+
+#[proc_macro_attribute]
+pub fn non_reentrant(args: TokenStream, item: TokenStream) -> TokenStream {
+    lock_guard(args, item, false)
+}
+
+#[proc_macro_attribute]
+pub fn non_reentrant_callback(args: TokenStream, item: TokenStream) -> TokenStream {
+    lock_guard(args, item, true)
+}
+
+#[proc_macro_attribute]
+pub fn not_blocked(args: TokenStream, item: TokenStream) -> TokenStream {
+    blocklist_guard(args, item)
+}
+
+#[proc_macro_attribute]
+pub fn rate_limit(args: TokenStream, item: TokenStream) -> TokenStream {
+    rate_limit_guard(args, item)
+}
+
+#[proc_macro_attribute]
+pub fn timelocked(args: TokenStream, item: TokenStream) -> TokenStream {
+    timelock_guard(args, item)
+}
+
+#[proc_macro_attribute]
+pub fn multisig(args: TokenStream, item: TokenStream) -> TokenStream {
+    multisig_guard(args, item)
+}
@@ -0,0 +1,377 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{parse_macro_input, FnArg, Ident, ImplItemMethod, Meta, Pat, ReturnType};
+
+use crate::utils::{module_options, u64_option};
+
+pub fn multisig_expiration(meta: &Meta) -> Result<u64, String> {
+    let options = module_options(meta, "Multisig", &["expiration_secs"])?;
+
+    let expiration_ns = u64_option(&options, "expiration_secs", 604_800)?
+        .checked_mul(1_000_000_000)
+        .ok_or_else(|| "expiration_secs is too large".to_string())?;
+
+    if expiration_ns == 0 {
+        return Err("expiration_secs has to be positive".to_string());
+    }
+
+    Ok(expiration_ns)
+}
+
+pub fn multisignature(item_ident: Ident, expiration_ns: u64) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyMultisig {
+            Members,
+            Requests,
+        }
+
+        impl StorageKeyMultisig {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyMultisig::Members => "msm".to_string(),
+                    StorageKeyMultisig::Requests => "msr".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct MultisigRequest {
+            pub method: String,
+            pub args: String,
+            pub created_by: near_sdk::AccountId,
+            pub created_at: u64,
+            pub expires_at: u64,
+            pub confirmations: std::vec::Vec<near_sdk::AccountId>,
+        }
+
+
+        pub trait Multisig {
+            fn create_request(&mut self, method: String, args: String) -> String;
+
+            fn confirm(&mut self, id: String);
+
+            fn revoke_confirmation(&mut self, id: String);
+
+            fn multisig_request(&self, id: String) -> Option<MultisigRequest>;
+
+            fn multisig_requests(&self, from_index: u64, limit: u64) -> std::vec::Vec<(String, MultisigRequest)>;
+
+            fn multisig_members(&self) -> std::vec::Vec<near_sdk::AccountId>;
+
+            fn multisig_threshold(&self) -> u64;
+
+            fn add_multisig_member(&mut self, account_id: near_sdk::AccountId);
+
+            fn remove_multisig_member(&mut self, account_id: near_sdk::AccountId);
+
+            fn set_multisig_threshold(&mut self, threshold: u64);
+        }
+
+
+            #[near_bindgen]
+            impl Multisig for #item_ident {
+
+                fn create_request(&mut self, method: String, args: String) -> String {
+                    let args = near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args)
+                        .unwrap_or_else(|_| near_sdk::env::panic(b"Arguments have to be a JSON object"))
+                        .to_string();
+
+                    let id = Self::multisig_id(&method, &args);
+
+                    if self.multisig_confirm(&id, &method, args) {
+                        self.multisig_execute(&id);
+                    }
+
+                    id
+                }
+
+                fn confirm(&mut self, id: String) {
+                    let request = self.multisig_requests.get(&id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Request {} does not exist", id).as_bytes())
+                    });
+
+                    if near_sdk::env::block_timestamp() >= request.expires_at {
+                        near_sdk::env::panic(format!("Request {} has expired", id).as_bytes())
+                    }
+
+                    if self.multisig_confirm(&id, &request.method, request.args) {
+                        self.multisig_execute(&id);
+                    }
+                }
+
+                fn revoke_confirmation(&mut self, id: String) {
+                    let caller = near_sdk::env::predecessor_account_id();
+
+                    let mut request = self.multisig_requests.get(&id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Request {} does not exist", id).as_bytes())
+                    });
+
+                    let count = request.confirmations.len();
+                    request.confirmations.retain(|account| *account != caller);
+
+                    if request.confirmations.len() == count {
+                        near_sdk::env::panic(
+                            format!("Account {} has not confirmed request {}", caller, id).as_bytes(),
+                        )
+                    }
+
+                    near_sdk::env::log(format!("Request {} is revoked by {}", id, caller).as_bytes());
+
+                    if request.confirmations.is_empty() {
+                        self.multisig_requests.remove(&id);
+                    } else {
+                        self.multisig_requests.insert(&id, &request);
+                    }
+                }
+
+                fn multisig_request(&self, id: String) -> Option<MultisigRequest> {
+                    self.multisig_requests.get(&id)
+                }
+
+                fn multisig_requests(&self, from_index: u64, limit: u64) -> std::vec::Vec<(String, MultisigRequest)> {
+                    self.multisig_requests
+                        .iter()
+                        .skip(from_index as usize)
+                        .take(limit as usize)
+                        .collect()
+                }
+
+                fn multisig_members(&self) -> std::vec::Vec<near_sdk::AccountId> {
+                    self.multisig_members.to_vec()
+                }
+
+                fn multisig_threshold(&self) -> u64 {
+                    self.multisig_threshold
+                }
+
+                // Members and the threshold are changed by the contract itself, i.e. through a confirmed request
+                fn add_multisig_member(&mut self, account_id: near_sdk::AccountId) {
+                    Self::multisig_assert_self();
+
+                    if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                    }
+
+                    if !self.multisig_members.insert(&account_id) {
+                        near_sdk::env::panic(format!("Account {} is already a member", account_id).as_bytes())
+                    }
+
+                    near_sdk::env::log(format!("Account {} is added to the members", account_id).as_bytes())
+                }
+
+                fn remove_multisig_member(&mut self, account_id: near_sdk::AccountId) {
+                    Self::multisig_assert_self();
+
+                    if !self.multisig_members.remove(&account_id) {
+                        near_sdk::env::panic(format!("Account {} is not a member", account_id).as_bytes())
+                    }
+
+                    if self.multisig_members.len() < self.multisig_threshold {
+                        near_sdk::env::panic(b"Members cannot be fewer than the threshold")
+                    }
+
+                    near_sdk::env::log(format!("Account {} is removed from the members", account_id).as_bytes())
+                }
+
+                fn set_multisig_threshold(&mut self, threshold: u64) {
+                    Self::multisig_assert_self();
+
+                    if threshold == 0 || threshold > self.multisig_members.len() {
+                        near_sdk::env::panic(b"Threshold has to be between 1 and the number of members")
+                    }
+
+                    near_sdk::env::log(format!("Threshold is set to {}", threshold).as_bytes());
+
+                    self.multisig_threshold = threshold;
+                }
+            }
+
+            impl #item_ident {
+                pub fn setup_multisig(&mut self, members: std::vec::Vec<near_sdk::AccountId>, threshold: u64) {
+                    for member in members.iter() {
+                        self.multisig_members.insert(member);
+                    }
+
+                    if threshold == 0 || threshold > self.multisig_members.len() {
+                        near_sdk::env::panic(b"Threshold has to be between 1 and the number of members")
+                    }
+
+                    self.multisig_threshold = threshold;
+                }
+
+                fn multisig_assert_self() {
+                    if near_sdk::env::predecessor_account_id() != near_sdk::env::current_account_id() {
+                        near_sdk::env::panic(b"Method is private")
+                    }
+                }
+
+                fn multisig_id(method: &str, args: &str) -> String {
+                    near_sdk::env::sha256(format!("{}:{}", method, args).as_bytes())
+                        .iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect()
+                }
+
+                // Adds the confirmation of the caller, creating the request if needed.
+                // Returns `true` once the request has enough confirmations
+                fn multisig_confirm(&mut self, id: &str, method: &str, args: String) -> bool {
+                    let caller = near_sdk::env::predecessor_account_id();
+                    let now = near_sdk::env::block_timestamp();
+
+                    if !self.multisig_members.contains(&caller) {
+                        near_sdk::env::panic(format!("Account {} is not a member", caller).as_bytes())
+                    }
+
+                    let mut request = match self.multisig_requests.get(&id.to_string()) {
+                        Some(request) if now < request.expires_at => request,
+                        // An expired request starts over
+                        _ => {
+                            near_sdk::env::log(
+                                format!("Request {} calling {}({}) is created by {}", id, method, args, caller)
+                                    .as_bytes(),
+                            );
+
+                            MultisigRequest {
+                                method: method.to_string(),
+                                args,
+                                created_by: caller.clone(),
+                                created_at: now,
+                                expires_at: now.saturating_add(#expiration_ns),
+                                confirmations: std::vec::Vec::new(),
+                            }
+                        }
+                    };
+
+                    if request.confirmations.contains(&caller) {
+                        near_sdk::env::panic(
+                            format!("Account {} has already confirmed request {}", caller, id).as_bytes(),
+                        )
+                    }
+
+                    request.confirmations.push(caller.clone());
+
+                    // Confirmations of removed members are kept, but do not count
+                    let confirmed = request
+                        .confirmations
+                        .iter()
+                        .filter(|account| self.multisig_members.contains(account))
+                        .count() as u64;
+
+                    near_sdk::env::log(
+                        format!(
+                            "Request {} is confirmed by {} ({}/{})",
+                            id,
+                            caller,
+                            confirmed,
+                            self.multisig_threshold
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.multisig_requests.insert(&id.to_string(), &request);
+
+                    confirmed >= self.multisig_threshold
+                }
+
+                // Removes a confirmed request and calls its method from the contract account
+                fn multisig_execute(&mut self, id: &str) {
+                    let request = self.multisig_requests.remove(&id.to_string()).unwrap();
+
+                    near_sdk::env::log(format!("Request {} is executed", id).as_bytes());
+
+                    near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                        request.method.into_bytes(),
+                        request.args.into_bytes(),
+                        0,
+                        near_sdk::env::prepaid_gas()
+                            .saturating_sub(near_sdk::env::used_gas())
+                            .saturating_sub(20_000_000_000_000),
+                    );
+                }
+
+                // Calls from the contract account come from confirmed requests. Calls from members confirm
+                // the request for the call and return `true` if it reaches the threshold
+                fn multisig_call(&mut self, method: &str, args: String) -> bool {
+                    if near_sdk::env::predecessor_account_id() == near_sdk::env::current_account_id() {
+                        return true;
+                    }
+
+                    let id = Self::multisig_id(method, &args);
+
+                    if !self.multisig_confirm(&id, method, args) {
+                        return false;
+                    }
+
+                    self.multisig_requests.remove(&id);
+
+                    near_sdk::env::log(format!("Request {} is executed", id).as_bytes());
+
+                    true
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            multisig_members: near_sdk::collections::UnorderedSet<near_sdk::AccountId>,
+            multisig_threshold: u64,
+            multisig_requests: near_sdk::collections::UnorderedMap<String, MultisigRequest>,
+        }
+        .into(),
+    )
+}
+
+pub fn multisig_guard(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if !args.is_empty() {
+        return quote! {compile_error!("multisig does not accept arguments");}.into();
+    }
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("multisig can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    // Confirming returns early, so there is no value to return
+    if let ReturnType::Type(_, _) = method.sig.output {
+        return quote! {compile_error!("multisig methods cannot return values");}.into();
+    }
+
+    let mut names = Vec::new();
+
+    for input in method.sig.inputs.iter() {
+        if let FnArg::Typed(arg) = input {
+            match &*arg.pat {
+                Pat::Ident(pat) => names.push(pat.ident.clone()),
+                _ => return quote! {compile_error!("multisig methods can only take named arguments");}.into(),
+            }
+        }
+    }
+
+    let method_name = method.sig.ident.to_string();
+    let keys = names.iter().map(|name| name.to_string());
+
+    let guard = quote! {
+        if !self.multisig_call(
+            #method_name,
+            near_sdk::serde_json::json!({#(#keys: &#names),*}).to_string(),
+        ) {
+            return;
+        }
+    };
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
@@ -0,0 +1 @@
+pub mod non_fungible_token_near;
@@ -0,0 +1,688 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{bool_option, module_options, string_option};
+
+pub struct NonFungibleTokenConfig {
+    minter: Option<String>,
+    enumeration: bool,
+    approval: bool,
+}
+
+pub fn non_fungible_token_config(
+    meta: &Meta,
+    has_access: bool,
+) -> Result<NonFungibleTokenConfig, String> {
+    let options = module_options(
+        meta,
+        "NonFungibleToken",
+        &["minter", "enumeration", "approval"],
+    )?;
+
+    let minter = if has_access {
+        Some(string_option(&options, "minter", "minter")?)
+    } else if options.contains_key("minter") {
+        return Err(
+            "NonFungibleToken roles can only be configured together with Access".to_string(),
+        );
+    } else {
+        None
+    };
+
+    Ok(NonFungibleTokenConfig {
+        minter,
+        enumeration: bool_option(&options, "enumeration", true)?,
+        approval: bool_option(&options, "approval", true)?,
+    })
+}
+
+pub fn non_fungible_token(
+    item_ident: Ident,
+    config: NonFungibleTokenConfig,
+    has_pausable: bool,
+) -> (TokenStream, TokenStream) {
+    let pause_guard = if has_pausable {
+        quote! {self.when_not_paused();}
+    } else {
+        quote! {}
+    };
+
+    let minter_ts = match config.minter {
+        Some(minter) => quote! {
+            #[near_bindgen]
+            impl #item_ident {
+                // The attached deposit pays for the storage of the token
+                #[payable]
+                pub fn nft_mint_token(&mut self, token_id: String, receiver_id: near_sdk::AccountId, metadata: Option<TokenMetadata>) -> Token {
+                    self.assert_role(&#minter.to_string());
+                    #pause_guard
+
+                    let storage_before = near_sdk::env::storage_usage();
+                    let token = self.nft_mint(token_id, &receiver_id, metadata);
+                    Self::nft_refund_deposit(storage_before);
+
+                    token
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    // Approvals are cleared on transfer. Without approvals, only the owner can transfer
+    let transfer_approvals = if config.approval {
+        quote! {
+            let approvals = self.nft_approvals.remove(token_id);
+
+            if sender_id != &owner_id {
+                let approved_id = approvals
+                    .as_ref()
+                    .and_then(|approvals| approvals.get(sender_id).copied())
+                    .unwrap_or_else(|| {
+                        near_sdk::env::panic(
+                            format!("Sender {} is not approved for token {}", sender_id, token_id).as_bytes(),
+                        )
+                    });
+
+                if approval_id.map_or(false, |approval_id| approval_id != approved_id) {
+                    near_sdk::env::panic(
+                        format!("Approval of {} for token {} has changed", sender_id, token_id).as_bytes(),
+                    )
+                }
+            }
+
+            approvals
+        }
+    } else {
+        quote! {
+            let _ = approval_id;
+
+            if sender_id != &owner_id {
+                near_sdk::env::panic(
+                    format!("Sender {} is not the owner of token {}", sender_id, token_id).as_bytes(),
+                )
+            }
+
+            None
+        }
+    };
+
+    let token_approvals = if config.approval {
+        quote! {Some(self.nft_approvals.get(&token_id).unwrap_or_default())}
+    } else {
+        quote! {None}
+    };
+
+    let approval_ts = if config.approval {
+        quote! {
+            pub trait NonFungibleTokenApproval {
+                fn nft_approve(&mut self, token_id: String, account_id: near_sdk::AccountId, msg: Option<String>) -> Option<near_sdk::Promise>;
+
+                fn nft_revoke(&mut self, token_id: String, account_id: near_sdk::AccountId);
+
+                fn nft_revoke_all(&mut self, token_id: String);
+
+                fn nft_is_approved(&self, token_id: String, approved_account_id: near_sdk::AccountId, approval_id: Option<u64>) -> bool;
+            }
+
+            #[near_bindgen]
+            impl NonFungibleTokenApproval for #item_ident {
+
+                // The attached deposit pays for the storage of the approval
+                #[payable]
+                fn nft_approve(&mut self, token_id: String, account_id: near_sdk::AccountId, msg: Option<String>) -> Option<near_sdk::Promise> {
+                    #pause_guard
+
+                    if near_sdk::env::attached_deposit() == 0 {
+                        near_sdk::env::panic(b"Requires attached deposit of at least 1 yoctoNEAR")
+                    }
+
+                    let owner_id = self.nft_assert_owner(&token_id);
+                    let storage_before = near_sdk::env::storage_usage();
+
+                    let approval_id = self.nft_next_approval_ids.get(&token_id).unwrap_or(0);
+
+                    let mut approvals = self.nft_approvals.get(&token_id).unwrap_or_default();
+                    approvals.insert(account_id.clone(), approval_id);
+
+                    self.nft_approvals.insert(&token_id, &approvals);
+                    self.nft_next_approval_ids.insert(&token_id, &(approval_id + 1));
+
+                    Self::nft_refund_deposit(storage_before);
+
+                    msg.map(|msg| {
+                        near_sdk::Promise::new(account_id).function_call(
+                            b"nft_on_approve".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "token_id": token_id,
+                                "owner_id": owner_id,
+                                "approval_id": approval_id,
+                                "msg": msg,
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            near_sdk::env::prepaid_gas()
+                                .saturating_sub(near_sdk::env::used_gas())
+                                .saturating_sub(Self::NFT_GAS_FOR_APPROVE),
+                        )
+                    })
+                }
+
+                #[payable]
+                fn nft_revoke(&mut self, token_id: String, account_id: near_sdk::AccountId) {
+                    Self::nft_assert_one_yocto();
+                    self.nft_assert_owner(&token_id);
+
+                    if let Some(mut approvals) = self.nft_approvals.get(&token_id) {
+                        if approvals.remove(&account_id).is_some() {
+                            if approvals.is_empty() {
+                                self.nft_approvals.remove(&token_id);
+                            } else {
+                                self.nft_approvals.insert(&token_id, &approvals);
+                            }
+                        }
+                    }
+                }
+
+                #[payable]
+                fn nft_revoke_all(&mut self, token_id: String) {
+                    Self::nft_assert_one_yocto();
+                    self.nft_assert_owner(&token_id);
+
+                    self.nft_approvals.remove(&token_id);
+                }
+
+                fn nft_is_approved(&self, token_id: String, approved_account_id: near_sdk::AccountId, approval_id: Option<u64>) -> bool {
+                    self.nft_owner(&token_id);
+
+                    match self.nft_approvals.get(&token_id).and_then(|approvals| approvals.get(&approved_account_id).copied()) {
+                        Some(approved_id) => approval_id.map_or(true, |approval_id| approval_id == approved_id),
+                        None => false,
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let enumeration_move = if config.enumeration {
+        quote! {
+            if let Some(from) = from {
+                let mut tokens = self.nft_tokens_per_owner.get(from).unwrap();
+                tokens.remove(token_id);
+
+                if tokens.is_empty() {
+                    self.nft_tokens_per_owner.remove(from);
+                } else {
+                    self.nft_tokens_per_owner.insert(from, &tokens);
+                }
+            }
+
+            let mut tokens = self.nft_tokens_per_owner.get(to).unwrap_or_else(|| {
+                near_sdk::collections::UnorderedSet::new(
+                    [
+                        StorageKeyNonFungibleToken::OwnerTokens.into_bytes(),
+                        near_sdk::env::sha256(to.as_bytes()),
+                    ]
+                    .concat(),
+                )
+            });
+
+            tokens.insert(token_id);
+            self.nft_tokens_per_owner.insert(to, &tokens);
+        }
+    } else {
+        quote! {
+            let _ = from;
+        }
+    };
+
+    let enumeration_mint = if config.enumeration {
+        quote! {self.nft_token_ids.insert(&token_id);}
+    } else {
+        quote! {}
+    };
+
+    let enumeration_ts = if config.enumeration {
+        quote! {
+            pub trait NonFungibleTokenEnumeration {
+                fn nft_total_supply(&self) -> near_sdk::json_types::U128;
+
+                fn nft_tokens(&self, from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> std::vec::Vec<Token>;
+
+                fn nft_supply_for_owner(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+
+                fn nft_tokens_for_owner(&self, account_id: near_sdk::AccountId, from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> std::vec::Vec<Token>;
+            }
+
+            #[near_bindgen]
+            impl NonFungibleTokenEnumeration for #item_ident {
+
+                fn nft_total_supply(&self) -> near_sdk::json_types::U128 {
+                    (self.nft_token_ids.len() as u128).into()
+                }
+
+                fn nft_tokens(&self, from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> std::vec::Vec<Token> {
+                    let (from_index, limit) = Self::nft_page(from_index, limit);
+
+                    self.nft_token_ids
+                        .iter()
+                        .skip(from_index)
+                        .take(limit)
+                        .map(|token_id| self.nft_token(token_id).unwrap())
+                        .collect()
+                }
+
+                fn nft_supply_for_owner(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+                    let supply = self.nft_tokens_per_owner
+                        .get(&account_id)
+                        .map_or(0, |tokens| tokens.len());
+
+                    (supply as u128).into()
+                }
+
+                fn nft_tokens_for_owner(&self, account_id: near_sdk::AccountId, from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> std::vec::Vec<Token> {
+                    let (from_index, limit) = Self::nft_page(from_index, limit);
+
+                    match self.nft_tokens_per_owner.get(&account_id) {
+                        Some(tokens) => tokens
+                            .iter()
+                            .skip(from_index)
+                            .take(limit)
+                            .map(|token_id| self.nft_token(token_id).unwrap())
+                            .collect(),
+                        None => std::vec::Vec::new(),
+                    }
+                }
+            }
+
+            impl #item_ident {
+                fn nft_page(from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> (usize, usize) {
+                    if limit == Some(0) {
+                        near_sdk::env::panic(b"Cannot provide limit of 0")
+                    }
+
+                    (
+                        from_index.map_or(0, |from_index| from_index.0 as usize),
+                        limit.map_or(usize::MAX, |limit| limit as usize),
+                    )
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyNonFungibleToken {
+            OwnerById,
+            TokenMetadata,
+            Metadata,
+            TokenIds,
+            TokensPerOwner,
+            OwnerTokens,
+            Approvals,
+            NextApprovalIds,
+        }
+
+        impl StorageKeyNonFungibleToken {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyNonFungibleToken::OwnerById => "nfo".to_string(),
+                    StorageKeyNonFungibleToken::TokenMetadata => "nfd".to_string(),
+                    StorageKeyNonFungibleToken::Metadata => "nfm".to_string(),
+                    StorageKeyNonFungibleToken::TokenIds => "nft".to_string(),
+                    StorageKeyNonFungibleToken::TokensPerOwner => "nfp".to_string(),
+                    StorageKeyNonFungibleToken::OwnerTokens => "nfs".to_string(),
+                    StorageKeyNonFungibleToken::Approvals => "nfa".to_string(),
+                    StorageKeyNonFungibleToken::NextApprovalIds => "nfn".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct NFTContractMetadata {
+            pub spec: String,
+            pub name: String,
+            pub symbol: String,
+            pub icon: Option<String>,
+            pub base_uri: Option<String>,
+            pub reference: Option<String>,
+            pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq, Default)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct TokenMetadata {
+            pub title: Option<String>,
+            pub description: Option<String>,
+            pub media: Option<String>,
+            pub media_hash: Option<near_sdk::json_types::Base64VecU8>,
+            pub copies: Option<u64>,
+            pub issued_at: Option<String>,
+            pub expires_at: Option<String>,
+            pub starts_at: Option<String>,
+            pub updated_at: Option<String>,
+            pub extra: Option<String>,
+            pub reference: Option<String>,
+            pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+        }
+
+        #[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct Token {
+            pub token_id: String,
+            pub owner_id: near_sdk::AccountId,
+            pub metadata: Option<TokenMetadata>,
+            pub approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>,
+        }
+
+
+        pub trait NonFungibleToken {
+            fn nft_transfer(&mut self, receiver_id: near_sdk::AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>);
+
+            fn nft_transfer_call(&mut self, receiver_id: near_sdk::AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>, msg: String) -> near_sdk::PromiseOrValue<bool>;
+
+            fn nft_token(&self, token_id: String) -> Option<Token>;
+
+            fn nft_resolve_transfer(&mut self, previous_owner_id: near_sdk::AccountId, receiver_id: near_sdk::AccountId, token_id: String, approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>) -> bool;
+
+            fn nft_metadata(&self) -> NFTContractMetadata;
+        }
+
+
+            #[near_bindgen]
+            impl NonFungibleToken for #item_ident {
+
+                #[payable]
+                fn nft_transfer(&mut self, receiver_id: near_sdk::AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>) {
+                    Self::nft_assert_one_yocto();
+                    #pause_guard
+
+                    let sender_id = near_sdk::env::predecessor_account_id();
+
+                    self.nft_transfer_internal(&sender_id, &receiver_id, &token_id, approval_id, memo);
+                }
+
+                #[payable]
+                fn nft_transfer_call(&mut self, receiver_id: near_sdk::AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>, msg: String) -> near_sdk::PromiseOrValue<bool> {
+                    Self::nft_assert_one_yocto();
+                    #pause_guard
+
+                    let sender_id = near_sdk::env::predecessor_account_id();
+
+                    let (previous_owner_id, approvals) =
+                        self.nft_transfer_internal(&sender_id, &receiver_id, &token_id, approval_id, memo);
+
+                    let receiver_gas = near_sdk::env::prepaid_gas()
+                        .checked_sub(Self::NFT_GAS_FOR_TRANSFER_CALL)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Prepaid gas is not enough for nft_transfer_call"));
+
+                    near_sdk::Promise::new(receiver_id.clone())
+                        .function_call(
+                            b"nft_on_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "sender_id": sender_id,
+                                "previous_owner_id": previous_owner_id,
+                                "token_id": token_id,
+                                "msg": msg,
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            receiver_gas,
+                        )
+                        .then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                            b"nft_resolve_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "previous_owner_id": previous_owner_id,
+                                "receiver_id": receiver_id,
+                                "token_id": token_id,
+                                "approved_account_ids": approvals,
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            Self::NFT_GAS_FOR_RESOLVE_TRANSFER,
+                        ))
+                        .into()
+                }
+
+                fn nft_token(&self, token_id: String) -> Option<Token> {
+                    let owner_id = self.nft_owner_by_id.get(&token_id)?;
+
+                    Some(Token {
+                        metadata: self.nft_token_metadata.get(&token_id),
+                        approved_account_ids: #token_approvals,
+                        token_id,
+                        owner_id,
+                    })
+                }
+
+                // Returns `true` if the receiver keeps the token. Not paused, so that pending transfers can be returned
+                #[private]
+                fn nft_resolve_transfer(&mut self, previous_owner_id: near_sdk::AccountId, receiver_id: near_sdk::AccountId, token_id: String, approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>) -> bool {
+                    let keep = match near_sdk::env::promise_result(0) {
+                        near_sdk::PromiseResult::Successful(value) => {
+                            near_sdk::serde_json::from_slice::<bool>(&value).map_or(false, |return_token| !return_token)
+                        }
+                        _ => false,
+                    };
+
+                    if keep {
+                        return true;
+                    }
+
+                    // The receiver may have transferred the token already
+                    if self.nft_owner_by_id.get(&token_id).as_ref() != Some(&receiver_id) {
+                        return true;
+                    }
+
+                    self.nft_move_token(Some(&receiver_id), &previous_owner_id, &token_id);
+
+                    self.nft_approvals.remove(&token_id);
+
+                    if let Some(approvals) = approved_account_ids {
+                        self.nft_approvals.insert(&token_id, &approvals);
+                    }
+
+                    Self::nft_event("nft_transfer", near_sdk::serde_json::json!({
+                        "old_owner_id": receiver_id,
+                        "new_owner_id": previous_owner_id,
+                        "token_ids": [token_id],
+                    }));
+
+                    false
+                }
+
+                fn nft_metadata(&self) -> NFTContractMetadata {
+                    self.nft_metadata
+                        .get()
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Contract metadata is not set"))
+                }
+            }
+
+            impl #item_ident {
+                pub const NFT_GAS_FOR_APPROVE: near_sdk::Gas = 10_000_000_000_000;
+                pub const NFT_GAS_FOR_RESOLVE_TRANSFER: near_sdk::Gas = 5_000_000_000_000;
+                pub const NFT_GAS_FOR_TRANSFER_CALL: near_sdk::Gas = 25_000_000_000_000 + Self::NFT_GAS_FOR_RESOLVE_TRANSFER;
+
+                pub fn setup_non_fungible_token(&mut self, metadata: NFTContractMetadata) {
+                    if metadata.spec != "nft-1.0.0" {
+                        near_sdk::env::panic(b"Contract metadata spec has to be nft-1.0.0")
+                    }
+
+                    if metadata.reference.is_some() != metadata.reference_hash.is_some() {
+                        near_sdk::env::panic(b"Contract metadata reference and reference_hash have to be set together")
+                    }
+
+                    if let Some(reference_hash) = &metadata.reference_hash {
+                        if reference_hash.0.len() != 32 {
+                            near_sdk::env::panic(b"Contract metadata reference_hash has to be 32 bytes")
+                        }
+                    }
+
+                    self.nft_metadata.set(&metadata);
+                }
+
+                pub fn nft_mint(&mut self, token_id: String, owner_id: &near_sdk::AccountId, metadata: Option<TokenMetadata>) -> Token {
+                    if self.nft_owner_by_id.get(&token_id).is_some() {
+                        near_sdk::env::panic(format!("Token {} already exists", token_id).as_bytes())
+                    }
+
+                    if !near_sdk::env::is_valid_account_id(owner_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", owner_id).as_bytes())
+                    }
+
+                    self.nft_move_token(None, owner_id, &token_id);
+
+                    if let Some(metadata) = &metadata {
+                        self.nft_token_metadata.insert(&token_id, metadata);
+                    }
+
+                    #enumeration_mint
+
+                    Self::nft_event("nft_mint", near_sdk::serde_json::json!({
+                        "owner_id": owner_id,
+                        "token_ids": [token_id],
+                    }));
+
+                    self.nft_token(token_id).unwrap()
+                }
+
+                fn nft_assert_one_yocto() {
+                    if near_sdk::env::attached_deposit() != 1 {
+                        near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+                    }
+                }
+
+                fn nft_owner(&self, token_id: &String) -> near_sdk::AccountId {
+                    self.nft_owner_by_id.get(token_id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Token {} does not exist", token_id).as_bytes())
+                    })
+                }
+
+                fn nft_assert_owner(&self, token_id: &String) -> near_sdk::AccountId {
+                    let owner_id = self.nft_owner(token_id);
+                    let caller = near_sdk::env::predecessor_account_id();
+
+                    if caller != owner_id {
+                        near_sdk::env::panic(
+                            format!("Sender {} is not the owner of token {}", caller, token_id).as_bytes(),
+                        )
+                    }
+
+                    owner_id
+                }
+
+                // Charges the storage added since `storage_before` to the attached deposit and refunds the rest
+                fn nft_refund_deposit(storage_before: near_sdk::StorageUsage) {
+                    let required = near_sdk::env::storage_usage().saturating_sub(storage_before) as near_sdk::Balance
+                        * near_sdk::env::storage_byte_cost();
+                    let attached = near_sdk::env::attached_deposit();
+
+                    if attached < required {
+                        near_sdk::env::panic(
+                            format!("Must attach {} yoctoNEAR to cover storage", required).as_bytes(),
+                        )
+                    }
+
+                    if attached > required {
+                        near_sdk::Promise::new(near_sdk::env::predecessor_account_id()).transfer(attached - required);
+                    }
+                }
+
+                fn nft_move_token(&mut self, from: Option<&near_sdk::AccountId>, to: &near_sdk::AccountId, token_id: &String) {
+                    self.nft_owner_by_id.insert(token_id, to);
+
+                    #enumeration_move
+                }
+
+                // Returns the previous owner and the approvals cleared by the transfer
+                fn nft_transfer_internal(&mut self, sender_id: &near_sdk::AccountId, receiver_id: &near_sdk::AccountId, token_id: &String, approval_id: Option<u64>, memo: Option<String>) -> (near_sdk::AccountId, Option<std::collections::HashMap<near_sdk::AccountId, u64>>) {
+                    let owner_id = self.nft_owner(token_id);
+
+                    let approvals: Option<std::collections::HashMap<near_sdk::AccountId, u64>> = {
+                        #transfer_approvals
+                    };
+
+                    if &owner_id == receiver_id {
+                        near_sdk::env::panic(b"The token owner and the receiver should be different")
+                    }
+
+                    if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+                    }
+
+                    self.nft_move_token(Some(&owner_id), receiver_id, token_id);
+
+                    let authorized_id = if sender_id != &owner_id {
+                        Some(sender_id)
+                    } else {
+                        None
+                    };
+
+                    Self::nft_event("nft_transfer", near_sdk::serde_json::json!({
+                        "authorized_id": authorized_id,
+                        "old_owner_id": owner_id,
+                        "new_owner_id": receiver_id,
+                        "token_ids": [token_id],
+                        "memo": memo,
+                    }));
+
+                    (owner_id, approvals)
+                }
+
+                // Logs a NEP-297 event, leaving out empty optional fields
+                fn nft_event(event: &str, mut data: near_sdk::serde_json::Value) {
+                    if let Some(data) = data.as_object_mut() {
+                        data.retain(|_, value| !value.is_null());
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "EVENT_JSON:{}",
+                            near_sdk::serde_json::json!({
+                                "standard": "nep171",
+                                "version": "1.0.0",
+                                "event": event,
+                                "data": [data],
+                            })
+                        )
+                        .as_bytes(),
+                    )
+                }
+            }
+
+            #approval_ts
+
+            #enumeration_ts
+
+            #minter_ts
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            nft_owner_by_id: near_sdk::collections::LookupMap<String, near_sdk::AccountId>,
+            nft_token_metadata: near_sdk::collections::LookupMap<String, TokenMetadata>,
+            nft_metadata: near_sdk::collections::LazyOption<NFTContractMetadata>,
+            nft_token_ids: near_sdk::collections::UnorderedSet<String>,
+            nft_tokens_per_owner: near_sdk::collections::LookupMap<near_sdk::AccountId, near_sdk::collections::UnorderedSet<String>>,
+            nft_approvals: near_sdk::collections::LookupMap<String, std::collections::HashMap<near_sdk::AccountId, u64>>,
+            nft_next_approval_ids: near_sdk::collections::LookupMap<String, u64>,
+        }
+        .into(),
+    )
+}
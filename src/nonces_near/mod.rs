@@ -0,0 +1 @@
+pub mod nonces_near;
@@ -0,0 +1,144 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::Ident;
+
+pub fn nonces(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyNonces {
+            Nonces,
+        }
+
+        impl StorageKeyNonces {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyNonces::Nonces => "nnc".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        // What the signer signs: the contract and the nonce bind the signature to one call on one contract
+        #[derive(near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct NoncePayload<'a, T> {
+            pub contract_id: near_sdk::AccountId,
+            pub signer_id: &'a near_sdk::AccountId,
+            pub nonce: u64,
+            pub message: &'a T,
+        }
+
+
+        pub trait Nonces {
+            fn use_nonce(&mut self, account_id: &near_sdk::AccountId, nonce: u64);
+
+            fn verify_signature(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool;
+
+            fn verify_borsh_payload<T: near_sdk::borsh::BorshSerialize>(&self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) -> bool;
+
+            fn verify_json_payload<T: near_sdk::serde::Serialize>(&self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) -> bool;
+
+            fn use_borsh_payload<T: near_sdk::borsh::BorshSerialize>(&mut self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]);
+
+            fn use_json_payload<T: near_sdk::serde::Serialize>(&mut self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]);
+        }
+
+
+        impl Nonces for #item_ident {
+
+            fn use_nonce(&mut self, account_id: &near_sdk::AccountId, nonce: u64) {
+                let last = self.nonces.get(account_id).unwrap_or(0);
+
+                if nonce <= last {
+                    near_sdk::env::panic(
+                        format!("Nonce {} of {} is already used", nonce, account_id).as_bytes(),
+                    )
+                }
+
+                self.nonces.insert(account_id, &nonce);
+            }
+
+            fn verify_signature(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+                // `Base58PublicKey` prefixes the key with its curve type, 0 for ed25519
+                let public_key = match public_key.len() {
+                    33 if public_key[0] == 0 => &public_key[1..],
+                    _ => public_key,
+                };
+
+                let public_key = match ed25519_dalek::PublicKey::from_bytes(public_key) {
+                    Ok(public_key) => public_key,
+                    Err(_) => return false,
+                };
+
+                let signature = match <ed25519_dalek::Signature as std::convert::TryFrom<&[u8]>>::try_from(signature) {
+                    Ok(signature) => signature,
+                    Err(_) => return false,
+                };
+
+                public_key.verify_strict(message, &signature).is_ok()
+            }
+
+            fn verify_borsh_payload<T: near_sdk::borsh::BorshSerialize>(&self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) -> bool {
+                let payload = NoncePayload {
+                    contract_id: near_sdk::env::current_account_id(),
+                    signer_id,
+                    nonce,
+                    message,
+                };
+
+                let payload = near_sdk::borsh::BorshSerialize::try_to_vec(&payload)
+                    .unwrap_or_else(|_| near_sdk::env::panic(b"Failed to serialize the payload"));
+
+                self.verify_signature(public_key, &payload, signature)
+            }
+
+            fn verify_json_payload<T: near_sdk::serde::Serialize>(&self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) -> bool {
+                let payload = NoncePayload {
+                    contract_id: near_sdk::env::current_account_id(),
+                    signer_id,
+                    nonce,
+                    message,
+                };
+
+                let payload = near_sdk::serde_json::to_vec(&payload)
+                    .unwrap_or_else(|_| near_sdk::env::panic(b"Failed to serialize the payload"));
+
+                self.verify_signature(public_key, &payload, signature)
+            }
+
+            fn use_borsh_payload<T: near_sdk::borsh::BorshSerialize>(&mut self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) {
+                if !self.verify_borsh_payload(signer_id, public_key, nonce, message, signature) {
+                    near_sdk::env::panic(format!("Signature of {} is invalid", signer_id).as_bytes())
+                }
+
+                self.use_nonce(signer_id, nonce)
+            }
+
+            fn use_json_payload<T: near_sdk::serde::Serialize>(&mut self, signer_id: &near_sdk::AccountId, public_key: &[u8], nonce: u64, message: &T, signature: &[u8]) {
+                if !self.verify_json_payload(signer_id, public_key, nonce, message, signature) {
+                    near_sdk::env::panic(format!("Signature of {} is invalid", signer_id).as_bytes())
+                }
+
+                self.use_nonce(signer_id, nonce)
+            }
+        }
+
+        #[near_bindgen]
+        impl #item_ident {
+            pub fn nonce(&self, account_id: near_sdk::AccountId) -> u64 {
+                self.nonces.get(&account_id).unwrap_or(0)
+            }
+        }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {nonces: near_sdk::collections::LookupMap<near_sdk::AccountId, u64>,}.into(),
+    )
+}
@@ -0,0 +1 @@
+pub mod oracle_near;
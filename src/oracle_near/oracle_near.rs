@@ -0,0 +1,239 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{module_options, string_option, u64_option};
+
+pub struct OracleConfig {
+    reporter: String,
+    min_reports: u64,
+    report_ttl_ns: u64,
+}
+
+pub fn oracle_config(meta: &Meta, has_access: bool) -> Result<OracleConfig, String> {
+    let options = module_options(
+        meta,
+        "Oracle",
+        &["reporter", "min_reports", "report_ttl_secs"],
+    )?;
+
+    if !has_access {
+        return Err("Oracle requires Access".to_string());
+    }
+
+    let min_reports = u64_option(&options, "min_reports", 1)?;
+
+    if min_reports == 0 {
+        return Err("min_reports has to be positive".to_string());
+    }
+
+    let report_ttl_ns = u64_option(&options, "report_ttl_secs", 3_600)?
+        .checked_mul(1_000_000_000)
+        .ok_or_else(|| "report_ttl_secs is too large".to_string())?;
+
+    Ok(OracleConfig {
+        reporter: string_option(&options, "reporter", "reporter")?,
+        min_reports,
+        report_ttl_ns,
+    })
+}
+
+pub fn oracle(item_ident: Ident, config: OracleConfig) -> (TokenStream, TokenStream) {
+    let reporter = config.reporter;
+    let min_reports = config.min_reports;
+    let report_ttl_ns = config.report_ttl_ns;
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyOracle {
+            Assets,
+        }
+
+        impl StorageKeyOracle {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyOracle::Assets => "orc".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct OraclePrice {
+            pub value: near_sdk::json_types::U128,
+            pub decimals: u8,
+            pub timestamp: u64,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct OracleReport {
+            pub reporter: near_sdk::AccountId,
+            pub value: near_sdk::json_types::U128,
+            pub timestamp: u64,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct OracleAsset {
+            pub decimals: u8,
+            // Median of the latest reports, `None` until there are enough of them
+            pub price: Option<OraclePrice>,
+            // Latest report of each reporter
+            pub reports: std::vec::Vec<OracleReport>,
+        }
+
+
+        pub trait Oracle {
+            fn report_price(&mut self, asset: String, value: near_sdk::json_types::U128, decimals: u8);
+
+            fn get_price(&self, asset: String) -> Option<OraclePrice>;
+
+            fn get_fresh_price(&self, asset: String, max_age_secs: u64) -> OraclePrice;
+
+            fn oracle_reports(&self, asset: String) -> std::vec::Vec<OracleReport>;
+
+            fn oracle_assets(&self) -> std::vec::Vec<String>;
+        }
+
+
+            #[near_bindgen]
+            impl Oracle for #item_ident {
+
+                fn report_price(&mut self, asset: String, value: near_sdk::json_types::U128, decimals: u8) {
+                    let reporter = near_sdk::env::predecessor_account_id();
+
+                    self.check_role(&#reporter.to_string(), &reporter);
+
+                    let now = near_sdk::env::block_timestamp();
+
+                    let mut oracle_asset = self.oracle_assets.get(&asset).unwrap_or(OracleAsset {
+                        decimals,
+                        price: None,
+                        reports: std::vec::Vec::new(),
+                    });
+
+                    if oracle_asset.decimals != decimals {
+                        near_sdk::env::panic(
+                            format!("Asset {} has {} decimals", asset, oracle_asset.decimals).as_bytes(),
+                        )
+                    }
+
+                    oracle_asset.reports.retain(|report| report.reporter != reporter);
+                    oracle_asset.reports.push(OracleReport {
+                        reporter: reporter.clone(),
+                        value,
+                        timestamp: now,
+                    });
+
+                    // Reports older than the TTL are dropped, e.g. of reporters that stopped reporting,
+                    // and so are reports of reporters whose role was revoked
+                    oracle_asset.reports.retain(|report| {
+                        now - report.timestamp < Self::ORACLE_REPORT_TTL
+                            && self.has_role(&#reporter.to_string(), &report.reporter)
+                    });
+
+                    // Without enough reports the previous price is dropped as well, as it was aggregated from
+                    // reports that are gone now
+                    oracle_asset.price = Self::oracle_aggregate(&oracle_asset.reports, decimals);
+
+                    self.oracle_assets.insert(&asset, &oracle_asset);
+
+                    near_sdk::env::log(format!("Price of {} is reported as {} by {}", asset, value.0, reporter).as_bytes());
+                }
+
+                // The stored price is aggregated from the stored reports. If a reporter was revoked since, the
+                // price is aggregated again from the reports of the remaining reporters
+                fn get_price(&self, asset: String) -> Option<OraclePrice> {
+                    let oracle_asset = self.oracle_assets.get(&asset)?;
+                    let reports = self.oracle_reports(asset);
+
+                    if reports.len() == oracle_asset.reports.len() {
+                        oracle_asset.price
+                    } else {
+                        Self::oracle_aggregate(&reports, oracle_asset.decimals)
+                    }
+                }
+
+                fn get_fresh_price(&self, asset: String, max_age_secs: u64) -> OraclePrice {
+                    let price = self
+                        .get_price(asset.clone())
+                        .unwrap_or_else(|| near_sdk::env::panic(format!("There is no price of {}", asset).as_bytes()));
+
+                    let age = near_sdk::env::block_timestamp() - price.timestamp;
+
+                    if age > max_age_secs.saturating_mul(1_000_000_000) {
+                        near_sdk::env::panic(
+                            format!("Price of {} is stale, it is {} seconds old", asset, age / 1_000_000_000).as_bytes(),
+                        )
+                    }
+
+                    price
+                }
+
+                fn oracle_reports(&self, asset: String) -> std::vec::Vec<OracleReport> {
+                    self.oracle_assets
+                        .get(&asset)
+                        .map(|oracle_asset| oracle_asset.reports)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|report| self.has_role(&#reporter.to_string(), &report.reporter))
+                        .collect()
+                }
+
+                fn oracle_assets(&self) -> std::vec::Vec<String> {
+                    self.oracle_assets.keys().collect()
+                }
+            }
+
+            impl #item_ident {
+                pub const ORACLE_MIN_REPORTS: u64 = #min_reports;
+                pub const ORACLE_REPORT_TTL: u64 = #report_ttl_ns;
+
+                fn oracle_aggregate(reports: &[OracleReport], decimals: u8) -> Option<OraclePrice> {
+                    if (reports.len() as u64) < Self::ORACLE_MIN_REPORTS {
+                        return None;
+                    }
+
+                    Some(OraclePrice {
+                        value: Self::oracle_median(reports).into(),
+                        decimals,
+                        // The oldest report, so the price is not fresher than any of its reports
+                        timestamp: reports.iter().map(|report| report.timestamp).min().unwrap(),
+                    })
+                }
+
+                // Mean of the two middle values for an even number of reports, rounded down
+                fn oracle_median(reports: &[OracleReport]) -> near_sdk::Balance {
+                    let mut values: std::vec::Vec<near_sdk::Balance> = reports.iter().map(|report| report.value.0).collect();
+                    values.sort_unstable();
+
+                    let middle = values.len() / 2;
+
+                    if values.len() % 2 == 1 {
+                        values[middle]
+                    } else {
+                        let (a, b) = (values[middle - 1], values[middle]);
+
+                        // Halved first to avoid overflowing `a + b`
+                        a / 2 + b / 2 + (a % 2 + b % 2) / 2
+                    }
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            oracle_assets: near_sdk::collections::UnorderedMap<String, OracleAsset>,
+        }
+        .into(),
+    )
+}
@@ -0,0 +1 @@
+pub mod ownable_near;
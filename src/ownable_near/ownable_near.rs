@@ -0,0 +1,89 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::Ident;
+
+pub fn ownable(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub trait Ownable {
+            fn owner(&self) -> near_sdk::AccountId;
+
+            fn pending_owner(&self) -> Option<near_sdk::AccountId>;
+
+            fn assert_owner(&self);
+
+            fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId);
+
+            fn accept_ownership(&mut self);
+
+            fn renounce_ownership(&mut self);
+        }
+
+
+            #[near_bindgen]
+            impl Ownable for #item_ident {
+
+                fn owner(&self) -> near_sdk::AccountId {
+                    self.owner.clone()
+                }
+
+                fn pending_owner(&self) -> Option<near_sdk::AccountId> {
+                    self.pending_owner.clone()
+                }
+
+                fn assert_owner(&self) {
+                    let caller = near_sdk::env::predecessor_account_id();
+
+                    if self.owner.is_empty() || caller != self.owner {
+                        near_sdk::env::panic(format!("Account {} is not the owner", caller).as_bytes())
+                    }
+                }
+
+                fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId) {
+                    self.assert_owner();
+
+                    if !near_sdk::env::is_valid_account_id(new_owner.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", new_owner).as_bytes())
+                    }
+
+                    near_sdk::env::log(
+                        format!("Ownership transfer from {} to {} is started", self.owner, new_owner)
+                            .as_bytes(),
+                    );
+
+                    self.pending_owner = Some(new_owner);
+                }
+
+                fn accept_ownership(&mut self) {
+                    let caller = near_sdk::env::predecessor_account_id();
+
+                    if self.pending_owner.as_ref() != Some(&caller) {
+                        near_sdk::env::panic(format!("Account {} is not the pending owner", caller).as_bytes())
+                    }
+
+                    near_sdk::env::log(
+                        format!("Ownership is transferred from {} to {}", self.owner, caller).as_bytes(),
+                    );
+
+                    self.owner = caller;
+                    self.pending_owner = None;
+                }
+
+                fn renounce_ownership(&mut self) {
+                    self.assert_owner();
+
+                    near_sdk::env::log(format!("Ownership is renounced by {}", self.owner).as_bytes());
+
+                    self.owner = String::new();
+                    self.pending_owner = None;
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {owner: near_sdk::AccountId, pending_owner: Option<near_sdk::AccountId>,}.into(),
+    )
+}
@@ -1,18 +1,115 @@
 use proc_macro::TokenStream;
 
 use quote::quote;
-use syn::Ident;
+use syn::{parse_macro_input, Ident, ImplItemMethod, LitStr, Meta};
+
+use crate::utils::{module_options, string_option};
+
+pub fn pausable_roles(meta: &Meta, has_access: bool) -> Result<Option<(String, String)>, String> {
+    let options = module_options(meta, "Pausable", &["pauser", "unpauser"])?;
+
+    if !has_access {
+        if !options.is_empty() {
+            return Err("Pausable roles can only be configured together with Access".to_string());
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some((
+        string_option(&options, "pauser", "pauser")?,
+        string_option(&options, "unpauser", "unpauser")?,
+    )))
+}
+
+pub fn pausable(item_ident: Ident, roles: Option<(String, String)>) -> (TokenStream, TokenStream) {
+    let role_ts = match roles {
+        Some((pauser, unpauser)) => quote! {
+            #[near_bindgen]
+            impl #item_ident {
+                pub fn pause(&mut self, reason: Option<String>) {
+                    self.assert_role(&#pauser.to_string());
+                    Pausable::pause(self, reason)
+                }
+
+                pub fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+                    self.assert_role(&#pauser.to_string());
+                    Pausable::pause_for(self, duration_ns, reason)
+                }
+
+                pub fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+                    self.assert_role(&#pauser.to_string());
+                    Pausable::pause_until(self, timestamp, reason)
+                }
+
+                pub fn unpause(&mut self) {
+                    self.assert_role(&#unpauser.to_string());
+                    Pausable::unpause(self)
+                }
+
+                pub fn pause_feature(&mut self, feature: String) {
+                    self.assert_role(&#pauser.to_string());
+                    Pausable::pause_feature(self, &feature)
+                }
+
+                pub fn unpause_feature(&mut self, feature: String) {
+                    self.assert_role(&#unpauser.to_string());
+                    Pausable::unpause_feature(self, &feature)
+                }
+            }
+        },
+        None => quote! {},
+    };
 
-pub fn pausable(item_ident: Ident) -> (TokenStream, TokenStream) {
     let core_ts = quote! {
 
+        pub enum StorageKeyPausable {
+            Info,
+            Features,
+            FeatureSet,
+        }
+
+        impl StorageKeyPausable {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyPausable::Info => "pai".to_string(),
+                    StorageKeyPausable::Features => "paf".to_string(),
+                    StorageKeyPausable::FeatureSet => "pas".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct PauseInfo {
+            pub paused_by: near_sdk::AccountId,
+            pub paused_at: u64,
+            pub reason: Option<String>,
+            pub expires_at: Option<u64>,
+        }
+
 
         pub trait Pausable {
 
         fn toggle_pause(&mut self);
-        fn pause(&mut self);
+        fn pause(&mut self, reason: Option<String>);
+        fn pause_for(&mut self, duration_ns: u64, reason: Option<String>);
+        fn pause_until(&mut self, timestamp: u64, reason: Option<String>);
+        fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>);
         fn unpause(&mut self);
+        fn is_paused(&self) -> bool;
         fn when_not_paused(&self);
+        fn pause_feature(&mut self, feature: &str);
+        fn unpause_feature(&mut self, feature: &str);
+        fn is_feature_paused(&self, feature: &str) -> bool;
+        fn when_not_paused_feature(&self, feature: &str);
+        fn when_paused(&self);
+        fn when_paused_feature(&self, feature: &str);
 
     }
 
@@ -22,37 +119,207 @@ pub fn pausable(item_ident: Ident) -> (TokenStream, TokenStream) {
 
             fn toggle_pause(&mut self) {
 
-                if !self.pause_status {
-                    self.pause()
+                if !self.is_paused() {
+                    Pausable::pause(self, None)
                 } else {
-                    self.unpause()
+                    Pausable::unpause(self)
+                }
+
+            }
+
+            fn pause(&mut self, reason: Option<String>) {
+                self.pause_with_expiry(reason, None)
+            }
+
+            fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+                let expires_at = near_sdk::env::block_timestamp()
+                    .checked_add(duration_ns)
+                    .unwrap_or_else(|| near_sdk::env::panic(b"Pause duration overflow"));
+
+                Pausable::pause_until(self, expires_at, reason)
+            }
+
+            fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+                if timestamp <= near_sdk::env::block_timestamp() {
+                    near_sdk::env::panic(b"Pause deadline must be in the future")
                 }
 
+                self.pause_with_expiry(reason, Some(timestamp))
             }
 
-            fn pause(&mut self) {
+            fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>) {
+
+                    if self.is_paused() {
+                        near_sdk::env::panic(b"The system is already paused")
+                    }
+
+                    let info = PauseInfo {
+                        paused_by: near_sdk::env::predecessor_account_id(),
+                        paused_at: near_sdk::env::block_timestamp(),
+                        reason,
+                        expires_at,
+                    };
+
+                    near_sdk::env::log(
+                        format!(
+                            "The system is paused by {} at {} until {}. Reason: {}",
+                            info.paused_by,
+                            info.paused_at,
+                            info.expires_at.map_or("unpaused".to_string(), |t| t.to_string()),
+                            info.reason.as_deref().unwrap_or("not specified")
+                        )
+                        .as_bytes(),
+                    );
 
                     self.pause_status = true;
-                    near_sdk::env::log(b"The system is paused")
+                    Self::pause_record().set(&info);
 
             }
 
             fn unpause(&mut self) {
 
+                    // An expired pause is still stored until it is cleared here, so it can be unpaused as well
+                    if !self.pause_status {
+                        near_sdk::env::panic(b"The system is not paused")
+                    }
+
                     self.pause_status = false;
-                    near_sdk::env::log(b"The system is unpaused")
+                    Self::pause_record().remove();
+
+                    near_sdk::env::log(
+                        format!(
+                            "The system is unpaused by {} at {}",
+                            near_sdk::env::predecessor_account_id(),
+                            near_sdk::env::block_timestamp()
+                        )
+                        .as_bytes(),
+                    )
+
+            }
+
+            fn is_paused(&self) -> bool {
+                if !self.pause_status {
+                    return false;
+                }
 
+                match Self::pause_record().get().and_then(|info| info.expires_at) {
+                    Some(expires_at) => near_sdk::env::block_timestamp() < expires_at,
+                    None => true,
+                }
             }
 
             fn when_not_paused(&self) {
-                if self.pause_status {
+                if self.is_paused() {
                     near_sdk::env::panic(b"Function is paused")
                 }
             }
 
+            fn pause_feature(&mut self, feature: &str) {
+
+                    let mut features = Self::paused_feature_set();
+
+                    if features.insert(&feature.to_string()) {
+                        Self::save_paused_feature_set(&features);
+
+                        near_sdk::env::log(
+                            format!(
+                                "Feature {} is paused by {} at {}",
+                                feature,
+                                near_sdk::env::predecessor_account_id(),
+                                near_sdk::env::block_timestamp()
+                            )
+                            .as_bytes(),
+                        )
+                    }
+
+            }
+
+            fn unpause_feature(&mut self, feature: &str) {
+
+                    let mut features = Self::paused_feature_set();
+
+                    if features.remove(&feature.to_string()) {
+                        Self::save_paused_feature_set(&features);
+
+                        near_sdk::env::log(
+                            format!(
+                                "Feature {} is unpaused by {} at {}",
+                                feature,
+                                near_sdk::env::predecessor_account_id(),
+                                near_sdk::env::block_timestamp()
+                            )
+                            .as_bytes(),
+                        )
+                    }
+
+            }
+
+            fn is_feature_paused(&self, feature: &str) -> bool {
+                self.is_paused() || Self::paused_feature_set().contains(&feature.to_string())
+            }
+
+            fn when_not_paused_feature(&self, feature: &str) {
+                self.when_not_paused();
+
+                if Self::paused_feature_set().contains(&feature.to_string()) {
+                    near_sdk::env::panic(format!("Feature {} is paused", feature).as_bytes())
+                }
+            }
+
+            fn when_paused(&self) {
+                if !self.is_paused() {
+                    near_sdk::env::panic(b"Function is not paused")
+                }
+            }
+
+            fn when_paused_feature(&self, feature: &str) {
+                if !self.is_feature_paused(feature) {
+                    near_sdk::env::panic(format!("Feature {} is not paused", feature).as_bytes())
+                }
+            }
+
+
+        }
+
+        #[near_bindgen]
+        impl #item_ident {
+            pub fn pause_info(&self) -> Option<PauseInfo> {
+                if self.is_paused() {
+                    Self::pause_record().get()
+                } else {
+                    None
+                }
+            }
+
+            pub fn paused_features(&self) -> Vec<String> {
+                Self::paused_feature_set().to_vec()
+            }
+        }
+
+        impl #item_ident {
+            // The record and the paused features are stored under their own keys instead of fields,
+            // so the layout of the main struct stays the same as before they were added
+            fn pause_record() -> near_sdk::collections::LazyOption<PauseInfo> {
+                near_sdk::collections::LazyOption::new(StorageKeyPausable::Info.into_bytes(), None)
+            }
 
+            // The set keeps its length in its own struct, so the struct is stored as well
+            fn paused_feature_set() -> near_sdk::collections::UnorderedSet<String> {
+                near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+                    .get()
+                    .unwrap_or_else(|| {
+                        near_sdk::collections::UnorderedSet::new(StorageKeyPausable::FeatureSet.into_bytes())
+                    })
+            }
+
+            fn save_paused_feature_set(features: &near_sdk::collections::UnorderedSet<String>) {
+                near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+                    .set(features);
+            }
         }
 
+        #role_ts
+
 
         };
 
@@ -61,3 +328,36 @@ pub fn pausable(item_ident: Ident) -> (TokenStream, TokenStream) {
         quote! {pause_status: bool,}.into(),
     )
 }
+
+pub fn pause_guard(args: TokenStream, item: TokenStream, paused: bool) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("Pause guards can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    let guard = if args.is_empty() {
+        if paused {
+            quote! {self.when_paused();}
+        } else {
+            quote! {self.when_not_paused();}
+        }
+    } else {
+        let feature = parse_macro_input!(args as LitStr);
+
+        if feature.value().is_empty() {
+            return quote! {compile_error!("Feature name cannot be empty");}.into();
+        }
+
+        if paused {
+            quote! {self.when_paused_feature(#feature);}
+        } else {
+            quote! {self.when_not_paused_feature(#feature);}
+        }
+    };
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
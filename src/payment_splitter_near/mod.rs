@@ -0,0 +1 @@
+pub mod payment_splitter_near;
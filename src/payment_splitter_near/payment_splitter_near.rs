@@ -0,0 +1,233 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::Ident;
+
+pub fn payment_splitter(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyPaymentSplitter {
+            Shares,
+            Received,
+            Released,
+        }
+
+        impl StorageKeyPaymentSplitter {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyPaymentSplitter::Shares => "sps".to_string(),
+                    StorageKeyPaymentSplitter::Received => "spr".to_string(),
+                    StorageKeyPaymentSplitter::Released => "spl".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait PaymentSplitter {
+            fn splitter_deposit(&mut self);
+
+            fn splitter_release(&mut self, payee: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise;
+
+            fn splitter_on_release(&mut self, payee: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>, amount: near_sdk::json_types::U128) -> bool;
+
+            fn pending_payment(&self, account_id: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+
+            fn released(&self, account_id: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+
+            fn total_received(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+
+            fn splitter_payees(&self) -> std::vec::Vec<(near_sdk::AccountId, u64)>;
+
+            fn splitter_total_shares(&self) -> u64;
+        }
+
+
+            #[near_bindgen]
+            impl PaymentSplitter for #item_ident {
+
+                #[payable]
+                fn splitter_deposit(&mut self) {
+                    let amount = near_sdk::env::attached_deposit();
+
+                    if amount == 0 {
+                        near_sdk::env::panic(b"Requires attached deposit")
+                    }
+
+                    self.splitter_receive(None, amount);
+                }
+
+                // Anyone can release the payment of a payee, it is always sent to the payee.
+                // The release is booked before the transfer and rolled back by the callback if it fails
+                fn splitter_release(&mut self, payee: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise {
+                    if self.splitter_shares.get(&payee).is_none() {
+                        near_sdk::env::panic(format!("Account {} is not a payee", payee).as_bytes())
+                    }
+
+                    let amount = self.splitter_pending(&payee, &token_id);
+
+                    if amount == 0 {
+                        near_sdk::env::panic(format!("Account {} is not due any payment", payee).as_bytes())
+                    }
+
+                    let key = (token_id.clone(), payee.clone());
+                    let released = self.splitter_released.get(&key).unwrap_or(0);
+                    self.splitter_released.insert(&key, &(released + amount));
+
+                    near_sdk::env::log(
+                        format!(
+                            "{} {} is released to {}",
+                            amount,
+                            token_id.as_deref().unwrap_or("yoctoNEAR"),
+                            payee
+                        )
+                        .as_bytes(),
+                    );
+
+                    let transfer = match &token_id {
+                        Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(
+                            b"ft_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "receiver_id": payee,
+                                "amount": near_sdk::json_types::U128(amount),
+                                "memo": "Payment",
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            1,
+                            Self::SPLITTER_GAS_FOR_FT_TRANSFER,
+                        ),
+                        None => near_sdk::Promise::new(payee.clone()).transfer(amount),
+                    };
+
+                    transfer.then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                        b"splitter_on_release".to_vec(),
+                        near_sdk::serde_json::json!({
+                            "payee": payee,
+                            "token_id": token_id,
+                            "amount": near_sdk::json_types::U128(amount),
+                        })
+                        .to_string()
+                        .into_bytes(),
+                        0,
+                        Self::SPLITTER_GAS_FOR_RELEASE_CALLBACK,
+                    ))
+                }
+
+                #[private]
+                fn splitter_on_release(&mut self, payee: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>, amount: near_sdk::json_types::U128) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        return true;
+                    }
+
+                    let key = (token_id, payee.clone());
+                    let released = self.splitter_released.get(&key).unwrap_or(0);
+                    self.splitter_released.insert(&key, &(released - amount.0));
+
+                    near_sdk::env::log(format!("Release of {} to {} failed", amount.0, payee).as_bytes());
+
+                    false
+                }
+
+                fn pending_payment(&self, account_id: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+                    self.splitter_pending(&account_id, &token_id).into()
+                }
+
+                fn released(&self, account_id: near_sdk::AccountId, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+                    self.splitter_released.get(&(token_id, account_id)).unwrap_or(0).into()
+                }
+
+                fn total_received(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+                    self.splitter_received.get(&token_id).unwrap_or(0).into()
+                }
+
+                fn splitter_payees(&self) -> std::vec::Vec<(near_sdk::AccountId, u64)> {
+                    self.splitter_shares.to_vec()
+                }
+
+                fn splitter_total_shares(&self) -> u64 {
+                    self.splitter_total_shares
+                }
+            }
+
+            impl #item_ident {
+                pub const SPLITTER_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+                pub const SPLITTER_GAS_FOR_RELEASE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+
+                pub fn setup_payment_splitter(&mut self, payees: std::vec::Vec<near_sdk::AccountId>, shares: std::vec::Vec<u64>) {
+                    if self.splitter_total_shares > 0 {
+                        near_sdk::env::panic(b"Payees are already set up")
+                    }
+
+                    if payees.is_empty() || payees.len() != shares.len() {
+                        near_sdk::env::panic(b"Payees and shares must be non-empty and of the same length")
+                    }
+
+                    for (payee, shares) in payees.iter().zip(shares) {
+                        if !near_sdk::env::is_valid_account_id(payee.as_bytes()) {
+                            near_sdk::env::panic(format!("Account {} is invalid", payee).as_bytes())
+                        }
+
+                        if shares == 0 {
+                            near_sdk::env::panic(format!("Shares of {} must be positive", payee).as_bytes())
+                        }
+
+                        if self.splitter_shares.insert(payee, &shares).is_some() {
+                            near_sdk::env::panic(format!("Account {} is already a payee", payee).as_bytes())
+                        }
+
+                        self.splitter_total_shares = self
+                            .splitter_total_shares
+                            .checked_add(shares)
+                            .unwrap_or_else(|| near_sdk::env::panic(b"Total shares overflow"));
+                    }
+                }
+
+                // Books `amount` of `token_id`, `None` for native NEAR, as received by the contract to be split.
+                // Call it from `ft_on_transfer` for tokens
+                pub fn splitter_receive(&mut self, token_id: Option<near_sdk::AccountId>, amount: near_sdk::Balance) {
+                    let received = self
+                        .splitter_received
+                        .get(&token_id)
+                        .unwrap_or(0)
+                        .checked_add(amount)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Received amount overflow"));
+
+                    self.splitter_received.insert(&token_id, &received);
+                }
+
+                fn splitter_pending(&self, account_id: &near_sdk::AccountId, token_id: &Option<near_sdk::AccountId>) -> near_sdk::Balance {
+                    let shares = match self.splitter_shares.get(account_id) {
+                        Some(shares) => shares as near_sdk::Balance,
+                        None => return 0,
+                    };
+
+                    let total_shares = self.splitter_total_shares as near_sdk::Balance;
+                    let received = self.splitter_received.get(token_id).unwrap_or(0);
+
+                    // Split to avoid overflowing `received * shares`
+                    let due = received / total_shares * shares + received % total_shares * shares / total_shares;
+
+                    due - self
+                        .splitter_released
+                        .get(&(token_id.clone(), account_id.clone()))
+                        .unwrap_or(0)
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            splitter_shares: near_sdk::collections::UnorderedMap<near_sdk::AccountId, u64>,
+            splitter_total_shares: u64,
+            splitter_received: near_sdk::collections::LookupMap<Option<near_sdk::AccountId>, near_sdk::Balance>,
+            splitter_released: near_sdk::collections::LookupMap<(Option<near_sdk::AccountId>, near_sdk::AccountId), near_sdk::Balance>,
+        }
+        .into(),
+    )
+}
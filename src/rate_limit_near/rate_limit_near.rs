@@ -0,0 +1,219 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Ident, ImplItemMethod, NestedMeta, Token};
+
+use crate::utils::{name_value_options, string_option, u64_option};
+
+pub fn rate_limiting(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyRateLimit {
+            Configs,
+            Usages,
+        }
+
+        impl StorageKeyRateLimit {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyRateLimit::Configs => "rlc".to_string(),
+                    StorageKeyRateLimit::Usages => "rlu".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub enum RateLimitPolicy {
+            Fixed,
+            Sliding,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct RateLimitConfig {
+            pub max_calls: u64,
+            pub window_ns: u64,
+            pub policy: RateLimitPolicy,
+        }
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct RateLimitUsage {
+            pub window_start: u64,
+            pub count: u64,
+            pub last_call: u64,
+            pub calls: std::vec::Vec<u64>,
+        }
+
+
+        pub trait RateLimit {
+            fn rate_limit(&mut self, action: &str, config: RateLimitConfig);
+
+            fn rate_limit_current(&self, account_id: &near_sdk::AccountId, action: &str, config: &RateLimitConfig) -> RateLimitUsage;
+        }
+
+
+        impl RateLimit for #item_ident {
+
+            fn rate_limit(&mut self, action: &str, config: RateLimitConfig) {
+                // Limits live in the method attributes, the stored copy only serves the views
+                if self.rate_limit_configs.get(&action.to_string()).as_ref() != Some(&config) {
+                    self.rate_limit_configs.insert(&action.to_string(), &config);
+                }
+
+                let account_id = near_sdk::env::predecessor_account_id();
+                let mut usage = self.rate_limit_current(&account_id, action, &config);
+
+                if usage.count >= config.max_calls {
+                    near_sdk::env::panic(
+                        format!(
+                            "Rate limit for {} is exceeded, try again at {}",
+                            action,
+                            usage.window_start.saturating_add(config.window_ns)
+                        )
+                        .as_bytes(),
+                    )
+                }
+
+                let now = near_sdk::env::block_timestamp();
+
+                usage.count += 1;
+                usage.last_call = now;
+
+                if config.policy == RateLimitPolicy::Sliding {
+                    usage.calls.push(now);
+                }
+
+                self.rate_limit_usages.insert(&(account_id, action.to_string()), &usage);
+            }
+
+            fn rate_limit_current(&self, account_id: &near_sdk::AccountId, action: &str, config: &RateLimitConfig) -> RateLimitUsage {
+                let now = near_sdk::env::block_timestamp();
+
+                let mut usage = self
+                    .rate_limit_usages
+                    .get(&(account_id.clone(), action.to_string()))
+                    .unwrap_or(RateLimitUsage {
+                        window_start: now,
+                        count: 0,
+                        last_call: 0,
+                        calls: std::vec::Vec::new(),
+                    });
+
+                match config.policy {
+                    RateLimitPolicy::Fixed => {
+                        usage.calls.clear();
+
+                        if now >= usage.window_start.saturating_add(config.window_ns) {
+                            usage.window_start = now;
+                            usage.count = 0;
+                        }
+                    }
+                    RateLimitPolicy::Sliding => {
+                        usage.calls.retain(|call| now < call.saturating_add(config.window_ns));
+                        usage.count = usage.calls.len() as u64;
+                        usage.window_start = usage.calls.first().copied().unwrap_or(now);
+                    }
+                }
+
+                usage
+            }
+        }
+
+        #[near_bindgen]
+        impl #item_ident {
+            pub fn rate_limit_config(&self, action: String) -> Option<RateLimitConfig> {
+                self.rate_limit_configs.get(&action)
+            }
+
+            pub fn rate_limit_usage(&self, account_id: near_sdk::AccountId, action: String) -> Option<RateLimitUsage> {
+                let config = self.rate_limit_configs.get(&action)?;
+
+                Some(self.rate_limit_current(&account_id, &action, &config))
+            }
+
+            pub fn remaining_quota(&self, account_id: near_sdk::AccountId, action: String) -> Option<u64> {
+                let config = self.rate_limit_configs.get(&action)?;
+                let usage = self.rate_limit_current(&account_id, &action, &config);
+
+                Some(config.max_calls.saturating_sub(usage.count))
+            }
+        }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            rate_limit_configs: near_sdk::collections::LookupMap<String, RateLimitConfig>,
+            rate_limit_usages: near_sdk::collections::LookupMap<(near_sdk::AccountId, String), RateLimitUsage>,
+        }
+        .into(),
+    )
+}
+
+pub fn rate_limit_guard(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("Rate limits can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    let args = match Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let config = name_value_options(
+        args.iter(),
+        "rate_limit",
+        &["action", "max", "window_secs", "policy"],
+    )
+    .and_then(|options| {
+        for name in ["action", "max", "window_secs"] {
+            if !options.contains_key(name) {
+                return Err(format!("rate_limit requires {}", name));
+            }
+        }
+
+        let action = string_option(&options, "action", "")?;
+        let max = u64_option(&options, "max", 0)?;
+        let window_ns = u64_option(&options, "window_secs", 0)?
+            .checked_mul(1_000_000_000)
+            .ok_or_else(|| "window_secs is too large".to_string())?;
+
+        if max == 0 || window_ns == 0 {
+            return Err("max and window_secs have to be positive".to_string());
+        }
+
+        let policy = match string_option(&options, "policy", "fixed")?.as_str() {
+            "fixed" => quote! {RateLimitPolicy::Fixed},
+            "sliding" => quote! {RateLimitPolicy::Sliding},
+            _ => return Err("policy has to be fixed or sliding".to_string()),
+        };
+
+        Ok((action, max, window_ns, policy))
+    });
+
+    let (action, max, window_ns, policy) = match config {
+        Ok(config) => config,
+        Err(err) => return quote! {compile_error!(#err);}.into(),
+    };
+
+    let guard = quote! {
+        self.rate_limit(#action, RateLimitConfig { max_calls: #max, window_ns: #window_ns, policy: #policy });
+    };
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
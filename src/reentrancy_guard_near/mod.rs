@@ -0,0 +1 @@
+pub mod reentrancy_guard_near;
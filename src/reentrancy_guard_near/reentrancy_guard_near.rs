@@ -0,0 +1,128 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::{parse_macro_input, Expr, Ident, ImplItemMethod};
+
+pub fn reentrancy_guard(item_ident: Ident) -> (TokenStream, TokenStream) {
+    let core_ts = quote! {
+
+        pub enum StorageKeyReentrancyGuard {
+            Locks,
+        }
+
+        impl StorageKeyReentrancyGuard {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyReentrancyGuard::Locks => "rlk".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait ReentrancyGuard {
+            fn acquire_lock(&mut self);
+
+            fn release_lock(&mut self);
+
+            fn is_locked(&self) -> bool;
+
+            fn acquire_lock_for(&mut self, key: &str);
+
+            fn release_lock_for(&mut self, key: &str);
+
+            fn is_locked_for(&self, key: &str) -> bool;
+        }
+
+
+        impl ReentrancyGuard for #item_ident {
+
+            fn acquire_lock(&mut self) {
+                if self.reentrancy_lock {
+                    near_sdk::env::panic(b"Reentrant call")
+                }
+
+                self.reentrancy_lock = true;
+            }
+
+            fn release_lock(&mut self) {
+                self.reentrancy_lock = false;
+            }
+
+            fn is_locked(&self) -> bool {
+                self.reentrancy_lock
+            }
+
+            fn acquire_lock_for(&mut self, key: &str) {
+                if !self.reentrancy_locks.insert(&key.to_string()) {
+                    near_sdk::env::panic(format!("Reentrant call for {}", key).as_bytes())
+                }
+            }
+
+            fn release_lock_for(&mut self, key: &str) {
+                self.reentrancy_locks.remove(&key.to_string());
+            }
+
+            fn is_locked_for(&self, key: &str) -> bool {
+                self.reentrancy_locks.contains(&key.to_string())
+            }
+        }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {reentrancy_lock: bool, reentrancy_locks: near_sdk::collections::LookupSet<String>,}
+            .into(),
+    )
+}
+
+fn parse_lock_key(input: ParseStream) -> syn::Result<Expr> {
+    let name: Ident = input.parse()?;
+
+    if name != "key" {
+        return Err(syn::Error::new(
+            name.span(),
+            "Expected `key = <expression>`",
+        ));
+    }
+
+    input.parse::<syn::Token![=]>()?;
+    input.parse()
+}
+
+pub fn lock_guard(args: TokenStream, item: TokenStream, release: bool) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("Reentrancy guards can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    let guard = if args.is_empty() {
+        if release {
+            quote! {self.release_lock();}
+        } else {
+            quote! {self.acquire_lock();}
+        }
+    } else {
+        let key = match parse_lock_key.parse(args) {
+            Ok(key) => key,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        if release {
+            quote! {self.release_lock_for(&(#key).to_string());}
+        } else {
+            quote! {self.acquire_lock_for(&(#key).to_string());}
+        }
+    };
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
@@ -0,0 +1 @@
+pub mod state_migration_near;
@@ -0,0 +1,340 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+
+use quote::{format_ident, quote};
+use syn::{Field, Fields, FieldsNamed, Ident, Lit, Meta, NestedMeta};
+
+use crate::init_macro::{module_initializers, module_name};
+
+pub struct StateMigrationConfig {
+    version: u16,
+    // Modules of the previous layout, in the order they were required. `None` when no migration is generated
+    from: Option<Vec<String>>,
+    // Fields of the previous layout declared by the caller, used instead of the ones derived from `from`
+    old_fields: Option<FieldsNamed>,
+    // Owner of the migrated contract when Ownable is added during the migration
+    owner: Option<String>,
+}
+
+pub fn state_migration_config(
+    meta: &Meta,
+    required: &[String],
+) -> Result<StateMigrationConfig, String> {
+    let mut version = None;
+    let mut from = None;
+    let mut old_fields = None;
+    let mut owner = None;
+
+    if let Meta::List(list) = meta {
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("version") => {
+                    if version.is_some() {
+                        return Err("StateMigration option version is set twice".to_string());
+                    }
+
+                    version = match &nv.lit {
+                        Lit::Int(l) => match l.base10_parse::<u16>() {
+                            Ok(v) if v > 0 => Some(v),
+                            _ => return Err("version has to be a positive u16".to_string()),
+                        },
+                        _ => return Err("version has to be a positive u16".to_string()),
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("old_fields") => {
+                    if old_fields.is_some() {
+                        return Err("StateMigration option old_fields is set twice".to_string());
+                    }
+
+                    old_fields = match &nv.lit {
+                        Lit::Str(l) => match syn::parse_str::<FieldsNamed>(&format!("{{{}}}", l.value())) {
+                            Ok(fields) if !fields.named.is_empty() => Some(fields),
+                            _ => return Err("old_fields has to be a list of named fields, e.g. \"data: String, pause_status: bool\"".to_string()),
+                        },
+                        _ => return Err("old_fields has to be a string".to_string()),
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("owner") => {
+                    if owner.is_some() {
+                        return Err("StateMigration option owner is set twice".to_string());
+                    }
+
+                    owner = match &nv.lit {
+                        Lit::Str(l) if !l.value().is_empty() => Some(l.value()),
+                        _ => return Err("owner has to be a non-empty account id".to_string()),
+                    };
+                }
+                NestedMeta::Meta(Meta::List(modules)) if modules.path.is_ident("from") => {
+                    if from.is_some() {
+                        return Err("StateMigration option from is set twice".to_string());
+                    }
+
+                    let mut names: Vec<String> = Vec::new();
+
+                    for module in modules.nested.iter() {
+                        let ident = match module {
+                            NestedMeta::Meta(Meta::Path(path)) => match path.get_ident() {
+                                Some(ident) => ident,
+                                None => return Err("from only accepts module names".to_string()),
+                            },
+                            _ => return Err("from only accepts module names".to_string()),
+                        };
+
+                        let name = ident.to_string().to_lowercase();
+
+                        if !required.contains(&name) {
+                            return Err(format!("{} is not required by the current layout", ident));
+                        }
+
+                        if names.contains(&name) {
+                            return Err("Please do not enter duplicate modules in from".to_string());
+                        }
+
+                        names.push(name);
+                    }
+
+                    from = Some(names);
+                }
+                _ => {
+                    return Err(
+                        "StateMigration only accepts: version, from, old_fields, owner".to_string(),
+                    )
+                }
+            }
+        }
+    }
+
+    if from.is_some() && old_fields.is_some() {
+        return Err("Please set either from or old_fields".to_string());
+    }
+
+    if owner.is_some() && from.is_none() && old_fields.is_none() {
+        return Err("owner is only used together with from or old_fields".to_string());
+    }
+
+    Ok(StateMigrationConfig {
+        version: version.unwrap_or(1),
+        from,
+        old_fields,
+        owner,
+    })
+}
+
+pub fn state_migration(
+    item_ident: Ident,
+    config: StateMigrationConfig,
+    original_fields: &Fields,
+    module_fields: &[(String, TokenStream2)],
+) -> (TokenStream, TokenStream) {
+    let version = config.version;
+
+    let old_fields = match (config.from, config.old_fields) {
+        (Some(from), _) => Some(from_fields(&from, original_fields, module_fields)),
+        (None, Some(old_fields)) => Some(old_fields.named.into_iter().collect()),
+        (None, None) => None,
+    };
+
+    let migration_ts = match old_fields {
+        Some(old_fields) => {
+            match migration(
+                item_ident.clone(),
+                &old_fields,
+                config.owner,
+                original_fields,
+                module_fields,
+            ) {
+                Ok(migration_ts) => migration_ts,
+                Err(err) => quote! {compile_error!(#err);},
+            }
+        }
+        None => quote! {},
+    };
+
+    let core_ts = quote! {
+
+        impl #item_ident {
+            pub const STATE_VERSION: u16 = #version;
+        }
+
+        #[near_bindgen]
+        impl #item_ident {
+            pub fn state_version(&self) -> u16 {
+                self.state_version
+            }
+        }
+
+        #migration_ts
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {state_version: u16,}.into(),
+    )
+}
+
+fn field_names(fields: &TokenStream2) -> Vec<Ident> {
+    let fields: FieldsNamed = syn::parse2(quote! {{#fields}}).unwrap();
+
+    fields
+        .named
+        .into_iter()
+        .map(|field| field.ident.unwrap())
+        .collect()
+}
+
+// The layout described by `from`: the same user fields followed by the fields of the listed modules
+fn from_fields(
+    from: &[String],
+    original_fields: &Fields,
+    module_fields: &[(String, TokenStream2)],
+) -> Vec<Field> {
+    let mut old_fields = TokenStream2::new();
+
+    for field in original_fields.iter() {
+        old_fields.extend(quote! {#field,});
+    }
+
+    for module in from.iter() {
+        if let Some((_, fields)) = module_fields.iter().find(|(name, _)| name == module) {
+            old_fields.extend(fields.clone());
+        }
+    }
+
+    // Like in the current struct, the version is stored after the fields of all other modules
+    if from.contains(&"statemigration".to_string()) {
+        old_fields.extend(quote! {state_version: u16,});
+    }
+
+    let old_fields: FieldsNamed = syn::parse2(quote! {{#old_fields}}).unwrap();
+
+    old_fields.named.into_iter().collect()
+}
+
+// User fields and fields of modules found in the previous layout are moved by name, other fields of the
+// previous layout are dropped. Modules without fields in the previous layout get their `init_macro!` values
+fn migration(
+    item_ident: Ident,
+    old_fields: &[Field],
+    owner: Option<String>,
+    original_fields: &Fields,
+    module_fields: &[(String, TokenStream2)],
+) -> Result<TokenStream2, String> {
+    let old_ident = format_ident!("Old{}", item_ident);
+
+    let old_names: Vec<Ident> = old_fields
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect();
+
+    let mut conversion = TokenStream2::new();
+
+    for field in original_fields.iter() {
+        if let Some(name) = &field.ident {
+            if !old_names.contains(name) {
+                return Err(format!(
+                    "Field {} is not in the previous layout, please write the migration yourself",
+                    name
+                ));
+            }
+
+            conversion.extend(quote! {#name: old.#name,});
+        }
+    }
+
+    for (module, fields) in module_fields.iter() {
+        let names = field_names(fields);
+        let moved = names.iter().filter(|name| old_names.contains(name)).count();
+
+        if moved == names.len() {
+            for name in names {
+                conversion.extend(quote! {#name: old.#name,});
+            }
+
+            continue;
+        }
+
+        if moved > 0 {
+            return Err(format!(
+                "Fields of {} are only partly in the previous layout",
+                module
+            ));
+        }
+
+        // The contract account runs the migration, so it cannot become the owner
+        if module == "ownable" {
+            let owner = match &owner {
+                Some(owner) => owner,
+                None => {
+                    return Err(
+                        "Ownable is added during the migration, please set the owner option"
+                            .to_string(),
+                    )
+                }
+            };
+
+            conversion.extend(quote! {owner: #owner.to_string(),});
+            conversion.extend(module_initializers("ownable", &["owner".to_string()]));
+        } else {
+            conversion.extend(module_initializers(module_name(module).unwrap(), &[]));
+        }
+    }
+
+    if owner.is_some() && module_fields.iter().all(|(module, _)| module != "ownable") {
+        return Err("owner is only used when Ownable is added during the migration".to_string());
+    }
+
+    conversion.extend(module_initializers("state_migration", &[]));
+
+    let has_version = old_names.iter().any(|name| name == "state_version");
+
+    let version_check = if has_version {
+        quote! {
+            if old.state_version >= Self::STATE_VERSION {
+                near_sdk::env::panic(
+                    format!("State is already at version {}", old.state_version).as_bytes(),
+                )
+            }
+
+            near_sdk::env::log(
+                format!(
+                    "State is migrated from version {} to version {}",
+                    old.state_version,
+                    Self::STATE_VERSION
+                )
+                .as_bytes(),
+            );
+        }
+    } else {
+        quote! {
+            near_sdk::env::log(
+                format!("State is migrated to version {}", Self::STATE_VERSION).as_bytes(),
+            );
+        }
+    };
+
+    Ok(quote! {
+
+        #[derive(near_sdk::borsh::BorshDeserialize)]
+        pub struct #old_ident {
+            #(#old_fields),*
+        }
+
+        #[near_bindgen]
+        impl #item_ident {
+            #[init(ignore_state)]
+            #[private]
+            pub fn migrate() -> Self {
+                let old: #old_ident = near_sdk::env::state_read()
+                    .unwrap_or_else(|| near_sdk::env::panic(b"No state to migrate"));
+
+                #version_check
+
+                Self {
+                    #conversion
+                }
+            }
+        }
+
+    })
+}
@@ -0,0 +1,314 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{module_options, u64_option};
+
+pub fn storage_management_bounds(meta: &Meta) -> Result<(u64, Option<u64>), String> {
+    let options = module_options(meta, "StorageManagement", &["min_bytes", "max_bytes"])?;
+
+    let min_bytes = u64_option(&options, "min_bytes", 250)?;
+
+    let max_bytes = match options.get("max_bytes") {
+        Some(_) => Some(u64_option(&options, "max_bytes", 0)?),
+        None => None,
+    };
+
+    if let Some(max_bytes) = max_bytes {
+        if max_bytes < min_bytes {
+            return Err("max_bytes cannot be less than min_bytes".to_string());
+        }
+    }
+
+    Ok((min_bytes, max_bytes))
+}
+
+pub fn storage_management(
+    item_ident: Ident,
+    min_bytes: u64,
+    max_bytes: Option<u64>,
+) -> (TokenStream, TokenStream) {
+    let max_bytes = match max_bytes {
+        Some(max_bytes) => quote! {Some(#max_bytes)},
+        None => quote! {None},
+    };
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyStorageManagement {
+            Accounts,
+        }
+
+        impl StorageKeyStorageManagement {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyStorageManagement::Accounts => "stm".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, Clone, Debug, PartialEq)]
+        pub struct StorageAccount {
+            pub total: near_sdk::Balance,
+            pub used_bytes: near_sdk::StorageUsage,
+        }
+
+        #[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct StorageBalance {
+            pub total: near_sdk::json_types::U128,
+            pub available: near_sdk::json_types::U128,
+        }
+
+        #[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct StorageBalanceBounds {
+            pub min: near_sdk::json_types::U128,
+            pub max: Option<near_sdk::json_types::U128>,
+        }
+
+
+        pub trait StorageManagement {
+            fn storage_deposit(&mut self, account_id: Option<near_sdk::AccountId>, registration_only: Option<bool>) -> StorageBalance;
+
+            fn storage_withdraw(&mut self, amount: Option<near_sdk::json_types::U128>) -> StorageBalance;
+
+            fn storage_unregister(&mut self, force: Option<bool>) -> bool;
+
+            fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+
+            fn storage_balance_of(&self, account_id: near_sdk::AccountId) -> Option<StorageBalance>;
+        }
+
+
+            #[near_bindgen]
+            impl StorageManagement for #item_ident {
+
+                #[payable]
+                fn storage_deposit(&mut self, account_id: Option<near_sdk::AccountId>, registration_only: Option<bool>) -> StorageBalance {
+                    let amount = near_sdk::env::attached_deposit();
+                    let account_id = account_id.unwrap_or_else(near_sdk::env::predecessor_account_id);
+
+                    if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                    }
+
+                    let min = self.storage_min_balance();
+
+                    let (mut account, deposit) = match self.storage_accounts.get(&account_id) {
+                        // Registering again only refunds the deposit
+                        Some(account) if registration_only.unwrap_or(false) => (account, 0),
+                        Some(account) => (account, amount),
+                        None => {
+                            if amount < min {
+                                near_sdk::env::panic(
+                                    format!("The attached deposit is less than the minimum storage balance {}", min)
+                                        .as_bytes(),
+                                )
+                            }
+
+                            near_sdk::env::log(format!("Account {} is registered", account_id).as_bytes());
+
+                            let account = StorageAccount { total: 0, used_bytes: 0 };
+
+                            if registration_only.unwrap_or(false) {
+                                (account, min)
+                            } else {
+                                (account, amount)
+                            }
+                        }
+                    };
+
+                    let deposit = match self.storage_max_balance() {
+                        Some(max) => deposit.min(max.saturating_sub(account.total)),
+                        None => deposit,
+                    };
+
+                    account.total += deposit;
+                    self.storage_accounts.insert(&account_id, &account);
+
+                    let refund = amount - deposit;
+
+                    if refund > 0 {
+                        near_sdk::Promise::new(near_sdk::env::predecessor_account_id()).transfer(refund);
+                    }
+
+                    self.storage_to_balance(&account)
+                }
+
+                #[payable]
+                fn storage_withdraw(&mut self, amount: Option<near_sdk::json_types::U128>) -> StorageBalance {
+                    Self::storage_assert_one_yocto();
+
+                    let account_id = near_sdk::env::predecessor_account_id();
+                    let mut account = self.storage_account(&account_id);
+
+                    let available = self.storage_available(&account);
+                    let amount = amount.map_or(available, |amount| amount.0);
+
+                    if amount > available {
+                        near_sdk::env::panic(
+                            format!("The amount is greater than the available storage balance {}", available)
+                                .as_bytes(),
+                        )
+                    }
+
+                    account.total -= amount;
+                    self.storage_accounts.insert(&account_id, &account);
+
+                    if amount > 0 {
+                        near_sdk::Promise::new(account_id).transfer(amount);
+                    }
+
+                    self.storage_to_balance(&account)
+                }
+
+                #[payable]
+                fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+                    Self::storage_assert_one_yocto();
+
+                    let account_id = near_sdk::env::predecessor_account_id();
+
+                    let account = match self.storage_accounts.get(&account_id) {
+                        Some(account) => account,
+                        None => {
+                            near_sdk::env::log(format!("Account {} is not registered", account_id).as_bytes());
+                            return false;
+                        }
+                    };
+
+                    if account.used_bytes > 0 && !force.unwrap_or(false) {
+                        near_sdk::env::panic(
+                            format!(
+                                "Account {} still uses {} bytes of storage, unregister with force to give them up",
+                                account_id, account.used_bytes
+                            )
+                            .as_bytes(),
+                        )
+                    }
+
+                    // The data of the account stays in the contract, so the deposit covering it is kept
+                    let refund = account
+                        .total
+                        .saturating_sub(account.used_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost());
+
+                    self.storage_accounts.remove(&account_id);
+
+                    if refund > 0 {
+                        near_sdk::Promise::new(account_id.clone()).transfer(refund);
+                    }
+
+                    near_sdk::env::log(format!("Account {} is unregistered", account_id).as_bytes());
+
+                    true
+                }
+
+                fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+                    StorageBalanceBounds {
+                        min: self.storage_min_balance().into(),
+                        max: self.storage_max_balance().map(|max| max.into()),
+                    }
+                }
+
+                fn storage_balance_of(&self, account_id: near_sdk::AccountId) -> Option<StorageBalance> {
+                    self.storage_accounts
+                        .get(&account_id)
+                        .map(|account| self.storage_to_balance(&account))
+                }
+            }
+
+            impl #item_ident {
+                pub const STORAGE_MIN_BYTES: near_sdk::StorageUsage = #min_bytes;
+                pub const STORAGE_MAX_BYTES: Option<near_sdk::StorageUsage> = #max_bytes;
+
+                // Measures the storage used by `f` and charges it to the storage balance of the caller.
+                // Storage freed by `f` is refunded to the storage balance
+                pub fn charge_storage<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+                    let account_id = near_sdk::env::predecessor_account_id();
+                    let mut account = self.storage_account(&account_id);
+
+                    let before = near_sdk::env::storage_usage();
+                    let result = f(self);
+                    let after = near_sdk::env::storage_usage();
+
+                    if after >= before {
+                        account.used_bytes += after - before;
+                    } else {
+                        account.used_bytes = account.used_bytes.saturating_sub(before - after);
+                    }
+
+                    let locked = self.storage_locked(&account);
+
+                    if locked > account.total {
+                        near_sdk::env::panic(
+                            format!(
+                                "Account {} needs {} more yoctoNEAR of storage balance",
+                                account_id,
+                                locked - account.total
+                            )
+                            .as_bytes(),
+                        )
+                    }
+
+                    self.storage_accounts.insert(&account_id, &account);
+
+                    result
+                }
+
+                fn storage_assert_one_yocto() {
+                    if near_sdk::env::attached_deposit() != 1 {
+                        near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+                    }
+                }
+
+                fn storage_account(&self, account_id: &near_sdk::AccountId) -> StorageAccount {
+                    self.storage_accounts.get(account_id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Account {} is not registered", account_id).as_bytes())
+                    })
+                }
+
+                fn storage_min_balance(&self) -> near_sdk::Balance {
+                    self.storage_min_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost()
+                }
+
+                fn storage_max_balance(&self) -> Option<near_sdk::Balance> {
+                    self.storage_max_bytes
+                        .map(|max_bytes| max_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost())
+                }
+
+                // The minimum balance pays for the registration, the rest is charged by `charge_storage`
+                fn storage_locked(&self, account: &StorageAccount) -> near_sdk::Balance {
+                    self.storage_min_balance()
+                        + account.used_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost()
+                }
+
+                fn storage_available(&self, account: &StorageAccount) -> near_sdk::Balance {
+                    account.total.saturating_sub(self.storage_locked(account))
+                }
+
+                fn storage_to_balance(&self, account: &StorageAccount) -> StorageBalance {
+                    StorageBalance {
+                        total: account.total.into(),
+                        available: self.storage_available(account).into(),
+                    }
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            storage_accounts: near_sdk::collections::LookupMap<near_sdk::AccountId, StorageAccount>,
+            storage_min_bytes: near_sdk::StorageUsage,
+            storage_max_bytes: Option<near_sdk::StorageUsage>,
+        }
+        .into(),
+    )
+}
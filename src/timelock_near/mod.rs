@@ -0,0 +1 @@
+pub mod timelock_near;
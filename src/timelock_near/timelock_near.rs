@@ -0,0 +1,372 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, FnArg, Ident, ImplItemMethod, Meta, NestedMeta, Pat, ReturnType, Token,
+};
+
+use crate::utils::{authorization, module_options, name_value_options, string_option, u64_option};
+
+pub struct TimelockConfig {
+    proposer: Option<String>,
+    executor: Option<String>,
+    delay_ns: u64,
+}
+
+pub fn timelock_config(meta: &Meta, has_access: bool) -> Result<TimelockConfig, String> {
+    let options = module_options(meta, "Timelock", &["proposer", "executor", "delay_secs"])?;
+
+    if !has_access && (options.contains_key("proposer") || options.contains_key("executor")) {
+        return Err("Timelock roles can only be configured together with Access".to_string());
+    }
+
+    let (proposer, executor) = if has_access {
+        (
+            Some(string_option(&options, "proposer", "proposer")?),
+            Some(string_option(&options, "executor", "executor")?),
+        )
+    } else {
+        (None, None)
+    };
+
+    let delay_ns = u64_option(&options, "delay_secs", 86_400)?
+        .checked_mul(1_000_000_000)
+        .ok_or_else(|| "delay_secs is too large".to_string())?;
+
+    Ok(TimelockConfig {
+        proposer,
+        executor,
+        delay_ns,
+    })
+}
+
+pub fn timelock(
+    item_ident: Ident,
+    config: TimelockConfig,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let proposer_check = authorization(config.proposer.as_deref(), has_ownable);
+    let executor_check = authorization(config.executor.as_deref(), has_ownable);
+    let delay_ns = config.delay_ns;
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyTimelock {
+            Operations,
+        }
+
+        impl StorageKeyTimelock {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyTimelock::Operations => "tlo".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct TimelockOperation {
+            pub method: String,
+            pub args: String,
+            pub proposer: near_sdk::AccountId,
+            pub scheduled_at: u64,
+            pub ready_at: u64,
+            // Set by `execute` until its callback removes the operation, or restores it if the call failed
+            pub executing: bool,
+        }
+
+
+        pub trait Timelock {
+            fn schedule(&mut self, method: String, args: String) -> String;
+
+            fn cancel(&mut self, id: String);
+
+            fn execute(&mut self, id: String) -> near_sdk::Promise;
+
+            fn timelock_on_execute(&mut self, id: String) -> bool;
+
+            fn operation(&self, id: String) -> Option<TimelockOperation>;
+
+            fn operations(&self, from_index: u64, limit: u64) -> std::vec::Vec<(String, TimelockOperation)>;
+
+            fn timelock_delay(&self) -> u64;
+        }
+
+
+            #[near_bindgen]
+            impl Timelock for #item_ident {
+
+                fn schedule(&mut self, method: String, args: String) -> String {
+                    // Arguments are normalized, so the id matches the one computed by the timelocked method
+                    let args = near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args)
+                        .unwrap_or_else(|_| near_sdk::env::panic(b"Arguments have to be a JSON object"))
+                        .to_string();
+
+                    self.timelock_schedule(&method, args, #delay_ns)
+                }
+
+                fn cancel(&mut self, id: String) {
+                    #proposer_check
+
+                    if self.timelock_operations.remove(&id).is_none() {
+                        near_sdk::env::panic(format!("Operation {} is not scheduled", id).as_bytes())
+                    }
+
+                    near_sdk::env::log(
+                        format!(
+                            "Operation {} is cancelled by {}",
+                            id,
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    )
+                }
+
+                fn execute(&mut self, id: String) -> near_sdk::Promise {
+                    #executor_check
+
+                    let mut operation = self
+                        .timelock_operations
+                        .get(&id)
+                        .unwrap_or_else(|| near_sdk::env::panic(format!("Operation {} is not scheduled", id).as_bytes()));
+
+                    if operation.executing {
+                        near_sdk::env::panic(format!("Operation {} is being executed", id).as_bytes())
+                    }
+
+                    if near_sdk::env::block_timestamp() < operation.ready_at {
+                        near_sdk::env::panic(
+                            format!("Operation {} is not ready until {}", id, operation.ready_at).as_bytes(),
+                        )
+                    }
+
+                    // The operation cannot be executed again while the call is in flight. The callback removes it,
+                    // or restores it if the call failed, so it can be executed again
+                    operation.executing = true;
+                    self.timelock_operations.insert(&id, &operation);
+
+                    near_sdk::Promise::new(near_sdk::env::current_account_id())
+                        .function_call(
+                            operation.method.into_bytes(),
+                            operation.args.into_bytes(),
+                            0,
+                            near_sdk::env::prepaid_gas()
+                                .saturating_sub(near_sdk::env::used_gas())
+                                .saturating_sub(20_000_000_000_000)
+                                .saturating_sub(Self::TIMELOCK_GAS_FOR_EXECUTE_CALLBACK),
+                        )
+                        .then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                            b"timelock_on_execute".to_vec(),
+                            near_sdk::serde_json::json!({ "id": id }).to_string().into_bytes(),
+                            0,
+                            Self::TIMELOCK_GAS_FOR_EXECUTE_CALLBACK,
+                        ))
+                }
+
+                #[private]
+                fn timelock_on_execute(&mut self, id: String) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        // Timelocked methods already removed their operation
+                        if self.timelock_operations.remove(&id).is_some() {
+                            near_sdk::env::log(format!("Operation {} is executed", id).as_bytes());
+                        }
+
+                        return true;
+                    }
+
+                    if let Some(mut operation) = self.timelock_operations.get(&id) {
+                        operation.executing = false;
+                        self.timelock_operations.insert(&id, &operation);
+                    }
+
+                    near_sdk::env::log(format!("Execution of operation {} failed", id).as_bytes());
+
+                    false
+                }
+
+                fn operation(&self, id: String) -> Option<TimelockOperation> {
+                    self.timelock_operations.get(&id)
+                }
+
+                fn operations(&self, from_index: u64, limit: u64) -> std::vec::Vec<(String, TimelockOperation)> {
+                    self.timelock_operations
+                        .iter()
+                        .skip(from_index as usize)
+                        .take(limit as usize)
+                        .collect()
+                }
+
+                fn timelock_delay(&self) -> u64 {
+                    #delay_ns
+                }
+            }
+
+            impl #item_ident {
+                pub const TIMELOCK_GAS_FOR_EXECUTE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+
+                fn timelock_id(method: &str, args: &str) -> String {
+                    near_sdk::env::sha256(format!("{}:{}", method, args).as_bytes())
+                        .iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect()
+                }
+
+                fn timelock_schedule(&mut self, method: &str, args: String, delay_ns: u64) -> String {
+                    #proposer_check
+
+                    let id = Self::timelock_id(method, &args);
+
+                    if self.timelock_operations.get(&id).is_some() {
+                        near_sdk::env::panic(format!("Operation {} is already scheduled", id).as_bytes())
+                    }
+
+                    let now = near_sdk::env::block_timestamp();
+
+                    let operation = TimelockOperation {
+                        method: method.to_string(),
+                        args,
+                        proposer: near_sdk::env::predecessor_account_id(),
+                        scheduled_at: now,
+                        ready_at: now.saturating_add(delay_ns),
+                        executing: false,
+                    };
+
+                    near_sdk::env::log(
+                        format!(
+                            "Operation {} calling {}({}) is scheduled by {} and is ready at {}",
+                            id, operation.method, operation.args, operation.proposer, operation.ready_at
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.timelock_operations.insert(&id, &operation);
+
+                    id
+                }
+
+                // Schedules the call if it is not scheduled yet. Otherwise consumes the operation and returns `true`
+                // once the delay has passed, so the timelocked method can run
+                fn timelock_call(&mut self, method: &str, args: String, delay_ns: Option<u64>) -> bool {
+                    let id = Self::timelock_id(method, &args);
+
+                    let operation = match self.timelock_operations.get(&id) {
+                        Some(operation) => operation,
+                        None => {
+                            self.timelock_schedule(method, args, delay_ns.unwrap_or(#delay_ns));
+                            return false;
+                        }
+                    };
+
+                    // `execute` calls the method from the contract account
+                    if near_sdk::env::predecessor_account_id() != near_sdk::env::current_account_id() {
+                        #executor_check
+
+                        if operation.executing {
+                            near_sdk::env::panic(format!("Operation {} is being executed", id).as_bytes())
+                        }
+                    }
+
+                    // The delay of the method applies even if the call was scheduled with a shorter one
+                    let ready_at = operation
+                        .ready_at
+                        .max(operation.scheduled_at.saturating_add(delay_ns.unwrap_or(#delay_ns)));
+
+                    if near_sdk::env::block_timestamp() < ready_at {
+                        near_sdk::env::panic(format!("Operation {} is not ready until {}", id, ready_at).as_bytes())
+                    }
+
+                    self.timelock_operations.remove(&id);
+
+                    near_sdk::env::log(
+                        format!(
+                            "Operation {} is executed by {}",
+                            id,
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    );
+
+                    true
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {timelock_operations: near_sdk::collections::UnorderedMap<String, TimelockOperation>,}
+            .into(),
+    )
+}
+
+pub fn timelock_guard(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    if method.sig.receiver().is_none() {
+        return quote! {compile_error!("timelocked can only be used on methods with a self receiver");}
+            .into();
+    }
+
+    // Scheduling returns early, so there is no value to return
+    if let ReturnType::Type(_, _) = method.sig.output {
+        return quote! {compile_error!("timelocked methods cannot return values");}.into();
+    }
+
+    let args = match Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let delay_ns =
+        name_value_options(args.iter(), "timelocked", &["delay_secs"]).and_then(|options| {
+            if !options.contains_key("delay_secs") {
+                return Ok(quote! {None});
+            }
+
+            let delay_ns = u64_option(&options, "delay_secs", 0)?
+                .checked_mul(1_000_000_000)
+                .ok_or_else(|| "delay_secs is too large".to_string())?;
+
+            Ok(quote! {Some(#delay_ns)})
+        });
+
+    let delay_ns = match delay_ns {
+        Ok(delay_ns) => delay_ns,
+        Err(err) => return quote! {compile_error!(#err);}.into(),
+    };
+
+    let mut names = Vec::new();
+
+    for input in method.sig.inputs.iter() {
+        if let FnArg::Typed(arg) = input {
+            match &*arg.pat {
+                Pat::Ident(pat) => names.push(pat.ident.clone()),
+                _ => return quote! {compile_error!("timelocked methods can only take named arguments");}.into(),
+            }
+        }
+    }
+
+    let method_name = method.sig.ident.to_string();
+    let keys = names.iter().map(|name| name.to_string());
+
+    let guard = quote! {
+        if !self.timelock_call(
+            #method_name,
+            near_sdk::serde_json::json!({#(#keys: &#names),*}).to_string(),
+            #delay_ns,
+        ) {
+            return;
+        }
+    };
+
+    method.block.stmts.insert(0, syn::parse2(guard).unwrap());
+
+    quote! {#method}.into()
+}
@@ -0,0 +1 @@
+pub mod upgradable_near;
@@ -0,0 +1,209 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{authorization, module_options, string_option, u64_option};
+
+pub struct UpgradableConfig {
+    stager: Option<String>,
+    deployer: Option<String>,
+    delay_ns: u64,
+    migrate: String,
+}
+
+pub fn upgradable_config(meta: &Meta, has_access: bool) -> Result<UpgradableConfig, String> {
+    let options = module_options(
+        meta,
+        "Upgradable",
+        &["stager", "deployer", "delay_secs", "migrate"],
+    )?;
+
+    if !has_access && (options.contains_key("stager") || options.contains_key("deployer")) {
+        return Err("Upgradable roles can only be configured together with Access".to_string());
+    }
+
+    let (stager, deployer) = if has_access {
+        (
+            Some(string_option(&options, "stager", "code_stager")?),
+            Some(string_option(&options, "deployer", "code_deployer")?),
+        )
+    } else {
+        (None, None)
+    };
+
+    let delay_ns = u64_option(&options, "delay_secs", 86_400)?
+        .checked_mul(1_000_000_000)
+        .ok_or_else(|| "delay_secs is too large".to_string())?;
+
+    Ok(UpgradableConfig {
+        stager,
+        deployer,
+        delay_ns,
+        migrate: string_option(&options, "migrate", "migrate")?,
+    })
+}
+
+pub fn upgradable(
+    item_ident: Ident,
+    config: UpgradableConfig,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let stager_check = authorization(config.stager.as_deref(), has_ownable);
+    let deployer_check = authorization(config.deployer.as_deref(), has_ownable);
+    let delay_ns = config.delay_ns;
+    let migrate = config.migrate;
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyUpgradable {
+            StagedCode,
+        }
+
+        impl StorageKeyUpgradable {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyUpgradable::StagedCode => "upc".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        pub trait Upgradable {
+            fn stage_code(&mut self, code: near_sdk::json_types::Base64VecU8);
+
+            fn unstage_code(&mut self);
+
+            fn deploy_code(&mut self) -> near_sdk::Promise;
+
+            fn staged_code_hash(&self) -> Option<String>;
+
+            fn staged_at(&self) -> Option<u64>;
+
+            fn upgrade_delay(&self) -> u64;
+        }
+
+
+            #[near_bindgen]
+            impl Upgradable for #item_ident {
+
+                fn stage_code(&mut self, code: near_sdk::json_types::Base64VecU8) {
+                    #stager_check
+
+                    let code: std::vec::Vec<u8> = code.into();
+
+                    if code.is_empty() {
+                        near_sdk::env::panic(b"Staged code cannot be empty")
+                    }
+
+                    let hash: String = near_sdk::env::sha256(&code)
+                        .iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect();
+
+                    near_sdk::env::log(
+                        format!(
+                            "Code with hash {} is staged by {} at {}",
+                            hash,
+                            near_sdk::env::predecessor_account_id(),
+                            near_sdk::env::block_timestamp()
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.staged_code.set(&code);
+                    self.staged_code_hash = Some(hash);
+                    self.staged_at = Some(near_sdk::env::block_timestamp());
+                }
+
+                fn unstage_code(&mut self) {
+                    #stager_check
+
+                    if self.staged_code_hash.is_none() {
+                        near_sdk::env::panic(b"No code is staged")
+                    }
+
+                    near_sdk::env::log(
+                        format!("Code with hash {} is unstaged", self.staged_code_hash.as_ref().unwrap())
+                            .as_bytes(),
+                    );
+
+                    self.staged_code.remove();
+                    self.staged_code_hash = None;
+                    self.staged_at = None;
+                }
+
+                fn deploy_code(&mut self) -> near_sdk::Promise {
+                    #deployer_check
+
+                    let staged_at = self
+                        .staged_at
+                        .unwrap_or_else(|| near_sdk::env::panic(b"No code is staged"));
+
+                    if near_sdk::env::block_timestamp() < staged_at.saturating_add(#delay_ns) {
+                        near_sdk::env::panic(
+                            format!(
+                                "Staged code cannot be deployed before {}",
+                                staged_at.saturating_add(#delay_ns)
+                            )
+                            .as_bytes(),
+                        )
+                    }
+
+                    let code = self.staged_code.get().unwrap();
+
+                    near_sdk::env::log(
+                        format!(
+                            "Code with hash {} is deployed by {}",
+                            self.staged_code_hash.as_ref().unwrap(),
+                            near_sdk::env::predecessor_account_id()
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.staged_code.remove();
+                    self.staged_code_hash = None;
+                    self.staged_at = None;
+
+                    // Deploy and migrate are one batch, so a failing migration also reverts the deployment
+                    near_sdk::Promise::new(near_sdk::env::current_account_id())
+                        .deploy_contract(code)
+                        .function_call(
+                            #migrate.as_bytes().to_vec(),
+                            std::vec::Vec::new(),
+                            0,
+                            near_sdk::env::prepaid_gas()
+                                .saturating_sub(near_sdk::env::used_gas())
+                                .saturating_sub(20_000_000_000_000),
+                        )
+                }
+
+                fn staged_code_hash(&self) -> Option<String> {
+                    self.staged_code_hash.clone()
+                }
+
+                fn staged_at(&self) -> Option<u64> {
+                    self.staged_at
+                }
+
+                fn upgrade_delay(&self) -> u64 {
+                    #delay_ns
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            staged_code: near_sdk::collections::LazyOption<std::vec::Vec<u8>>,
+            staged_code_hash: Option<String>,
+            staged_at: Option<u64>,
+        }
+        .into(),
+    )
+}
@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream as TokenStream2;
+use std::collections::HashMap;
+
+use quote::quote;
+use syn::{Lit, Meta, NestedMeta};
+
+// Collects `name = literal` options passed to a macro, e.g. `Pausable(pauser = "guardian")`
+pub fn module_options(
+    meta: &Meta,
+    module: &str,
+    allowed: &[&str],
+) -> Result<HashMap<String, Lit>, String> {
+    match meta {
+        Meta::List(list) => name_value_options(list.nested.iter(), module, allowed),
+        _ => Ok(HashMap::new()),
+    }
+}
+
+// Same as `module_options`, for the arguments of method attributes, e.g. `#[rate_limit(max = 3)]`
+pub fn name_value_options<'a>(
+    nested: impl Iterator<Item = &'a NestedMeta>,
+    module: &str,
+    allowed: &[&str],
+) -> Result<HashMap<String, Lit>, String> {
+    let mut options = HashMap::new();
+
+    for nested in nested {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(nv))
+                if allowed.iter().any(|name| nv.path.is_ident(name)) =>
+            {
+                let name = nv.path.get_ident().unwrap().to_string();
+
+                if options.insert(name.clone(), nv.lit.clone()).is_some() {
+                    return Err(format!("{} option {} is set twice", module, name));
+                }
+            }
+            _ => return Err(format!("{} only accepts: {}", module, allowed.join(", "))),
+        }
+    }
+
+    Ok(options)
+}
+
+pub fn string_option(
+    options: &HashMap<String, Lit>,
+    name: &str,
+    default: &str,
+) -> Result<String, String> {
+    match options.get(name) {
+        Some(Lit::Str(l)) if !l.value().is_empty() => Ok(l.value()),
+        Some(_) => Err(format!("{} has to be a non-empty string", name)),
+        None => Ok(default.to_string()),
+    }
+}
+
+pub fn u64_option(options: &HashMap<String, Lit>, name: &str, default: u64) -> Result<u64, String> {
+    match options.get(name) {
+        Some(Lit::Int(l)) => l
+            .base10_parse::<u64>()
+            .map_err(|_| format!("{} has to be an unsigned integer", name)),
+        Some(_) => Err(format!("{} has to be an unsigned integer", name)),
+        None => Ok(default),
+    }
+}
+
+pub fn bool_option(
+    options: &HashMap<String, Lit>,
+    name: &str,
+    default: bool,
+) -> Result<bool, String> {
+    match options.get(name) {
+        Some(Lit::Bool(l)) => Ok(l.value),
+        Some(_) => Err(format!("{} has to be a boolean", name)),
+        None => Ok(default),
+    }
+}
+
+// Privileged methods are gated by an Access role when Access is required,
+// otherwise by the owner when Ownable is required, otherwise only the contract itself can call them
+pub fn authorization(role: Option<&str>, has_ownable: bool) -> TokenStream2 {
+    match role {
+        Some(role) => quote! {
+            self.assert_role(&#role.to_string());
+        },
+        None if has_ownable => quote! {
+            self.assert_owner();
+        },
+        None => quote! {
+            if near_sdk::env::predecessor_account_id() != near_sdk::env::current_account_id() {
+                near_sdk::env::panic(b"Method is private");
+            }
+        },
+    }
+}
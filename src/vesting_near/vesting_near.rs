@@ -0,0 +1,368 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Ident, Meta};
+
+use crate::utils::{authorization, module_options, string_option};
+
+pub fn vesting_role(meta: &Meta, has_access: bool) -> Result<Option<String>, String> {
+    let options = module_options(meta, "Vesting", &["role"])?;
+
+    if !has_access {
+        if !options.is_empty() {
+            return Err("Vesting role can only be configured together with Access".to_string());
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some(string_option(&options, "role", "vesting_admin")?))
+}
+
+pub fn vesting(
+    item_ident: Ident,
+    role: Option<String>,
+    has_ownable: bool,
+) -> (TokenStream, TokenStream) {
+    let admin_check = authorization(role.as_deref(), has_ownable);
+
+    let core_ts = quote! {
+
+        pub enum StorageKeyVesting {
+            Schedules,
+        }
+
+        impl StorageKeyVesting {
+            pub fn to_string(&self) -> String {
+                match self {
+                    StorageKeyVesting::Schedules => "vst".to_string(),
+                }
+            }
+
+            pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+                self.to_string().into_bytes()
+            }
+        }
+
+
+        #[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+        #[serde(crate = "near_sdk::serde")]
+        pub struct VestingSchedule {
+            pub start: u64,
+            pub cliff: u64,
+            pub duration: u64,
+            pub total: near_sdk::json_types::U128,
+            pub claimed: near_sdk::json_types::U128,
+            pub revoked: bool,
+        }
+
+
+        pub trait Vesting {
+            fn create_vesting(&mut self, account_id: near_sdk::AccountId, total: near_sdk::json_types::U128, start_secs: u64, cliff_secs: u64, duration_secs: u64);
+
+            fn revoke_vesting(&mut self, account_id: near_sdk::AccountId) -> near_sdk::Promise;
+
+            fn vesting_on_revoke(&mut self, amount: near_sdk::json_types::U128) -> bool;
+
+            fn claim(&mut self) -> near_sdk::Promise;
+
+            fn vesting_on_claim(&mut self, account_id: near_sdk::AccountId, amount: near_sdk::json_types::U128) -> bool;
+
+            fn claimable(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+
+            fn vesting_schedule(&self, account_id: near_sdk::AccountId) -> Option<VestingSchedule>;
+
+            fn vesting_token(&self) -> Option<near_sdk::AccountId>;
+
+            fn vesting_unallocated(&self) -> near_sdk::json_types::U128;
+        }
+
+
+            #[near_bindgen]
+            impl Vesting for #item_ident {
+
+                // Native NEAR schedules are funded by the attached deposit. Token schedules take their total
+                // from the tokens received with `vesting_fund`
+                #[payable]
+                fn create_vesting(&mut self, account_id: near_sdk::AccountId, total: near_sdk::json_types::U128, start_secs: u64, cliff_secs: u64, duration_secs: u64) {
+                    #admin_check
+
+                    if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                        near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+                    }
+
+                    if self.vesting_schedules.get(&account_id).is_some() {
+                        near_sdk::env::panic(format!("Account {} already has a vesting schedule", account_id).as_bytes())
+                    }
+
+                    if total.0 == 0 {
+                        near_sdk::env::panic(b"The amount should be a positive number")
+                    }
+
+                    if duration_secs == 0 || cliff_secs > duration_secs {
+                        near_sdk::env::panic(b"duration_secs has to be positive and not less than cliff_secs")
+                    }
+
+                    let deposit = near_sdk::env::attached_deposit();
+
+                    match &self.vesting_token {
+                        None if deposit != total.0 => near_sdk::env::panic(
+                            format!("Requires attached deposit of {} yoctoNEAR", total.0).as_bytes(),
+                        ),
+                        Some(_) if deposit != 0 => near_sdk::env::panic(b"Token schedules do not accept deposits"),
+                        Some(_) if total.0 > self.vesting_unallocated => near_sdk::env::panic(
+                            format!("Only {} tokens are funded and not allocated", self.vesting_unallocated).as_bytes(),
+                        ),
+                        Some(_) => self.vesting_unallocated -= total.0,
+                        None => {}
+                    }
+
+                    let start = start_secs
+                        .checked_mul(1_000_000_000)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"start_secs is too large"));
+                    let duration = duration_secs
+                        .checked_mul(1_000_000_000)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"duration_secs is too large"));
+
+                    if start.checked_add(duration).is_none() {
+                        near_sdk::env::panic(b"duration_secs is too large")
+                    }
+
+                    let cliff = cliff_secs
+                        .checked_mul(1_000_000_000)
+                        .and_then(|cliff| start.checked_add(cliff))
+                        .unwrap_or_else(|| near_sdk::env::panic(b"cliff_secs is too large"));
+
+                    near_sdk::env::log(
+                        format!(
+                            "Vesting of {} for {} is created, starting at {} with a cliff of {} seconds over {} seconds",
+                            total.0, account_id, start_secs, cliff_secs, duration_secs
+                        )
+                        .as_bytes(),
+                    );
+
+                    self.vesting_schedules.insert(
+                        &account_id,
+                        &VestingSchedule {
+                            start,
+                            cliff,
+                            duration,
+                            total,
+                            claimed: near_sdk::json_types::U128(0),
+                            revoked: false,
+                        },
+                    );
+                }
+
+                // Freezes the schedule at the vested amount and sends the unvested amount to the caller
+                fn revoke_vesting(&mut self, account_id: near_sdk::AccountId) -> near_sdk::Promise {
+                    #admin_check
+
+                    let mut schedule = self.vesting_schedule_of(&account_id);
+
+                    if schedule.revoked {
+                        near_sdk::env::panic(format!("Vesting of {} is already revoked", account_id).as_bytes())
+                    }
+
+                    let vested = Self::vesting_vested(&schedule, near_sdk::env::block_timestamp());
+                    let unvested = schedule.total.0 - vested;
+
+                    if unvested == 0 {
+                        near_sdk::env::panic(format!("Vesting of {} has nothing unvested to revoke", account_id).as_bytes())
+                    }
+
+                    schedule.total = vested.into();
+                    schedule.revoked = true;
+                    self.vesting_schedules.insert(&account_id, &schedule);
+
+                    near_sdk::env::log(
+                        format!("Vesting of {} is revoked, {} is unvested", account_id, unvested).as_bytes(),
+                    );
+
+                    self.vesting_transfer(near_sdk::env::predecessor_account_id(), unvested).then(
+                        near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                            b"vesting_on_revoke".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "amount": near_sdk::json_types::U128(unvested),
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            Self::VESTING_GAS_FOR_REVOKE_CALLBACK,
+                        ),
+                    )
+                }
+
+                // Unvested tokens that could not be sent back are funds for new schedules again.
+                // Native NEAR is refunded to the contract balance by the failed transfer
+                #[private]
+                fn vesting_on_revoke(&mut self, amount: near_sdk::json_types::U128) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        return true;
+                    }
+
+                    if self.vesting_token.is_some() {
+                        self.vesting_unallocated = self.vesting_unallocated.saturating_add(amount.0);
+                    }
+
+                    near_sdk::env::log(format!("Transfer of {} unvested failed", amount.0).as_bytes());
+
+                    false
+                }
+
+                // Claimed amounts are booked before the transfer and rolled back by the callback if it fails
+                fn claim(&mut self) -> near_sdk::Promise {
+                    let account_id = near_sdk::env::predecessor_account_id();
+                    let mut schedule = self.vesting_schedule_of(&account_id);
+
+                    let amount = Self::vesting_vested(&schedule, near_sdk::env::block_timestamp()) - schedule.claimed.0;
+
+                    if amount == 0 {
+                        near_sdk::env::panic(format!("Account {} has nothing to claim", account_id).as_bytes())
+                    }
+
+                    schedule.claimed = (schedule.claimed.0 + amount).into();
+                    self.vesting_schedules.insert(&account_id, &schedule);
+
+                    near_sdk::env::log(format!("Account {} claims {}", account_id, amount).as_bytes());
+
+                    self.vesting_transfer(account_id.clone(), amount).then(
+                        near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                            b"vesting_on_claim".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "account_id": account_id,
+                                "amount": near_sdk::json_types::U128(amount),
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            0,
+                            Self::VESTING_GAS_FOR_CLAIM_CALLBACK,
+                        ),
+                    )
+                }
+
+                #[private]
+                fn vesting_on_claim(&mut self, account_id: near_sdk::AccountId, amount: near_sdk::json_types::U128) -> bool {
+                    if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+                        return true;
+                    }
+
+                    let mut schedule = self.vesting_schedule_of(&account_id);
+                    schedule.claimed = schedule.claimed.0.saturating_sub(amount.0).into();
+                    self.vesting_schedules.insert(&account_id, &schedule);
+
+                    near_sdk::env::log(format!("Claim of {} by {} failed", amount.0, account_id).as_bytes());
+
+                    false
+                }
+
+                fn claimable(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+                    match self.vesting_schedules.get(&account_id) {
+                        Some(schedule) => {
+                            (Self::vesting_vested(&schedule, near_sdk::env::block_timestamp()) - schedule.claimed.0).into()
+                        }
+                        None => near_sdk::json_types::U128(0),
+                    }
+                }
+
+                fn vesting_schedule(&self, account_id: near_sdk::AccountId) -> Option<VestingSchedule> {
+                    self.vesting_schedules.get(&account_id)
+                }
+
+                fn vesting_token(&self) -> Option<near_sdk::AccountId> {
+                    self.vesting_token.clone()
+                }
+
+                fn vesting_unallocated(&self) -> near_sdk::json_types::U128 {
+                    self.vesting_unallocated.into()
+                }
+            }
+
+            impl #item_ident {
+                pub const VESTING_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+                pub const VESTING_GAS_FOR_CLAIM_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+                pub const VESTING_GAS_FOR_REVOKE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+
+                // `None` vests native NEAR. Call it in the constructor, before any schedule is created
+                pub fn setup_vesting(&mut self, token_id: Option<near_sdk::AccountId>) {
+                    if let Some(token_id) = &token_id {
+                        if !near_sdk::env::is_valid_account_id(token_id.as_bytes()) {
+                            near_sdk::env::panic(format!("Account {} is invalid", token_id).as_bytes())
+                        }
+                    }
+
+                    self.vesting_token = token_id;
+                }
+
+                // Books tokens received by the contract for new schedules. Call it from `ft_on_transfer`
+                pub fn vesting_fund(&mut self, amount: near_sdk::Balance) {
+                    let token_id = near_sdk::env::predecessor_account_id();
+
+                    if self.vesting_token.as_ref() != Some(&token_id) {
+                        near_sdk::env::panic(format!("Token {} is not vested", token_id).as_bytes())
+                    }
+
+                    self.vesting_unallocated = self
+                        .vesting_unallocated
+                        .checked_add(amount)
+                        .unwrap_or_else(|| near_sdk::env::panic(b"Vesting funds overflow"));
+
+                    near_sdk::env::log(format!("{} tokens are added to the vesting funds", amount).as_bytes());
+                }
+
+                fn vesting_schedule_of(&self, account_id: &near_sdk::AccountId) -> VestingSchedule {
+                    self.vesting_schedules.get(account_id).unwrap_or_else(|| {
+                        near_sdk::env::panic(format!("Account {} has no vesting schedule", account_id).as_bytes())
+                    })
+                }
+
+                // Nothing before the cliff, then linear from the start until the end of the duration
+                fn vesting_vested(schedule: &VestingSchedule, now: u64) -> near_sdk::Balance {
+                    let total = schedule.total.0;
+
+                    if schedule.revoked || now >= schedule.start + schedule.duration {
+                        return total;
+                    }
+
+                    if now < schedule.cliff {
+                        return 0;
+                    }
+
+                    let elapsed = (now - schedule.start) as u128;
+                    let duration = schedule.duration as u128;
+
+                    // Split to avoid overflowing `total * elapsed`
+                    total / duration * elapsed + total % duration * elapsed / duration
+                }
+
+                fn vesting_transfer(&self, receiver_id: near_sdk::AccountId, amount: near_sdk::Balance) -> near_sdk::Promise {
+                    match &self.vesting_token {
+                        Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(
+                            b"ft_transfer".to_vec(),
+                            near_sdk::serde_json::json!({
+                                "receiver_id": receiver_id,
+                                "amount": near_sdk::json_types::U128(amount),
+                                "memo": "Vesting",
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            1,
+                            Self::VESTING_GAS_FOR_FT_TRANSFER,
+                        ),
+                        None => near_sdk::Promise::new(receiver_id).transfer(amount),
+                    }
+                }
+            }
+
+    };
+
+    (
+        quote! {#core_ts}.into(),
+        quote! {
+            vesting_schedules: near_sdk::collections::LookupMap<near_sdk::AccountId, VestingSchedule>,
+            vesting_token: Option<near_sdk::AccountId>,
+            vesting_unallocated: near_sdk::Balance,
+        }
+        .into(),
+    )
+}
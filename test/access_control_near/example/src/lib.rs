@@ -1,17 +1,19 @@
 use near_sdk::borsh;
 use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault};
 
-use near_macros::{init_macro, require};
+use near_macros::{init_macro, require, when_not_paused};
 
 near_sdk::setup_alloc!();
 
 const DEFAULT_ADMIN: &str = "default_admin";
 const MINTER: &str = "minter";
 const MANAGER: &str = "manager";
+const GUARDIAN: &str = "guardian";
+const GOVERNANCE: &str = "governance";
 
 #[near_bindgen]
 #[derive(PanicOnDefault)]
-#[require(Access, Pausable)]
+#[require(Access, Pausable(pauser = "guardian", unpauser = "governance"))]
 pub struct StatusMessage {
     data: String,
 }
@@ -41,8 +43,8 @@ impl StatusMessage {
         constructor
     }
 
+    #[when_not_paused]
     pub fn get_data(&self) -> String {
-        self.when_not_paused();
         self.data.clone()
     }
 
@@ -370,6 +372,99 @@ mod tests {
         contract.grant_role(&"manager".to_string(), &"mike.testnet".to_string());
     }
 
+    #[test]
+    fn pauser_role_can_pause() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::without_setup(String::from("SOME DATA"));
+
+        contract.setup_account_role(&GUARDIAN.to_string(), &"mike.testnet".to_string());
+        contract.setup_account_role(&GOVERNANCE.to_string(), &"timur.testnet".to_string());
+
+        testing_env!(get_context(String::from("mike.testnet"), 0));
+
+        contract.pause(Some(String::from("Incident")));
+
+        assert_eq!(contract.is_paused(), true);
+
+        testing_env!(get_context(String::from("timur.testnet"), 0));
+
+        contract.unpause();
+
+        assert_eq!(contract.is_paused(), false);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mike.testnet , is missing: governance role"#)]
+    fn pauser_role_cannot_unpause() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::without_setup(String::from("SOME DATA"));
+
+        contract.setup_account_role(&GUARDIAN.to_string(), &"mike.testnet".to_string());
+        contract.setup_account_role(&GOVERNANCE.to_string(), &"timur.testnet".to_string());
+
+        testing_env!(get_context(String::from("mike.testnet"), 0));
+
+        contract.pause(None);
+        contract.unpause();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account jane.testnet , is missing: guardian role"#)]
+    fn pause_requires_pauser_role() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::without_setup(String::from("SOME DATA"));
+
+        contract.setup_account_role(&GUARDIAN.to_string(), &"mike.testnet".to_string());
+
+        testing_env!(get_context(String::from("jane.testnet"), 0));
+
+        contract.pause(None);
+    }
+
+    #[test]
+    fn pauser_role_can_pause_feature() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::without_setup(String::from("SOME DATA"));
+
+        contract.setup_account_role(&GUARDIAN.to_string(), &"mike.testnet".to_string());
+        contract.setup_account_role(&GOVERNANCE.to_string(), &"timur.testnet".to_string());
+
+        testing_env!(get_context(String::from("mike.testnet"), 0));
+
+        contract.pause_feature(String::from("transfer"));
+
+        assert_eq!(contract.is_feature_paused("transfer"), true);
+
+        testing_env!(get_context(String::from("timur.testnet"), 0));
+
+        contract.unpause_feature(String::from("transfer"));
+
+        assert_eq!(contract.is_feature_paused("transfer"), false);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account jane.testnet , is missing: guardian role"#)]
+    fn pause_for_requires_pauser_role() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::without_setup(String::from("SOME DATA"));
+
+        contract.setup_account_role(&GUARDIAN.to_string(), &"mike.testnet".to_string());
+
+        testing_env!(get_context(String::from("jane.testnet"), 0));
+
+        contract.pause_for(1_000, None);
+    }
+
     // #[test]
     // fn only_role_admin_can_grant_role() {
     //     let context = get_context(String::from("jane.testnet"), 0);
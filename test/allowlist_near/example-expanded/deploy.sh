@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "admin": "timurguvenkaya.testnet", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near call $ACCOUNT add_to_allowlist '{"account_ids": ["app.timurguvenkaya.testnet"]}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT is_allowlisted '{"account_id": "app.timurguvenkaya.testnet"}'
+
+near call $ACCOUNT set_data '{"data": "NEW_DATA", "proof": []}' --accountId app.timurguvenkaya.testnet
+
+# Should fail because the account is not allowlisted
+near call $ACCOUNT set_data '{"data": "NEW_DATA", "proof": []}' --accountId timurguvenkaya.testnet
@@ -0,0 +1,492 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const ALLOWLIST_ADMIN: &str = "allowlist_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    allowlist: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    allowlist_root: Option<String>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyAllowlist {
+    Allowed,
+}
+
+impl StorageKeyAllowlist {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAllowlist::Allowed => "alw".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait Allowlist {
+    fn add_to_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>);
+    fn remove_from_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>);
+    fn set_allowlist_root(&mut self, root: Option<String>);
+    fn allowlist_root(&self) -> Option<String>;
+    fn is_allowlisted(&self, account_id: near_sdk::AccountId) -> bool;
+    fn verify_allowlisted(
+        &self,
+        account_id: near_sdk::AccountId,
+        proof: std::vec::Vec<String>,
+    ) -> bool;
+    fn assert_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>);
+}
+
+#[near_bindgen]
+impl Allowlist for StatusMessage {
+    fn add_to_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>) {
+        self.assert_role(&"allowlist_admin".to_string());
+        for account_id in account_ids.iter() {
+            if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+                near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+            }
+            if self.allowlist.insert(account_id) {
+                near_sdk::env::log(format!("Account {} is allowlisted", account_id).as_bytes())
+            }
+        }
+    }
+
+    fn remove_from_allowlist(&mut self, account_ids: std::vec::Vec<near_sdk::AccountId>) {
+        self.assert_role(&"allowlist_admin".to_string());
+        for account_id in account_ids.iter() {
+            if self.allowlist.remove(account_id) {
+                near_sdk::env::log(
+                    format!("Account {} is removed from the allowlist", account_id).as_bytes(),
+                )
+            }
+        }
+    }
+
+    fn set_allowlist_root(&mut self, root: Option<String>) {
+        self.assert_role(&"allowlist_admin".to_string());
+        let root = root.map(|root| {
+            Self::allowlist_decode_hash(&root);
+            root.to_lowercase()
+        });
+        near_sdk::env::log(
+            format!(
+                "Allowlist root is set to {} by {}",
+                root.as_deref().unwrap_or("none"),
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        );
+        self.allowlist_root = root;
+    }
+
+    fn allowlist_root(&self) -> Option<String> {
+        self.allowlist_root.clone()
+    }
+
+    fn is_allowlisted(&self, account_id: near_sdk::AccountId) -> bool {
+        self.allowlist.contains(&account_id)
+    }
+
+    fn verify_allowlisted(
+        &self,
+        account_id: near_sdk::AccountId,
+        proof: std::vec::Vec<String>,
+    ) -> bool {
+        if self.allowlist.contains(&account_id) {
+            return true;
+        }
+        let root = match &self.allowlist_root {
+            Some(root) => root,
+            None => return false,
+        };
+        let mut hash = near_sdk::env::sha256(&[&[0u8], account_id.as_bytes()].concat());
+        for sibling in proof.iter() {
+            let sibling = Self::allowlist_decode_hash(sibling);
+            hash = if hash <= sibling {
+                near_sdk::env::sha256(&[&[1u8], &hash[..], &sibling[..]].concat())
+            } else {
+                near_sdk::env::sha256(&[&[1u8], &sibling[..], &hash[..]].concat())
+            };
+        }
+        Self::allowlist_encode_hash(&hash) == *root
+    }
+
+    fn assert_allowlisted(&self, account_id: near_sdk::AccountId, proof: std::vec::Vec<String>) {
+        if !self.verify_allowlisted(account_id.clone(), proof) {
+            near_sdk::env::panic(format!("Account {} is not allowlisted", account_id).as_bytes())
+        }
+    }
+}
+
+impl StatusMessage {
+    fn allowlist_encode_hash(hash: &[u8]) -> String {
+        hash.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn allowlist_decode_hash(hash: &str) -> std::vec::Vec<u8> {
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            near_sdk::env::panic(format!("Hash {} is not a hex-encoded sha256", hash).as_bytes())
+        }
+        (0..32)
+            .map(|i| u8::from_str_radix(&hash[2 * i..2 * i + 2], 16).unwrap())
+            .collect()
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, admin: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: data,
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            allowlist: near_sdk::collections::LookupSet::new(
+                StorageKeyAllowlist::Allowed.into_bytes(),
+            ),
+            allowlist_root: None,
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&ALLOWLIST_ADMIN.to_string(), &admin);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Accounts from the on-chain allowlist pass an empty proof
+    pub fn set_data(&mut self, data: String, proof: Vec<String>) {
+        self.assert_allowlisted(env::predecessor_account_id(), proof);
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn hex(hash: &[u8]) -> String {
+        hash.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn leaf(account_id: &str) -> Vec<u8> {
+        env::sha256(&[&[0u8][..], account_id.as_bytes()].concat())
+    }
+
+    fn node(a: &[u8], b: &[u8]) -> Vec<u8> {
+        if a <= b {
+            env::sha256(&[&[1u8][..], a, b].concat())
+        } else {
+            env::sha256(&[&[1u8][..], b, a].concat())
+        }
+    }
+
+    // Tree of bob, carol, dave and erin. Returns the root and the proof of bob
+    fn tree() -> (String, Vec<String>) {
+        let (bob, carol) = (leaf("bob.testnet"), leaf("carol.testnet"));
+        let (dave, erin) = (leaf("dave.testnet"), leaf("erin.testnet"));
+
+        let left = node(&bob, &carol);
+        let right = node(&dave, &erin);
+
+        (hex(&node(&left, &right)), vec![hex(&carol), hex(&right)])
+    }
+
+    fn setup() -> StatusMessage {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("admin.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_allow_accounts_from_set() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.add_to_allowlist(vec![String::from("bob.testnet")]);
+
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), true);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), vec![]);
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.remove_from_allowlist(vec![String::from("bob.testnet")]);
+
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), false);
+    }
+
+    #[test]
+    fn should_verify_merkle_proof() {
+        let mut contract = setup();
+        let (root, proof) = tree();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(root.to_uppercase()));
+
+        assert_eq!(contract.allowlist_root(), Some(root));
+        assert_eq!(
+            contract.verify_allowlisted(String::from("bob.testnet"), proof.clone()),
+            true
+        );
+        assert_eq!(
+            contract.verify_allowlisted(String::from("mallory.testnet"), proof.clone()),
+            false
+        );
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), false);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), proof);
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet is not allowlisted"#)]
+    fn should_reject_wrong_proof() {
+        let mut contract = setup();
+        let (root, proof) = tree();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(root));
+
+        testing_env!(get_context(String::from("mallory.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), proof);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not a hex-encoded sha256"#)]
+    fn should_reject_invalid_root() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(String::from("root")));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet , is missing: allowlist_admin role"#)]
+    fn only_admin_can_change_allowlist() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.add_to_allowlist(vec![String::from("bob.testnet")]);
+    }
+}
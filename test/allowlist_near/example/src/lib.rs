@@ -0,0 +1,192 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const ALLOWLIST_ADMIN: &str = "allowlist_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Allowlist)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, admin: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!(["access", "allowlist", Self { data: data }]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&ALLOWLIST_ADMIN.to_string(), &admin);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Accounts from the on-chain allowlist pass an empty proof
+    pub fn set_data(&mut self, data: String, proof: Vec<String>) {
+        self.assert_allowlisted(env::predecessor_account_id(), proof);
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn hex(hash: &[u8]) -> String {
+        hash.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn leaf(account_id: &str) -> Vec<u8> {
+        env::sha256(&[&[0u8][..], account_id.as_bytes()].concat())
+    }
+
+    fn node(a: &[u8], b: &[u8]) -> Vec<u8> {
+        if a <= b {
+            env::sha256(&[&[1u8][..], a, b].concat())
+        } else {
+            env::sha256(&[&[1u8][..], b, a].concat())
+        }
+    }
+
+    // Tree of bob, carol, dave and erin. Returns the root and the proof of bob
+    fn tree() -> (String, Vec<String>) {
+        let (bob, carol) = (leaf("bob.testnet"), leaf("carol.testnet"));
+        let (dave, erin) = (leaf("dave.testnet"), leaf("erin.testnet"));
+
+        let left = node(&bob, &carol);
+        let right = node(&dave, &erin);
+
+        (hex(&node(&left, &right)), vec![hex(&carol), hex(&right)])
+    }
+
+    fn setup() -> StatusMessage {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("admin.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_allow_accounts_from_set() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.add_to_allowlist(vec![String::from("bob.testnet")]);
+
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), true);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), vec![]);
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.remove_from_allowlist(vec![String::from("bob.testnet")]);
+
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), false);
+    }
+
+    #[test]
+    fn should_verify_merkle_proof() {
+        let mut contract = setup();
+        let (root, proof) = tree();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(root.to_uppercase()));
+
+        assert_eq!(contract.allowlist_root(), Some(root));
+        assert_eq!(
+            contract.verify_allowlisted(String::from("bob.testnet"), proof.clone()),
+            true
+        );
+        assert_eq!(
+            contract.verify_allowlisted(String::from("mallory.testnet"), proof.clone()),
+            false
+        );
+        assert_eq!(contract.is_allowlisted(String::from("bob.testnet")), false);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), proof);
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet is not allowlisted"#)]
+    fn should_reject_wrong_proof() {
+        let mut contract = setup();
+        let (root, proof) = tree();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(root));
+
+        testing_env!(get_context(String::from("mallory.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"), proof);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not a hex-encoded sha256"#)]
+    fn should_reject_invalid_root() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("admin.testnet"), 0));
+
+        contract.set_allowlist_root(Some(String::from("root")));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet , is missing: allowlist_admin role"#)]
+    fn only_admin_can_change_allowlist() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.add_to_allowlist(vec![String::from("bob.testnet")]);
+    }
+}
@@ -0,0 +1,17 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near call $ACCOUNT block '{"account_id": "app.timurguvenkaya.testnet"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT is_blocked '{"account_id": "app.timurguvenkaya.testnet"}'
+
+# Should fail because the account is blocked
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId app.timurguvenkaya.testnet
+
+near call $ACCOUNT unblock '{"account_id": "app.timurguvenkaya.testnet"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId app.timurguvenkaya.testnet
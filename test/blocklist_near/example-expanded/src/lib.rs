@@ -0,0 +1,277 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    owner: near_sdk::AccountId,
+    pending_owner: Option<near_sdk::AccountId>,
+    blocklist: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+}
+
+pub trait Ownable {
+    fn owner(&self) -> near_sdk::AccountId;
+    fn pending_owner(&self) -> Option<near_sdk::AccountId>;
+    fn assert_owner(&self);
+    fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId);
+    fn accept_ownership(&mut self);
+    fn renounce_ownership(&mut self);
+}
+
+#[near_bindgen]
+impl Ownable for StatusMessage {
+    fn owner(&self) -> near_sdk::AccountId {
+        self.owner.clone()
+    }
+
+    fn pending_owner(&self) -> Option<near_sdk::AccountId> {
+        self.pending_owner.clone()
+    }
+
+    fn assert_owner(&self) {
+        let caller = near_sdk::env::predecessor_account_id();
+        if self.owner.is_empty() || caller != self.owner {
+            near_sdk::env::panic(format!("Account {} is not the owner", caller).as_bytes())
+        }
+    }
+
+    fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId) {
+        self.assert_owner();
+        if !near_sdk::env::is_valid_account_id(new_owner.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", new_owner).as_bytes())
+        }
+        near_sdk::env::log(
+            format!(
+                "Ownership transfer from {} to {} is started",
+                self.owner, new_owner
+            )
+            .as_bytes(),
+        );
+        self.pending_owner = Some(new_owner);
+    }
+
+    fn accept_ownership(&mut self) {
+        let caller = near_sdk::env::predecessor_account_id();
+        if self.pending_owner.as_ref() != Some(&caller) {
+            near_sdk::env::panic(format!("Account {} is not the pending owner", caller).as_bytes())
+        }
+        near_sdk::env::log(
+            format!("Ownership is transferred from {} to {}", self.owner, caller).as_bytes(),
+        );
+        self.owner = caller;
+        self.pending_owner = None;
+    }
+
+    fn renounce_ownership(&mut self) {
+        self.assert_owner();
+        near_sdk::env::log(format!("Ownership is renounced by {}", self.owner).as_bytes());
+        self.owner = String::new();
+        self.pending_owner = None;
+    }
+}
+
+pub enum StorageKeyBlocklist {
+    Blocked,
+}
+
+impl StorageKeyBlocklist {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyBlocklist::Blocked => "blk".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait Blocklist {
+    fn block(&mut self, account_id: near_sdk::AccountId);
+    fn unblock(&mut self, account_id: near_sdk::AccountId);
+    fn is_blocked(&self, account_id: near_sdk::AccountId) -> bool;
+    fn assert_not_blocked(&self, account_id: near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl Blocklist for StatusMessage {
+    fn block(&mut self, account_id: near_sdk::AccountId) {
+        self.assert_owner();
+        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+        }
+        if !self.blocklist.insert(&account_id) {
+            near_sdk::env::panic(format!("Account {} is already blocked", account_id).as_bytes())
+        }
+        near_sdk::env::log(
+            format!(
+                "Account {} is blocked by {}",
+                account_id,
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn unblock(&mut self, account_id: near_sdk::AccountId) {
+        self.assert_owner();
+        if !self.blocklist.remove(&account_id) {
+            near_sdk::env::panic(format!("Account {} is not blocked", account_id).as_bytes())
+        }
+        near_sdk::env::log(
+            format!(
+                "Account {} is unblocked by {}",
+                account_id,
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn is_blocked(&self, account_id: near_sdk::AccountId) -> bool {
+        self.blocklist.contains(&account_id)
+    }
+
+    fn assert_not_blocked(&self, account_id: near_sdk::AccountId) {
+        if self.blocklist.contains(&account_id) {
+            near_sdk::env::panic(format!("Account {} is blocked", account_id).as_bytes())
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: data,
+            owner: owner,
+            pending_owner: None,
+            blocklist: near_sdk::collections::LookupSet::new(
+                StorageKeyBlocklist::Blocked.into_bytes(),
+            ),
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        self.assert_not_blocked(near_sdk::env::predecessor_account_id());
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn setup() -> StatusMessage {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_block_and_unblock() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+
+        assert_eq!(contract.is_blocked(String::from("bob.testnet")), true);
+        assert_eq!(contract.is_blocked(String::from("jane.testnet")), false);
+
+        contract.unblock(String::from("bob.testnet"));
+
+        assert_eq!(contract.is_blocked(String::from("bob.testnet")), false);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is blocked"#)]
+    fn blocked_account_cannot_call() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is not the owner"#)]
+    fn only_owner_can_block() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.block(String::from("jane.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is already blocked"#)]
+    fn should_not_block_twice() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+        contract.block(String::from("bob.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is not blocked"#)]
+    fn should_not_unblock_unknown_account() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.unblock(String::from("bob.testnet"));
+    }
+}
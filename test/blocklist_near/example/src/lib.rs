@@ -0,0 +1,146 @@
+use near_macros::{init_macro, not_blocked, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Ownable, Blocklist)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "ownable",
+            "blocklist",
+            Self {
+                data: data,
+                owner: owner
+            }
+        ]);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    #[not_blocked]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn setup() -> StatusMessage {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_block_and_unblock() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+
+        assert_eq!(contract.is_blocked(String::from("bob.testnet")), true);
+        assert_eq!(contract.is_blocked(String::from("jane.testnet")), false);
+
+        contract.unblock(String::from("bob.testnet"));
+
+        assert_eq!(contract.is_blocked(String::from("bob.testnet")), false);
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is blocked"#)]
+    fn blocked_account_cannot_call() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is not the owner"#)]
+    fn only_owner_can_block() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("bob.testnet"), 0));
+
+        contract.block(String::from("jane.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is already blocked"#)]
+    fn should_not_block_twice() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.block(String::from("bob.testnet"));
+        contract.block(String::from("bob.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is not blocked"#)]
+    fn should_not_unblock_unknown_account() {
+        let mut contract = setup();
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.unblock(String::from("bob.testnet"));
+    }
+}
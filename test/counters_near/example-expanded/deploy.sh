@@ -0,0 +1,12 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{}' --accountId $ACCOUNT
+
+near call $ACCOUNT place_order '{"data": "FIRST ORDER"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT place_order '{"data": "SECOND ORDER"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT counters '{}'
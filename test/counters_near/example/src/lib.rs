@@ -0,0 +1,118 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::collections::LookupMap;
+use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Counters(orders, proposals))]
+pub struct StatusMessage {
+    orders: LookupMap<u64, String>,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new() -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        init_macro!([
+            "counters",
+            Self {
+                orders: LookupMap::new(b"o".to_vec())
+            }
+        ])
+    }
+
+    pub fn place_order(&mut self, data: String) -> u64 {
+        let id = self.next_orders_id();
+
+        self.orders.insert(&id, &data);
+
+        id
+    }
+
+    pub fn get_order(&self, id: u64) -> Option<String> {
+        self.orders.get(&id)
+    }
+
+    pub fn propose(&mut self) -> u64 {
+        self.next_proposals_id()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn setup() -> StatusMessage {
+        let context = get_context(String::from("bob.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::new()
+    }
+
+    #[test]
+    fn should_issue_sequential_ids() {
+        let mut contract = setup();
+
+        assert_eq!(contract.place_order(String::from("FIRST")), 0);
+        assert_eq!(contract.place_order(String::from("SECOND")), 1);
+
+        assert_eq!(contract.get_order(1), Some(String::from("SECOND")));
+        assert_eq!(contract.counter_value("orders"), 2);
+    }
+
+    #[test]
+    fn counters_are_independent() {
+        let mut contract = setup();
+
+        contract.place_order(String::from("FIRST"));
+        contract.place_order(String::from("SECOND"));
+
+        assert_eq!(contract.propose(), 0);
+
+        assert_eq!(
+            contract.counters(),
+            vec![(String::from("orders"), 2), (String::from("proposals"), 1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Counter proposals overflowed"#)]
+    fn should_not_overflow() {
+        let mut contract = setup();
+
+        contract
+            .counter_values
+            .insert(&String::from("proposals"), &u64::MAX);
+
+        contract.propose();
+    }
+}
@@ -0,0 +1,580 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    escrows: near_sdk::collections::UnorderedMap<u64, EscrowRecord>,
+    escrow_next_id: u64,
+}
+
+pub enum StorageKeyEscrow {
+    Records,
+}
+
+impl StorageKeyEscrow {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyEscrow::Records => "esc".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum EscrowStatus {
+    Active,
+    Releasing,
+    Refunding,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowRecord {
+    pub depositor: near_sdk::AccountId,
+    pub beneficiary: near_sdk::AccountId,
+    pub amount: near_sdk::json_types::U128,
+    pub token_id: Option<near_sdk::AccountId>,
+    pub deadline: u64,
+    pub arbiter: Option<near_sdk::AccountId>,
+    pub disputed: bool,
+    pub status: EscrowStatus,
+}
+
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowArgs {
+    pub beneficiary: near_sdk::AccountId,
+    pub duration_secs: u64,
+    pub arbiter: Option<near_sdk::AccountId>,
+}
+
+pub trait Escrow {
+    fn escrow_deposit(
+        &mut self,
+        beneficiary: near_sdk::AccountId,
+        duration_secs: u64,
+        arbiter: Option<near_sdk::AccountId>,
+    ) -> u64;
+    fn escrow_release(&mut self, id: u64) -> near_sdk::Promise;
+    fn refund_after_deadline(&mut self, id: u64) -> near_sdk::Promise;
+    fn escrow_dispute(&mut self, id: u64);
+    fn resolve_dispute(&mut self, id: u64, release: bool) -> near_sdk::Promise;
+    fn escrow_on_payout(&mut self, id: u64) -> bool;
+    fn escrow_get(&self, id: u64) -> Option<EscrowRecord>;
+    fn escrows(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, EscrowRecord)>;
+}
+
+#[near_bindgen]
+impl Escrow for StatusMessage {
+    #[payable]
+    fn escrow_deposit(
+        &mut self,
+        beneficiary: near_sdk::AccountId,
+        duration_secs: u64,
+        arbiter: Option<near_sdk::AccountId>,
+    ) -> u64 {
+        self.escrow_create(
+            near_sdk::env::predecessor_account_id(),
+            None,
+            near_sdk::env::attached_deposit(),
+            EscrowArgs {
+                beneficiary,
+                duration_secs,
+                arbiter,
+            },
+        )
+    }
+
+    fn escrow_release(&mut self, id: u64) -> near_sdk::Promise {
+        let record = self.escrow_active(id);
+        if near_sdk::env::predecessor_account_id() != record.depositor {
+            near_sdk::env::panic(format!("Only the depositor can release escrow {}", id).as_bytes())
+        }
+        if record.disputed {
+            near_sdk::env::panic(format!("Escrow {} is disputed", id).as_bytes())
+        }
+        self.escrow_payout(id, record, true)
+    }
+
+    fn refund_after_deadline(&mut self, id: u64) -> near_sdk::Promise {
+        let record = self.escrow_active(id);
+        if record.disputed {
+            near_sdk::env::panic(format!("Escrow {} is disputed", id).as_bytes())
+        }
+        if near_sdk::env::block_timestamp() < record.deadline {
+            near_sdk::env::panic(
+                format!("Escrow {} is locked until {}", id, record.deadline).as_bytes(),
+            )
+        }
+        self.escrow_payout(id, record, false)
+    }
+
+    fn escrow_dispute(&mut self, id: u64) {
+        let mut record = self.escrow_active(id);
+        let caller = near_sdk::env::predecessor_account_id();
+        if caller != record.depositor && caller != record.beneficiary {
+            near_sdk::env::panic(
+                format!(
+                    "Only the depositor or the beneficiary can dispute escrow {}",
+                    id
+                )
+                .as_bytes(),
+            )
+        }
+        if record.arbiter.is_none() {
+            near_sdk::env::panic(format!("Escrow {} has no arbiter", id).as_bytes())
+        }
+        if record.disputed {
+            near_sdk::env::panic(format!("Escrow {} is already disputed", id).as_bytes())
+        }
+        record.disputed = true;
+        self.escrows.insert(&id, &record);
+        near_sdk::env::log(format!("Escrow {} is disputed by {}", id, caller).as_bytes());
+    }
+
+    fn resolve_dispute(&mut self, id: u64, release: bool) -> near_sdk::Promise {
+        let record = self.escrow_active(id);
+        if Some(near_sdk::env::predecessor_account_id()) != record.arbiter {
+            near_sdk::env::panic(format!("Only the arbiter can resolve escrow {}", id).as_bytes())
+        }
+        if !record.disputed {
+            near_sdk::env::panic(format!("Escrow {} is not disputed", id).as_bytes())
+        }
+        self.escrow_payout(id, record, release)
+    }
+
+    #[private]
+    fn escrow_on_payout(&mut self, id: u64) -> bool {
+        let mut record = self.escrows.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Escrow {} does not exist", id).as_bytes())
+        });
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            let receiver = match record.status {
+                EscrowStatus::Releasing => &record.beneficiary,
+                _ => &record.depositor,
+            };
+            near_sdk::env::log(format!("Escrow {} is paid out to {}", id, receiver).as_bytes());
+            self.escrows.remove(&id);
+            return true;
+        }
+        near_sdk::env::log(format!("Payout of escrow {} failed", id).as_bytes());
+        record.status = EscrowStatus::Active;
+        self.escrows.insert(&id, &record);
+        false
+    }
+
+    fn escrow_get(&self, id: u64) -> Option<EscrowRecord> {
+        self.escrows.get(&id)
+    }
+
+    fn escrows(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, EscrowRecord)> {
+        self.escrows
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+impl StatusMessage {
+    pub const ESCROW_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+    pub const ESCROW_GAS_FOR_PAYOUT_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub fn escrow_on_ft_transfer(
+        &mut self,
+        sender_id: near_sdk::AccountId,
+        amount: near_sdk::Balance,
+        msg: String,
+    ) -> u64 {
+        let args = near_sdk::serde_json::from_str::<EscrowArgs>(&msg).unwrap_or_else(|_| {
+            near_sdk::env::panic(
+                b"msg has to be a JSON object with beneficiary, duration_secs and arbiter",
+            )
+        });
+        self.escrow_create(
+            sender_id,
+            Some(near_sdk::env::predecessor_account_id()),
+            amount,
+            args,
+        )
+    }
+
+    fn escrow_create(
+        &mut self,
+        depositor: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::Balance,
+        args: EscrowArgs,
+    ) -> u64 {
+        if amount == 0 {
+            near_sdk::env::panic(b"The amount should be a positive number")
+        }
+        if !near_sdk::env::is_valid_account_id(args.beneficiary.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", args.beneficiary).as_bytes())
+        }
+        if args.beneficiary == depositor {
+            near_sdk::env::panic(b"The depositor and the beneficiary should be different")
+        }
+        if let Some(arbiter) = &args.arbiter {
+            if !near_sdk::env::is_valid_account_id(arbiter.as_bytes()) {
+                near_sdk::env::panic(format!("Account {} is invalid", arbiter).as_bytes())
+            }
+            if *arbiter == depositor || *arbiter == args.beneficiary {
+                near_sdk::env::panic(b"The arbiter cannot be a party of the escrow")
+            }
+        }
+        if args.duration_secs == 0 {
+            near_sdk::env::panic(b"duration_secs has to be positive")
+        }
+        let deadline = args
+            .duration_secs
+            .checked_mul(1_000_000_000)
+            .and_then(|duration| near_sdk::env::block_timestamp().checked_add(duration))
+            .unwrap_or_else(|| near_sdk::env::panic(b"duration_secs is too large"));
+        let id = self.escrow_next_id;
+        self.escrow_next_id += 1;
+        near_sdk::env::log(
+            format!(
+                "Escrow {} of {} {} is created by {} for {}",
+                id,
+                amount,
+                token_id.as_deref().unwrap_or("yoctoNEAR"),
+                depositor,
+                args.beneficiary
+            )
+            .as_bytes(),
+        );
+        self.escrows.insert(
+            &id,
+            &EscrowRecord {
+                depositor,
+                beneficiary: args.beneficiary,
+                amount: amount.into(),
+                token_id,
+                deadline,
+                arbiter: args.arbiter,
+                disputed: false,
+                status: EscrowStatus::Active,
+            },
+        );
+        id
+    }
+
+    fn escrow_active(&self, id: u64) -> EscrowRecord {
+        let record = self.escrows.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Escrow {} does not exist", id).as_bytes())
+        });
+        if record.status != EscrowStatus::Active {
+            near_sdk::env::panic(format!("Escrow {} is being paid out", id).as_bytes())
+        }
+        record
+    }
+
+    fn escrow_payout(
+        &mut self,
+        id: u64,
+        mut record: EscrowRecord,
+        release: bool,
+    ) -> near_sdk::Promise {
+        let receiver = if release {
+            record.status = EscrowStatus::Releasing;
+            record.beneficiary.clone()
+        } else {
+            record.status = EscrowStatus::Refunding;
+            record.depositor.clone()
+        };
+        self.escrows.insert(&id, &record);
+        let payout = match &record.token_id {
+            Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(
+                b"ft_transfer".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "receiver_id" : receiver, "amount" : record.amount, "memo" :
+                    format! ("Escrow {}", id),
+                })
+                .to_string()
+                .into_bytes(),
+                1,
+                Self::ESCROW_GAS_FOR_FT_TRANSFER,
+            ),
+            None => near_sdk::Promise::new(receiver).transfer(record.amount.0),
+        };
+        payout.then(
+            near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                b"escrow_on_payout".to_vec(),
+                near_sdk::serde_json::json!
+        ({ "id" : id })
+                .to_string()
+                .into_bytes(),
+                0,
+                Self::ESCROW_GAS_FOR_PAYOUT_CALLBACK,
+            ),
+        )
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new() -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: String::from("SOME DATA"),
+            escrows: near_sdk::collections::UnorderedMap::new(
+                StorageKeyEscrow::Records.into_bytes(),
+            ),
+            escrow_next_id: 0,
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Transferred tokens are escrowed with the arguments in `msg`
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.escrow_on_ft_transfer(sender_id, amount.0, msg);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+    const DAY: u64 = 86400;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    // bob deposits 100 yoctoNEAR for carol for a day, dave is the arbiter
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        let mut contract = StatusMessage::new();
+
+        at("bob.testnet", 0, 100);
+        contract.escrow_deposit(
+            String::from("carol.testnet"),
+            DAY,
+            Some(String::from("dave.testnet")),
+        );
+
+        contract
+    }
+
+    #[test]
+    fn should_deposit() {
+        let contract = setup();
+
+        let record = contract.escrow_get(0).unwrap();
+
+        assert_eq!(record.amount, U128(100));
+        assert_eq!(record.token_id, None);
+        assert_eq!(record.deadline, DAY * SECOND);
+        assert_eq!(record.status, EscrowStatus::Active);
+        assert_eq!(contract.escrows(0, 10).len(), 1);
+    }
+
+    #[test]
+    fn should_release() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(0);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Releasing
+        );
+
+        resolve(PromiseResult::Successful(vec![]));
+
+        assert!(contract.escrow_on_payout(0));
+        assert_eq!(contract.escrow_get(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the depositor can release escrow 0")]
+    fn should_panic_on_release_by_beneficiary() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_release(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is being paid out")]
+    fn should_panic_on_release_during_payout() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(0);
+        contract.escrow_release(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is locked until")]
+    fn should_panic_on_early_refund() {
+        let mut contract = setup();
+
+        at("bob.testnet", DAY - 1, 0);
+        contract.refund_after_deadline(0);
+    }
+
+    #[test]
+    fn should_refund_after_deadline() {
+        let mut contract = setup();
+
+        at("bob.testnet", DAY, 0);
+        contract.refund_after_deadline(0);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Refunding
+        );
+    }
+
+    #[test]
+    fn should_resolve_dispute() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_dispute(0);
+
+        assert!(contract.escrow_get(0).unwrap().disputed);
+
+        at("dave.testnet", 20, 0);
+        contract.resolve_dispute(0, true);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Releasing
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is disputed")]
+    fn should_panic_on_refund_of_disputed_escrow() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_dispute(0);
+
+        at("bob.testnet", DAY, 0);
+        contract.refund_after_deadline(0);
+    }
+
+    #[test]
+    fn should_unlock_after_failed_token_payout() {
+        let mut contract = setup();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(
+            String::from("bob.testnet"),
+            U128(500),
+            String::from(
+                r#"{"beneficiary": "carol.testnet", "duration_secs": 3600, "arbiter": null}"#,
+            ),
+        );
+
+        let record = contract.escrow_get(1).unwrap();
+
+        assert_eq!(record.token_id, Some(String::from("token.testnet")));
+        assert_eq!(record.depositor, "bob.testnet");
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(1);
+
+        // e.g. carol is not registered with the token
+        resolve(PromiseResult::Failed);
+
+        assert!(!contract.escrow_on_payout(1));
+        assert_eq!(contract.escrow_get(1).unwrap().status, EscrowStatus::Active);
+    }
+
+    #[test]
+    #[should_panic(expected = "msg has to be a JSON object")]
+    fn should_panic_on_invalid_msg() {
+        let mut contract = setup();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(
+            String::from("bob.testnet"),
+            U128(500),
+            String::from("carol"),
+        );
+    }
+}
@@ -0,0 +1,13 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{}' --accountId $ACCOUNT
+
+# Holds 1 NEAR for a week
+near call $ACCOUNT escrow_deposit '{"beneficiary": "app.timurguvenkaya.testnet", "duration_secs": 604800, "arbiter": null}' --accountId timurguvenkaya.testnet --deposit 1
+
+near view $ACCOUNT escrows '{"from_index": 0, "limit": 10}'
+
+near call $ACCOUNT escrow_release '{"id": 0}' --accountId timurguvenkaya.testnet --gas 50000000000000
@@ -0,0 +1,254 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Escrow)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new() -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "escrow",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Transferred tokens are escrowed with the arguments in `msg`
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.escrow_on_ft_transfer(sender_id, amount.0, msg);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+    const DAY: u64 = 86400;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    // bob deposits 100 yoctoNEAR for carol for a day, dave is the arbiter
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        let mut contract = StatusMessage::new();
+
+        at("bob.testnet", 0, 100);
+        contract.escrow_deposit(
+            String::from("carol.testnet"),
+            DAY,
+            Some(String::from("dave.testnet")),
+        );
+
+        contract
+    }
+
+    #[test]
+    fn should_deposit() {
+        let contract = setup();
+
+        let record = contract.escrow_get(0).unwrap();
+
+        assert_eq!(record.amount, U128(100));
+        assert_eq!(record.token_id, None);
+        assert_eq!(record.deadline, DAY * SECOND);
+        assert_eq!(record.status, EscrowStatus::Active);
+        assert_eq!(contract.escrows(0, 10).len(), 1);
+    }
+
+    #[test]
+    fn should_release() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(0);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Releasing
+        );
+
+        resolve(PromiseResult::Successful(vec![]));
+
+        assert!(contract.escrow_on_payout(0));
+        assert_eq!(contract.escrow_get(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the depositor can release escrow 0")]
+    fn should_panic_on_release_by_beneficiary() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_release(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is being paid out")]
+    fn should_panic_on_release_during_payout() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(0);
+        contract.escrow_release(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is locked until")]
+    fn should_panic_on_early_refund() {
+        let mut contract = setup();
+
+        at("bob.testnet", DAY - 1, 0);
+        contract.refund_after_deadline(0);
+    }
+
+    #[test]
+    fn should_refund_after_deadline() {
+        let mut contract = setup();
+
+        at("bob.testnet", DAY, 0);
+        contract.refund_after_deadline(0);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Refunding
+        );
+    }
+
+    #[test]
+    fn should_resolve_dispute() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_dispute(0);
+
+        assert!(contract.escrow_get(0).unwrap().disputed);
+
+        at("dave.testnet", 20, 0);
+        contract.resolve_dispute(0, true);
+
+        assert_eq!(
+            contract.escrow_get(0).unwrap().status,
+            EscrowStatus::Releasing
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Escrow 0 is disputed")]
+    fn should_panic_on_refund_of_disputed_escrow() {
+        let mut contract = setup();
+
+        at("carol.testnet", 10, 0);
+        contract.escrow_dispute(0);
+
+        at("bob.testnet", DAY, 0);
+        contract.refund_after_deadline(0);
+    }
+
+    #[test]
+    fn should_unlock_after_failed_token_payout() {
+        let mut contract = setup();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(
+            String::from("bob.testnet"),
+            U128(500),
+            String::from(
+                r#"{"beneficiary": "carol.testnet", "duration_secs": 3600, "arbiter": null}"#,
+            ),
+        );
+
+        let record = contract.escrow_get(1).unwrap();
+
+        assert_eq!(record.token_id, Some(String::from("token.testnet")));
+        assert_eq!(record.depositor, "bob.testnet");
+
+        at("bob.testnet", 10, 0);
+        contract.escrow_release(1);
+
+        // e.g. carol is not registered with the token
+        resolve(PromiseResult::Failed);
+
+        assert!(!contract.escrow_on_payout(1));
+        assert_eq!(contract.escrow_get(1).unwrap().status, EscrowStatus::Active);
+    }
+
+    #[test]
+    #[should_panic(expected = "msg has to be a JSON object")]
+    fn should_panic_on_invalid_msg() {
+        let mut contract = setup();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(
+            String::from("bob.testnet"),
+            U128(500),
+            String::from("carol"),
+        );
+    }
+}
@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"admin": "timurguvenkaya.testnet", "treasury": "app.timurguvenkaya.testnet"}' --accountId $ACCOUNT
+
+# Pays 1 NEAR for the data, 0.3% of which is kept as a fee
+near call $ACCOUNT set_data '{"data": "NEW DATA"}' --accountId timurguvenkaya.testnet --deposit 1
+
+near view $ACCOUNT accrued_fees '{}'
+
+near call $ACCOUNT set_fee_bps '{"fee_bps": 50}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT withdraw_fees '{"token_id": null}' --accountId timurguvenkaya.testnet --gas 50000000000000
@@ -0,0 +1,598 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const FEE_ADMIN: &str = "fee_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    paid: u128,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    fee_bps: u16,
+    fee_treasury: Option<near_sdk::AccountId>,
+    fee_accrued:
+        near_sdk::collections::UnorderedMap<Option<near_sdk::AccountId>, near_sdk::Balance>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyFeeCollector {
+    Accrued,
+}
+
+impl StorageKeyFeeCollector {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyFeeCollector::Accrued => "fee".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait FeeCollector {
+    fn set_fee_bps(&mut self, fee_bps: u16);
+    fn set_fee_treasury(&mut self, treasury: near_sdk::AccountId);
+    fn withdraw_fees(&mut self, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise;
+    fn fee_on_withdraw(
+        &mut self,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::json_types::U128,
+    ) -> bool;
+    fn fee_bps(&self) -> u16;
+    fn fee_max_bps(&self) -> u16;
+    fn fee_treasury(&self) -> Option<near_sdk::AccountId>;
+    fn accrued_fee(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+    fn accrued_fees(
+        &self,
+    ) -> std::vec::Vec<(Option<near_sdk::AccountId>, near_sdk::json_types::U128)>;
+}
+
+#[near_bindgen]
+impl FeeCollector for StatusMessage {
+    fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.assert_role(&"fee_admin".to_string());
+        Self::fee_assert_bps(fee_bps);
+        near_sdk::env::log(
+            format!(
+                "Fee is changed from {} to {} basis points",
+                self.fee_bps, fee_bps
+            )
+            .as_bytes(),
+        );
+        self.fee_bps = fee_bps;
+    }
+
+    fn set_fee_treasury(&mut self, treasury: near_sdk::AccountId) {
+        self.assert_role(&"fee_admin".to_string());
+        Self::fee_assert_treasury(&treasury);
+        near_sdk::env::log(format!("Treasury is set to {}", treasury).as_bytes());
+        self.fee_treasury = Some(treasury);
+    }
+
+    fn withdraw_fees(&mut self, token_id: Option<near_sdk::AccountId>) -> near_sdk::Promise {
+        self.assert_role(&"fee_admin".to_string());
+        let treasury = self
+            .fee_treasury
+            .clone()
+            .unwrap_or_else(|| near_sdk::env::panic(b"Treasury is not set"));
+        let amount = self.fee_accrued.remove(&token_id).unwrap_or(0);
+        if amount == 0 {
+            near_sdk::env::panic(b"There are no fees to withdraw")
+        }
+        near_sdk::env::log(
+            format!(
+                "{} {} of fees are withdrawn to {}",
+                amount,
+                token_id.as_deref().unwrap_or("yoctoNEAR"),
+                treasury
+            )
+            .as_bytes(),
+        );
+        let transfer = match & token_id
+        {
+            Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(b"ft_transfer".to_vec(),
+            near_sdk::serde_json::json!
+            ({
+                "receiver_id" : treasury, "amount" : near_sdk::json_types::U128(amount), "memo" : "Fees",
+            }).to_string().into_bytes(), 1, Self::FEE_GAS_FOR_FT_TRANSFER,),
+            None => near_sdk::Promise::new(treasury).transfer(amount),
+        };
+        transfer.then(
+            near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                b"fee_on_withdraw".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "token_id" : token_id, "amount" : near_sdk::json_types::U128(amount),
+                })
+                .to_string()
+                .into_bytes(),
+                0,
+                Self::FEE_GAS_FOR_WITHDRAW_CALLBACK,
+            ),
+        )
+    }
+
+    #[private]
+    fn fee_on_withdraw(
+        &mut self,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::json_types::U128,
+    ) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            return true;
+        }
+        self.fee_accrue(&token_id, amount.0);
+        near_sdk::env::log(format!("Withdrawal of {} of fees failed", amount.0).as_bytes());
+        false
+    }
+
+    fn fee_bps(&self) -> u16 {
+        self.fee_bps
+    }
+
+    fn fee_max_bps(&self) -> u16 {
+        Self::FEE_MAX_BPS
+    }
+
+    fn fee_treasury(&self) -> Option<near_sdk::AccountId> {
+        self.fee_treasury.clone()
+    }
+
+    fn accrued_fee(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+        self.fee_accrued.get(&token_id).unwrap_or(0).into()
+    }
+
+    fn accrued_fees(
+        &self,
+    ) -> std::vec::Vec<(Option<near_sdk::AccountId>, near_sdk::json_types::U128)> {
+        self.fee_accrued
+            .iter()
+            .map(|(token_id, amount)| (token_id, amount.into()))
+            .collect()
+    }
+}
+
+impl StatusMessage {
+    pub const FEE_MAX_BPS: u16 = 100u16;
+    pub const FEE_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+    pub const FEE_GAS_FOR_WITHDRAW_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub fn setup_fee_collector(&mut self, treasury: near_sdk::AccountId, fee_bps: u16) {
+        Self::fee_assert_treasury(&treasury);
+        Self::fee_assert_bps(fee_bps);
+        self.fee_treasury = Some(treasury);
+        self.fee_bps = fee_bps;
+    }
+
+    pub fn take_fee(
+        &mut self,
+        amount: near_sdk::Balance,
+    ) -> (near_sdk::Balance, near_sdk::Balance) {
+        self.fee_take(None, amount)
+    }
+
+    pub fn take_token_fee(
+        &mut self,
+        token_id: near_sdk::AccountId,
+        amount: near_sdk::Balance,
+    ) -> (near_sdk::Balance, near_sdk::Balance) {
+        self.fee_take(Some(token_id), amount)
+    }
+
+    fn fee_take(
+        &mut self,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::Balance,
+    ) -> (near_sdk::Balance, near_sdk::Balance) {
+        let bps = self.fee_bps as near_sdk::Balance;
+        let fee = amount / 10_000 * bps + (amount % 10_000 * bps + 9_999) / 10_000;
+        if fee > 0 {
+            self.fee_accrue(&token_id, fee);
+        }
+        (amount - fee, fee)
+    }
+
+    fn fee_assert_bps(fee_bps: u16) {
+        if fee_bps > Self::FEE_MAX_BPS {
+            near_sdk::env::panic(
+                format!(
+                    "Fee of {} basis points is above the maximum of {}",
+                    fee_bps,
+                    Self::FEE_MAX_BPS
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn fee_assert_treasury(treasury: &near_sdk::AccountId) {
+        if !near_sdk::env::is_valid_account_id(treasury.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", treasury).as_bytes())
+        }
+    }
+
+    fn fee_accrue(&mut self, token_id: &Option<near_sdk::AccountId>, amount: near_sdk::Balance) {
+        let accrued = self
+            .fee_accrued
+            .get(token_id)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Accrued fees overflow"));
+        self.fee_accrued.insert(token_id, &accrued);
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, treasury: AccountId) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            paid: 0,
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            fee_bps: 0,
+            fee_treasury: None,
+            fee_accrued: near_sdk::collections::UnorderedMap::new(
+                StorageKeyFeeCollector::Accrued.into_bytes(),
+            ),
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+        constructor.setup_account_role(&FEE_ADMIN.to_string(), &admin);
+
+        // 0.3%
+        constructor.setup_fee_collector(treasury, 30);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn get_paid(&self) -> U128 {
+        self.paid.into()
+    }
+
+    #[payable]
+    pub fn set_data(&mut self, data: String) {
+        let (net, _) = self.take_fee(env::attached_deposit());
+
+        self.paid += net;
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            String::from("treasury.testnet"),
+        )
+    }
+
+    fn token() -> AccountId {
+        String::from("token.testnet")
+    }
+
+    #[test]
+    fn should_take_fee() {
+        let mut contract = setup();
+
+        call("carol.testnet", 10_000);
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert_eq!(contract.get_paid(), U128(9_970));
+        assert_eq!(contract.accrued_fee(None), U128(30));
+    }
+
+    #[test]
+    fn should_round_fee_up() {
+        let mut contract = setup();
+
+        assert_eq!(contract.take_fee(0), (0, 0));
+        assert_eq!(contract.take_fee(1), (0, 1));
+        assert_eq!(contract.take_fee(10_001), (9_970, 31));
+        assert_eq!(
+            contract.take_fee(u128::MAX),
+            (
+                u128::MAX - u128::MAX / 10_000 * 30 - 5,
+                u128::MAX / 10_000 * 30 + 5
+            )
+        );
+    }
+
+    #[test]
+    fn should_accrue_per_token() {
+        let mut contract = setup();
+
+        contract.take_fee(10_000);
+        contract.take_token_fee(token(), 20_000);
+        contract.take_token_fee(token(), 20_000);
+
+        assert_eq!(contract.accrued_fee(None), U128(30));
+        assert_eq!(contract.accrued_fee(Some(token())), U128(120));
+        assert_eq!(contract.accrued_fees().len(), 2);
+    }
+
+    #[test]
+    fn should_change_fee() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.set_fee_bps(100);
+
+        assert_eq!(contract.fee_bps(), 100);
+        assert_eq!(contract.take_fee(10_000), (9_900, 100));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Fee of 101 basis points is above the maximum of 100"#)]
+    fn should_not_exceed_max_fee() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.set_fee_bps(101);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is missing: fee_admin role"#)]
+    fn only_fee_admin_can_change_fee() {
+        let mut contract = setup();
+
+        call("carol.testnet", 0);
+        contract.set_fee_bps(0);
+    }
+
+    #[test]
+    fn should_withdraw_fees() {
+        let mut contract = setup();
+
+        contract.take_token_fee(token(), 10_000);
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(Some(token()));
+
+        assert_eq!(contract.accrued_fee(Some(token())), U128(0));
+    }
+
+    #[test]
+    fn should_restore_fees_of_failed_withdrawal() {
+        let mut contract = setup();
+
+        contract.take_fee(10_000);
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(None);
+
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.fee_on_withdraw(None, U128(30)));
+        assert_eq!(contract.accrued_fee(None), U128(30));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"There are no fees to withdraw"#)]
+    fn should_not_withdraw_nothing() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(None);
+    }
+}
@@ -0,0 +1,219 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const FEE_ADMIN: &str = "fee_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, FeeCollector(max_bps = 100))]
+pub struct StatusMessage {
+    data: String,
+    paid: u128,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, treasury: AccountId) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "access",
+            "fee_collector",
+            Self {
+                data: String::from("SOME DATA"),
+                paid: 0
+            }
+        ]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+        constructor.setup_account_role(&FEE_ADMIN.to_string(), &admin);
+
+        // 0.3%
+        constructor.setup_fee_collector(treasury, 30);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn get_paid(&self) -> U128 {
+        self.paid.into()
+    }
+
+    #[payable]
+    pub fn set_data(&mut self, data: String) {
+        let (net, _) = self.take_fee(env::attached_deposit());
+
+        self.paid += net;
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            String::from("treasury.testnet"),
+        )
+    }
+
+    fn token() -> AccountId {
+        String::from("token.testnet")
+    }
+
+    #[test]
+    fn should_take_fee() {
+        let mut contract = setup();
+
+        call("carol.testnet", 10_000);
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert_eq!(contract.get_paid(), U128(9_970));
+        assert_eq!(contract.accrued_fee(None), U128(30));
+    }
+
+    #[test]
+    fn should_round_fee_up() {
+        let mut contract = setup();
+
+        assert_eq!(contract.take_fee(0), (0, 0));
+        assert_eq!(contract.take_fee(1), (0, 1));
+        assert_eq!(contract.take_fee(10_001), (9_970, 31));
+        assert_eq!(
+            contract.take_fee(u128::MAX),
+            (
+                u128::MAX - u128::MAX / 10_000 * 30 - 5,
+                u128::MAX / 10_000 * 30 + 5
+            )
+        );
+    }
+
+    #[test]
+    fn should_accrue_per_token() {
+        let mut contract = setup();
+
+        contract.take_fee(10_000);
+        contract.take_token_fee(token(), 20_000);
+        contract.take_token_fee(token(), 20_000);
+
+        assert_eq!(contract.accrued_fee(None), U128(30));
+        assert_eq!(contract.accrued_fee(Some(token())), U128(120));
+        assert_eq!(contract.accrued_fees().len(), 2);
+    }
+
+    #[test]
+    fn should_change_fee() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.set_fee_bps(100);
+
+        assert_eq!(contract.fee_bps(), 100);
+        assert_eq!(contract.take_fee(10_000), (9_900, 100));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Fee of 101 basis points is above the maximum of 100"#)]
+    fn should_not_exceed_max_fee() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.set_fee_bps(101);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is missing: fee_admin role"#)]
+    fn only_fee_admin_can_change_fee() {
+        let mut contract = setup();
+
+        call("carol.testnet", 0);
+        contract.set_fee_bps(0);
+    }
+
+    #[test]
+    fn should_withdraw_fees() {
+        let mut contract = setup();
+
+        contract.take_token_fee(token(), 10_000);
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(Some(token()));
+
+        assert_eq!(contract.accrued_fee(Some(token())), U128(0));
+    }
+
+    #[test]
+    fn should_restore_fees_of_failed_withdrawal() {
+        let mut contract = setup();
+
+        contract.take_fee(10_000);
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(None);
+
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.fee_on_withdraw(None, U128(30)));
+        assert_eq!(contract.accrued_fee(None), U128(30));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"There are no fees to withdraw"#)]
+    fn should_not_withdraw_nothing() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.withdraw_fees(None);
+    }
+}
@@ -0,0 +1,1082 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Balance, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const MINTER: &str = "minter";
+const PAUSER: &str = "pauser";
+const UNPAUSER: &str = "unpauser";
+
+// Covers the storage of a new balance
+const REGISTRATION_DEPOSIT: Balance = 1_250_000_000_000_000_000_000;
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    pause_status: bool,
+    ft_accounts: near_sdk::collections::LookupMap<near_sdk::AccountId, near_sdk::Balance>,
+    ft_total_supply: near_sdk::Balance,
+    ft_metadata: near_sdk::collections::LazyOption<FungibleTokenMetadata>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyPausable {
+    Info,
+    Features,
+    FeatureSet,
+}
+
+impl StorageKeyPausable {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyPausable::Info => "pai".to_string(),
+            StorageKeyPausable::Features => "paf".to_string(),
+            StorageKeyPausable::FeatureSet => "pas".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseInfo {
+    pub paused_by: near_sdk::AccountId,
+    pub paused_at: u64,
+    pub reason: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+pub trait Pausable {
+    fn toggle_pause(&mut self);
+    fn pause(&mut self, reason: Option<String>);
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>);
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>);
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>);
+    fn unpause(&mut self);
+    fn is_paused(&self) -> bool;
+    fn when_not_paused(&self);
+    fn pause_feature(&mut self, feature: &str);
+    fn unpause_feature(&mut self, feature: &str);
+    fn is_feature_paused(&self, feature: &str) -> bool;
+    fn when_not_paused_feature(&self, feature: &str);
+    fn when_paused(&self);
+    fn when_paused_feature(&self, feature: &str);
+}
+
+impl Pausable for StatusMessage {
+    fn toggle_pause(&mut self) {
+        if !self.is_paused() {
+            Pausable::pause(self, None)
+        } else {
+            Pausable::unpause(self)
+        }
+    }
+
+    fn pause(&mut self, reason: Option<String>) {
+        self.pause_with_expiry(reason, None)
+    }
+
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        let expires_at = near_sdk::env::block_timestamp()
+            .checked_add(duration_ns)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Pause duration overflow"));
+        Pausable::pause_until(self, expires_at, reason)
+    }
+
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        if timestamp <= near_sdk::env::block_timestamp() {
+            near_sdk::env::panic(b"Pause deadline must be in the future")
+        }
+        self.pause_with_expiry(reason, Some(timestamp))
+    }
+
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"The system is already paused")
+        }
+        let info = PauseInfo {
+            paused_by: near_sdk::env::predecessor_account_id(),
+            paused_at: near_sdk::env::block_timestamp(),
+            reason,
+            expires_at,
+        };
+        near_sdk::env::log(
+            format!(
+                "The system is paused by {} at {} until {}. Reason: {}",
+                info.paused_by,
+                info.paused_at,
+                info.expires_at
+                    .map_or("unpaused".to_string(), |t| t.to_string()),
+                info.reason.as_deref().unwrap_or("not specified")
+            )
+            .as_bytes(),
+        );
+        self.pause_status = true;
+        Self::pause_record().set(&info);
+    }
+
+    fn unpause(&mut self) {
+        if !self.pause_status {
+            near_sdk::env::panic(b"The system is not paused")
+        }
+        self.pause_status = false;
+        Self::pause_record().remove();
+        near_sdk::env::log(
+            format!(
+                "The system is unpaused by {} at {}",
+                near_sdk::env::predecessor_account_id(),
+                near_sdk::env::block_timestamp()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn is_paused(&self) -> bool {
+        if !self.pause_status {
+            return false;
+        }
+        match Self::pause_record().get().and_then(|info| info.expires_at) {
+            Some(expires_at) => near_sdk::env::block_timestamp() < expires_at,
+            None => true,
+        }
+    }
+
+    fn when_not_paused(&self) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"Function is paused")
+        }
+    }
+
+    fn pause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.insert(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is paused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn unpause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.remove(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is unpaused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn is_feature_paused(&self, feature: &str) -> bool {
+        self.is_paused() || Self::paused_feature_set().contains(&feature.to_string())
+    }
+
+    fn when_not_paused_feature(&self, feature: &str) {
+        self.when_not_paused();
+        if Self::paused_feature_set().contains(&feature.to_string()) {
+            near_sdk::env::panic(format!("Feature {} is paused", feature).as_bytes())
+        }
+    }
+
+    fn when_paused(&self) {
+        if !self.is_paused() {
+            near_sdk::env::panic(b"Function is not paused")
+        }
+    }
+
+    fn when_paused_feature(&self, feature: &str) {
+        if !self.is_feature_paused(feature) {
+            near_sdk::env::panic(format!("Feature {} is not paused", feature).as_bytes())
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause_info(&self) -> Option<PauseInfo> {
+        if self.is_paused() {
+            Self::pause_record().get()
+        } else {
+            None
+        }
+    }
+
+    pub fn paused_features(&self) -> Vec<String> {
+        Self::paused_feature_set().to_vec()
+    }
+}
+
+impl StatusMessage {
+    fn pause_record() -> near_sdk::collections::LazyOption<PauseInfo> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Info.into_bytes(), None)
+    }
+
+    fn paused_feature_set() -> near_sdk::collections::UnorderedSet<String> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .get()
+            .unwrap_or_else(|| {
+                near_sdk::collections::UnorderedSet::new(
+                    StorageKeyPausable::FeatureSet.into_bytes(),
+                )
+            })
+    }
+
+    fn save_paused_feature_set(features: &near_sdk::collections::UnorderedSet<String>) {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .set(features);
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause(&mut self, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause(self, reason)
+    }
+
+    pub fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_for(self, duration_ns, reason)
+    }
+
+    pub fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_until(self, timestamp, reason)
+    }
+
+    pub fn unpause(&mut self) {
+        self.assert_role(&"unpauser".to_string());
+        Pausable::unpause(self)
+    }
+
+    pub fn pause_feature(&mut self, feature: String) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_feature(self, &feature)
+    }
+
+    pub fn unpause_feature(&mut self, feature: String) {
+        self.assert_role(&"unpauser".to_string());
+        Pausable::unpause_feature(self, &feature)
+    }
+}
+
+pub enum StorageKeyFungibleToken {
+    Accounts,
+    Metadata,
+}
+
+impl StorageKeyFungibleToken {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyFungibleToken::Accounts => "fta".to_string(),
+            StorageKeyFungibleToken::Metadata => "ftm".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FungibleTokenMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+    pub decimals: u8,
+}
+
+pub trait FungibleToken {
+    fn ft_transfer(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+        memo: Option<String>,
+    );
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> near_sdk::PromiseOrValue<near_sdk::json_types::U128>;
+    fn ft_total_supply(&self) -> near_sdk::json_types::U128;
+    fn ft_balance_of(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: near_sdk::AccountId,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+    ) -> near_sdk::json_types::U128;
+    fn ft_metadata(&self) -> FungibleTokenMetadata;
+}
+
+#[near_bindgen]
+impl FungibleToken for StatusMessage {
+    #[payable]
+    fn ft_transfer(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+        memo: Option<String>,
+    ) {
+        Self::ft_assert_one_yocto();
+        self.when_not_paused();
+        let sender_id = near_sdk::env::predecessor_account_id();
+        self.ft_transfer_internal(&sender_id, &receiver_id, amount.0, memo)
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> near_sdk::PromiseOrValue<near_sdk::json_types::U128> {
+        Self::ft_assert_one_yocto();
+        self.when_not_paused();
+        let sender_id = near_sdk::env::predecessor_account_id();
+        self.ft_transfer_internal(&sender_id, &receiver_id, amount.0, memo);
+        let receiver_gas = near_sdk::env::prepaid_gas()
+            .checked_sub(Self::FT_GAS_FOR_TRANSFER_CALL)
+            .unwrap_or_else(|| {
+                near_sdk::env::panic(b"Prepaid gas is not enough for ft_transfer_call")
+            });
+        near_sdk::Promise::new(receiver_id.clone())
+            .function_call(
+                b"ft_on_transfer".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "sender_id" : sender_id, "amount" : amount, "msg" : msg,
+                })
+                .to_string()
+                .into_bytes(),
+                0,
+                receiver_gas,
+            )
+            .then(
+                near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                    b"ft_resolve_transfer".to_vec(),
+                    near_sdk::serde_json::json!
+                    ({
+                        "sender_id" : sender_id, "receiver_id" : receiver_id, "amount" :
+                        amount,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    0,
+                    Self::FT_GAS_FOR_RESOLVE_TRANSFER,
+                ),
+            )
+            .into()
+    }
+
+    fn ft_total_supply(&self) -> near_sdk::json_types::U128 {
+        self.ft_total_supply.into()
+    }
+
+    fn ft_balance_of(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+        self.ft_accounts.get(&account_id).unwrap_or(0).into()
+    }
+
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: near_sdk::AccountId,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+    ) -> near_sdk::json_types::U128 {
+        let amount = amount.0;
+        let unused = match near_sdk::env::promise_result(0) {
+            near_sdk::PromiseResult::Successful(value) => {
+                match near_sdk::serde_json::from_slice::<near_sdk::json_types::U128>(&value) {
+                    Ok(unused) => std::cmp::min(amount, unused.0),
+                    Err(_) => amount,
+                }
+            }
+            _ => amount,
+        };
+        if unused == 0 {
+            return amount.into();
+        }
+        let refund = std::cmp::min(unused, self.ft_accounts.get(&receiver_id).unwrap_or(0));
+        if refund == 0 {
+            return amount.into();
+        }
+        self.ft_withdraw(&receiver_id, refund);
+        self.ft_deposit(&sender_id, refund);
+        Self::ft_event(
+            "ft_transfer",
+            near_sdk::serde_json::json!
+            ({
+                "old_owner_id" : receiver_id, "new_owner_id" : sender_id, "amount"
+                : near_sdk::json_types::U128(refund), "memo" : "refund",
+            }),
+        );
+        (amount - refund).into()
+    }
+
+    fn ft_metadata(&self) -> FungibleTokenMetadata {
+        self.ft_metadata
+            .get()
+            .unwrap_or_else(|| near_sdk::env::panic(b"Token metadata is not set"))
+    }
+}
+
+impl StatusMessage {
+    pub const FT_GAS_FOR_RESOLVE_TRANSFER: near_sdk::Gas = 5_000_000_000_000;
+    pub const FT_GAS_FOR_TRANSFER_CALL: near_sdk::Gas =
+        25_000_000_000_000 + Self::FT_GAS_FOR_RESOLVE_TRANSFER;
+    pub fn setup_fungible_token(
+        &mut self,
+        owner_id: near_sdk::AccountId,
+        total_supply: near_sdk::json_types::U128,
+        metadata: FungibleTokenMetadata,
+    ) {
+        if metadata.spec != "ft-1.0.0" {
+            near_sdk::env::panic(b"Token metadata spec has to be ft-1.0.0")
+        }
+        if metadata.reference.is_some() != metadata.reference_hash.is_some() {
+            near_sdk::env::panic(
+                b"Token metadata reference and reference_hash have to be set together",
+            )
+        }
+        if let Some(reference_hash) = &metadata.reference_hash {
+            if reference_hash.0.len() != 32 {
+                near_sdk::env::panic(b"Token metadata reference_hash has to be 32 bytes")
+            }
+        }
+        self.ft_metadata.set(&metadata);
+        self.ft_register_account(&owner_id);
+        if total_supply.0 > 0 {
+            self.ft_mint(
+                &owner_id,
+                total_supply.0,
+                Some("Initial supply".to_string()),
+            );
+        }
+    }
+
+    pub fn ft_mint(
+        &mut self,
+        account_id: &near_sdk::AccountId,
+        amount: near_sdk::Balance,
+        memo: Option<String>,
+    ) {
+        if amount == 0 {
+            near_sdk::env::panic(b"The amount should be a positive number")
+        }
+        self.ft_total_supply = self
+            .ft_total_supply
+            .checked_add(amount)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Total supply overflow"));
+        self.ft_deposit(account_id, amount);
+        Self::ft_event(
+            "ft_mint",
+            near_sdk::serde_json::json!
+            ({
+                "owner_id" : account_id, "amount" : near_sdk::json_types::U128(amount), "memo" : memo,
+            }),
+        );
+    }
+
+    pub fn ft_burn(
+        &mut self,
+        account_id: &near_sdk::AccountId,
+        amount: near_sdk::Balance,
+        memo: Option<String>,
+    ) {
+        if amount == 0 {
+            near_sdk::env::panic(b"The amount should be a positive number")
+        }
+        self.ft_withdraw(account_id, amount);
+        self.ft_total_supply -= amount;
+        Self::ft_event(
+            "ft_burn",
+            near_sdk::serde_json::json!
+            ({
+                "owner_id" : account_id, "amount" : near_sdk::json_types::U128(amount), "memo" : memo,
+            }),
+        );
+    }
+
+    pub fn ft_register_account(&mut self, account_id: &near_sdk::AccountId) -> bool {
+        if self.ft_accounts.get(account_id).is_some() {
+            return false;
+        }
+        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+        }
+        self.ft_accounts.insert(account_id, &0);
+        near_sdk::env::log(format!("Account {} is registered", account_id).as_bytes());
+        true
+    }
+
+    fn ft_assert_one_yocto() {
+        if near_sdk::env::attached_deposit() != 1 {
+            near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+        }
+    }
+
+    fn ft_deposit(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance) {
+        if self.ft_accounts.get(account_id).is_none() {
+            near_sdk::env::panic(format!("Account {} is not registered", account_id).as_bytes())
+        }
+        let balance = self
+            .ft_accounts
+            .get(account_id)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Balance overflow"));
+        self.ft_accounts.insert(account_id, &balance);
+    }
+
+    fn ft_withdraw(&mut self, account_id: &near_sdk::AccountId, amount: near_sdk::Balance) {
+        let balance = self
+            .ft_accounts
+            .get(account_id)
+            .unwrap_or(0)
+            .checked_sub(amount)
+            .unwrap_or_else(|| {
+                near_sdk::env::panic(
+                    format!("Account {} does not have enough balance", account_id).as_bytes(),
+                )
+            });
+        self.ft_accounts.insert(account_id, &balance);
+    }
+
+    fn ft_transfer_internal(
+        &mut self,
+        sender_id: &near_sdk::AccountId,
+        receiver_id: &near_sdk::AccountId,
+        amount: near_sdk::Balance,
+        memo: Option<String>,
+    ) {
+        if sender_id == receiver_id {
+            near_sdk::env::panic(b"Sender and receiver should be different")
+        }
+        if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+        }
+        if amount == 0 {
+            near_sdk::env::panic(b"The amount should be a positive number")
+        }
+        self.ft_withdraw(sender_id, amount);
+        self.ft_deposit(receiver_id, amount);
+        Self::ft_event(
+            "ft_transfer",
+            near_sdk::serde_json::json!
+            ({
+                "old_owner_id" : sender_id, "new_owner_id" : receiver_id, "amount"
+                : near_sdk::json_types::U128(amount), "memo" : memo,
+            }),
+        );
+    }
+
+    fn ft_event(event: &str, mut data: near_sdk::serde_json::Value) {
+        if let Some(data) = data.as_object_mut() {
+            if data.get("memo").map_or(false, |memo| memo.is_null()) {
+                data.remove("memo");
+            }
+        }
+        near_sdk::env::log(
+            format!(
+                "EVENT_JSON:{}",
+                near_sdk::serde_json::json!
+                ({
+                    "standard" : "nep141", "version" : "1.0.0", "event" : event,
+                    "data" : [data],
+                })
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn ft_mint_tokens(
+        &mut self,
+        account_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+        memo: Option<String>,
+    ) {
+        self.assert_role(&"minter".to_string());
+        self.when_not_paused();
+        self.ft_mint(&account_id, amount.0, memo)
+    }
+
+    pub fn ft_burn_tokens(&mut self, amount: near_sdk::json_types::U128, memo: Option<String>) {
+        self.assert_role(&"minter".to_string());
+        self.when_not_paused();
+        self.ft_burn(&near_sdk::env::predecessor_account_id(), amount.0, memo)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, total_supply: U128) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            pause_status: false,
+            ft_accounts: near_sdk::collections::LookupMap::new(
+                StorageKeyFungibleToken::Accounts.into_bytes(),
+            ),
+            ft_total_supply: 0,
+            ft_metadata: near_sdk::collections::LazyOption::new(
+                StorageKeyFungibleToken::Metadata.into_bytes(),
+                None,
+            ),
+        };
+
+        for role in [DEFAULT_ADMIN, MINTER, PAUSER, UNPAUSER].iter() {
+            constructor.setup_account_role(&role.to_string(), &owner);
+        }
+
+        constructor.setup_fungible_token(
+            owner,
+            total_supply,
+            FungibleTokenMetadata {
+                spec: String::from("ft-1.0.0"),
+                name: String::from("Status Token"),
+                symbol: String::from("STATUS"),
+                icon: None,
+                reference: None,
+                reference_hash: None,
+                decimals: 24,
+            },
+        );
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Holders pay for their storage to be registered
+    #[payable]
+    pub fn register_account(&mut self) {
+        assert!(
+            env::attached_deposit() >= REGISTRATION_DEPOSIT,
+            "Requires attached deposit of {} yoctoNEAR",
+            REGISTRATION_DEPOSIT
+        );
+        assert!(
+            self.ft_register_account(&env::predecessor_account_id()),
+            "The account is already registered"
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    fn balance(contract: &StatusMessage, account_id: &str) -> u128 {
+        contract.ft_balance_of(String::from(account_id)).0
+    }
+
+    // bob holds the supply, carol and dex are registered
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"), U128(1000));
+
+        for account_id in ["carol.testnet", "dex.testnet"].iter() {
+            call(account_id, REGISTRATION_DEPOSIT);
+            contract.register_account();
+        }
+
+        contract
+    }
+
+    #[test]
+    fn should_set_up_token() {
+        let contract = setup();
+
+        assert_eq!(contract.ft_metadata().symbol, "STATUS");
+        assert_eq!(contract.ft_total_supply(), U128(1000));
+        assert_eq!(balance(&contract, "bob.testnet"), 1000);
+        assert_eq!(balance(&contract, "carol.testnet"), 0);
+    }
+
+    #[test]
+    fn should_transfer() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+
+        assert_eq!(balance(&contract, "bob.testnet"), 700);
+        assert_eq!(balance(&contract, "carol.testnet"), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account dave.testnet is not registered")]
+    fn should_panic_on_transfer_to_unregistered_account() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("dave.testnet"), U128(1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The account is already registered")]
+    fn should_panic_on_second_registration() {
+        let mut contract = setup();
+
+        call("carol.testnet", REGISTRATION_DEPOSIT);
+        contract.register_account();
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn should_panic_without_one_yocto() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet does not have enough balance")]
+    fn should_panic_on_insufficient_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(1001), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Function is paused")]
+    fn should_panic_when_paused() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.pause(None);
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+    }
+
+    #[test]
+    fn should_mint_and_burn() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.ft_mint_tokens(String::from("carol.testnet"), U128(500), None);
+
+        assert_eq!(contract.ft_total_supply(), U128(1500));
+        assert_eq!(balance(&contract, "carol.testnet"), 500);
+
+        contract.ft_burn_tokens(U128(400), Some(String::from("buyback")));
+
+        assert_eq!(contract.ft_total_supply(), U128(1100));
+        assert_eq!(balance(&contract, "bob.testnet"), 600);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: minter role")]
+    fn should_panic_on_mint_without_role() {
+        let mut contract = setup();
+
+        call("carol.testnet", 0);
+        contract.ft_mint_tokens(String::from("carol.testnet"), U128(500), None);
+    }
+
+    #[test]
+    fn should_refund_unused_tokens() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer_call(String::from("dex.testnet"), U128(300), None, String::new());
+
+        assert_eq!(balance(&contract, "dex.testnet"), 300);
+
+        // The receiver returns 100 unused tokens
+        resolve(PromiseResult::Successful(b"\"100\"".to_vec()));
+        let used = contract.ft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("dex.testnet"),
+            U128(300),
+        );
+
+        assert_eq!(used, U128(200));
+        assert_eq!(balance(&contract, "bob.testnet"), 800);
+        assert_eq!(balance(&contract, "dex.testnet"), 200);
+    }
+
+    #[test]
+    fn should_refund_failed_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer_call(String::from("dex.testnet"), U128(300), None, String::new());
+
+        // The receiver has already spent a part of the tokens
+        call("dex.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(100), None);
+
+        resolve(PromiseResult::Failed);
+        let used = contract.ft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("dex.testnet"),
+            U128(300),
+        );
+
+        assert_eq!(used, U128(100));
+        assert_eq!(balance(&contract, "bob.testnet"), 900);
+        assert_eq!(balance(&contract, "dex.testnet"), 0);
+        assert_eq!(contract.ft_total_supply(), U128(1000));
+    }
+}
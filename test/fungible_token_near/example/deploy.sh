@@ -0,0 +1,21 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "total_supply": "1000000"}' --accountId $ACCOUNT
+
+near view $ACCOUNT ft_metadata
+
+near call $ACCOUNT register_account '{}' --accountId app.timurguvenkaya.testnet --deposit 0.00125
+
+near call $ACCOUNT ft_transfer '{"receiver_id": "app.timurguvenkaya.testnet", "amount": "1000"}' --accountId timurguvenkaya.testnet --depositYocto 1
+
+near view $ACCOUNT ft_balance_of '{"account_id": "app.timurguvenkaya.testnet"}'
+
+near call $ACCOUNT ft_mint_tokens '{"account_id": "app.timurguvenkaya.testnet", "amount": "500"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT pause '{}' --accountId timurguvenkaya.testnet
+
+# Fails while the contract is paused
+near call $ACCOUNT ft_transfer '{"receiver_id": "timurguvenkaya.testnet", "amount": "1000"}' --accountId app.timurguvenkaya.testnet --depositYocto 1
@@ -0,0 +1,279 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Balance, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const MINTER: &str = "minter";
+const PAUSER: &str = "pauser";
+const UNPAUSER: &str = "unpauser";
+
+// Covers the storage of a new balance
+const REGISTRATION_DEPOSIT: Balance = 1_250_000_000_000_000_000_000;
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Pausable, FungibleToken)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, total_supply: U128) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "access",
+            "pausable",
+            "fungible_token",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        for role in [DEFAULT_ADMIN, MINTER, PAUSER, UNPAUSER].iter() {
+            constructor.setup_account_role(&role.to_string(), &owner);
+        }
+
+        constructor.setup_fungible_token(
+            owner,
+            total_supply,
+            FungibleTokenMetadata {
+                spec: String::from("ft-1.0.0"),
+                name: String::from("Status Token"),
+                symbol: String::from("STATUS"),
+                icon: None,
+                reference: None,
+                reference_hash: None,
+                decimals: 24,
+            },
+        );
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Holders pay for their storage to be registered
+    #[payable]
+    pub fn register_account(&mut self) {
+        assert!(
+            env::attached_deposit() >= REGISTRATION_DEPOSIT,
+            "Requires attached deposit of {} yoctoNEAR",
+            REGISTRATION_DEPOSIT
+        );
+        assert!(
+            self.ft_register_account(&env::predecessor_account_id()),
+            "The account is already registered"
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    fn balance(contract: &StatusMessage, account_id: &str) -> u128 {
+        contract.ft_balance_of(String::from(account_id)).0
+    }
+
+    // bob holds the supply, carol and dex are registered
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"), U128(1000));
+
+        for account_id in ["carol.testnet", "dex.testnet"].iter() {
+            call(account_id, REGISTRATION_DEPOSIT);
+            contract.register_account();
+        }
+
+        contract
+    }
+
+    #[test]
+    fn should_set_up_token() {
+        let contract = setup();
+
+        assert_eq!(contract.ft_metadata().symbol, "STATUS");
+        assert_eq!(contract.ft_total_supply(), U128(1000));
+        assert_eq!(balance(&contract, "bob.testnet"), 1000);
+        assert_eq!(balance(&contract, "carol.testnet"), 0);
+    }
+
+    #[test]
+    fn should_transfer() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+
+        assert_eq!(balance(&contract, "bob.testnet"), 700);
+        assert_eq!(balance(&contract, "carol.testnet"), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account dave.testnet is not registered")]
+    fn should_panic_on_transfer_to_unregistered_account() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("dave.testnet"), U128(1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The account is already registered")]
+    fn should_panic_on_second_registration() {
+        let mut contract = setup();
+
+        call("carol.testnet", REGISTRATION_DEPOSIT);
+        contract.register_account();
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn should_panic_without_one_yocto() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet does not have enough balance")]
+    fn should_panic_on_insufficient_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(1001), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Function is paused")]
+    fn should_panic_when_paused() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.pause(None);
+
+        call("bob.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(300), None);
+    }
+
+    #[test]
+    fn should_mint_and_burn() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.ft_mint_tokens(String::from("carol.testnet"), U128(500), None);
+
+        assert_eq!(contract.ft_total_supply(), U128(1500));
+        assert_eq!(balance(&contract, "carol.testnet"), 500);
+
+        contract.ft_burn_tokens(U128(400), Some(String::from("buyback")));
+
+        assert_eq!(contract.ft_total_supply(), U128(1100));
+        assert_eq!(balance(&contract, "bob.testnet"), 600);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: minter role")]
+    fn should_panic_on_mint_without_role() {
+        let mut contract = setup();
+
+        call("carol.testnet", 0);
+        contract.ft_mint_tokens(String::from("carol.testnet"), U128(500), None);
+    }
+
+    #[test]
+    fn should_refund_unused_tokens() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer_call(String::from("dex.testnet"), U128(300), None, String::new());
+
+        assert_eq!(balance(&contract, "dex.testnet"), 300);
+
+        // The receiver returns 100 unused tokens
+        resolve(PromiseResult::Successful(b"\"100\"".to_vec()));
+        let used = contract.ft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("dex.testnet"),
+            U128(300),
+        );
+
+        assert_eq!(used, U128(200));
+        assert_eq!(balance(&contract, "bob.testnet"), 800);
+        assert_eq!(balance(&contract, "dex.testnet"), 200);
+    }
+
+    #[test]
+    fn should_refund_failed_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.ft_transfer_call(String::from("dex.testnet"), U128(300), None, String::new());
+
+        // The receiver has already spent a part of the tokens
+        call("dex.testnet", 1);
+        contract.ft_transfer(String::from("carol.testnet"), U128(100), None);
+
+        resolve(PromiseResult::Failed);
+        let used = contract.ft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("dex.testnet"),
+            U128(300),
+        );
+
+        assert_eq!(used, U128(100));
+        assert_eq!(balance(&contract, "bob.testnet"), 900);
+        assert_eq!(balance(&contract, "dex.testnet"), 0);
+        assert_eq!(contract.ft_total_supply(), U128(1000));
+    }
+}
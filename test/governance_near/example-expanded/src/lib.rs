@@ -0,0 +1,694 @@
+use near_sdk::borsh;
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Balance, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    checkpoints: LookupMap<AccountId, Vec<(u64, Balance)>>,
+    governance_proposals: near_sdk::collections::UnorderedMap<u64, GovernanceProposal>,
+    governance_votes: near_sdk::collections::LookupMap<(u64, near_sdk::AccountId), GovernanceVote>,
+    governance_next_id: u64,
+    governance_settings: GovernanceSettings,
+}
+
+pub enum StorageKeyGovernance {
+    Proposals,
+    Votes,
+}
+
+impl StorageKeyGovernance {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyGovernance::Proposals => "gvp".to_string(),
+            StorageKeyGovernance::Votes => "gvv".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum GovernanceAction {
+    Text,
+    FunctionCall {
+        receiver_id: near_sdk::AccountId,
+        method_name: String,
+        args: String,
+        deposit: near_sdk::json_types::U128,
+        gas: u64,
+    },
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GovernanceProposal {
+    pub proposer: near_sdk::AccountId,
+    pub description: String,
+    pub action: GovernanceAction,
+    pub created_at: u64,
+    pub voting_ends_at: u64,
+    pub votes_for: near_sdk::json_types::U128,
+    pub votes_against: near_sdk::json_types::U128,
+    pub votes_abstain: near_sdk::json_types::U128,
+    pub executed: bool,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum GovernanceVote {
+    For,
+    Against,
+    Abstain,
+}
+
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum GovernanceStatus {
+    Active,
+    Defeated,
+    Succeeded,
+    Executed,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GovernanceSettings {
+    pub quorum: near_sdk::json_types::U128,
+    pub threshold_bps: u16,
+    pub voting_period_secs: u64,
+}
+
+pub trait GovernanceVotingPower {
+    fn voting_power(&self, account_id: &near_sdk::AccountId, created_at: u64) -> near_sdk::Balance;
+}
+
+pub trait Governance {
+    fn create_proposal(&mut self, description: String, action: GovernanceAction) -> u64;
+    fn vote(&mut self, id: u64, vote: GovernanceVote);
+    fn execute_proposal(&mut self, id: u64) -> near_sdk::PromiseOrValue<bool>;
+    fn governance_on_execute(&mut self, id: u64) -> bool;
+    fn set_governance_settings(&mut self, settings: GovernanceSettings);
+    fn proposal(&self, id: u64) -> Option<GovernanceProposal>;
+    fn proposals(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, GovernanceProposal)>;
+    fn proposal_status(&self, id: u64) -> GovernanceStatus;
+    fn governance_vote(&self, id: u64, account_id: near_sdk::AccountId) -> Option<GovernanceVote>;
+    fn governance_settings(&self) -> GovernanceSettings;
+}
+
+#[near_bindgen]
+impl Governance for StatusMessage {
+    fn create_proposal(&mut self, description: String, action: GovernanceAction) -> u64 {
+        let proposer = near_sdk::env::predecessor_account_id();
+        let now = near_sdk::env::block_timestamp();
+        if self.voting_power(&proposer, now) == 0 {
+            near_sdk::env::panic(format!("Account {} has no voting power", proposer).as_bytes())
+        }
+        if let GovernanceAction::FunctionCall { receiver_id, .. } = &action {
+            if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+                near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+            }
+        }
+        let id = self.governance_next_id;
+        let proposal = GovernanceProposal {
+            proposer: proposer.clone(),
+            description,
+            action,
+            created_at: now,
+            voting_ends_at: self
+                .governance_settings
+                .voting_period_secs
+                .checked_mul(1_000_000_000)
+                .and_then(|period| now.checked_add(period))
+                .unwrap_or_else(|| {
+                    near_sdk::env::panic(b"Voting period overflows the block timestamp")
+                }),
+            votes_for: 0.into(),
+            votes_against: 0.into(),
+            votes_abstain: 0.into(),
+            executed: false,
+        };
+        self.governance_proposals.insert(&id, &proposal);
+        self.governance_next_id += 1;
+        near_sdk::env::log(format!("Proposal {} is created by {}", id, proposer).as_bytes());
+        id
+    }
+
+    fn vote(&mut self, id: u64, vote: GovernanceVote) {
+        let voter = near_sdk::env::predecessor_account_id();
+        let mut proposal = self.governance_proposal(id);
+        if near_sdk::env::block_timestamp() >= proposal.voting_ends_at {
+            near_sdk::env::panic(format!("Voting on proposal {} has ended", id).as_bytes())
+        }
+        let power = self.voting_power(&voter, proposal.created_at);
+        if power == 0 {
+            near_sdk::env::panic(format!("Account {} has no voting power", voter).as_bytes())
+        }
+        let key = (id, voter.clone());
+        if self.governance_votes.insert(&key, &vote).is_some() {
+            near_sdk::env::panic(
+                format!("Account {} has already voted on proposal {}", voter, id).as_bytes(),
+            )
+        }
+        let votes = match vote {
+            GovernanceVote::For => &mut proposal.votes_for,
+            GovernanceVote::Against => &mut proposal.votes_against,
+            GovernanceVote::Abstain => &mut proposal.votes_abstain,
+        };
+        votes.0 = votes
+            .0
+            .checked_add(power)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Votes overflow"));
+        self.governance_proposals.insert(&id, &proposal);
+        near_sdk::env::log(
+            format!(
+                "Account {} votes {:?} on proposal {} with {}",
+                voter, vote, id, power
+            )
+            .as_bytes(),
+        );
+    }
+
+    fn execute_proposal(&mut self, id: u64) -> near_sdk::PromiseOrValue<bool> {
+        let mut proposal = self.governance_proposal(id);
+        let status = self.governance_status(&proposal);
+        if status != GovernanceStatus::Succeeded {
+            near_sdk::env::panic(format!("Proposal {} is {:?}", id, status).as_bytes())
+        }
+        proposal.executed = true;
+        self.governance_proposals.insert(&id, &proposal);
+        near_sdk::env::log(format!("Proposal {} is executed", id).as_bytes());
+        match proposal.action {
+            GovernanceAction::Text => near_sdk::PromiseOrValue::Value(true),
+            GovernanceAction::FunctionCall {
+                receiver_id,
+                method_name,
+                args,
+                deposit,
+                gas,
+            } => near_sdk::Promise::new(receiver_id)
+                .function_call(method_name.into_bytes(), args.into_bytes(), deposit.0, gas)
+                .then(
+                    near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                        b"governance_on_execute".to_vec(),
+                        near_sdk::serde_json::json!
+                ({ "id" : id })
+                        .to_string()
+                        .into_bytes(),
+                        0,
+                        Self::GOVERNANCE_GAS_FOR_EXECUTE_CALLBACK,
+                    ),
+                )
+                .into(),
+        }
+    }
+
+    #[private]
+    fn governance_on_execute(&mut self, id: u64) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            return true;
+        }
+        let mut proposal = self.governance_proposal(id);
+        proposal.executed = false;
+        self.governance_proposals.insert(&id, &proposal);
+        near_sdk::env::log(format!("Execution of proposal {} failed", id).as_bytes());
+        false
+    }
+
+    #[private]
+    fn set_governance_settings(&mut self, settings: GovernanceSettings) {
+        Self::governance_assert_settings(&settings);
+        self.governance_settings = settings;
+    }
+
+    fn proposal(&self, id: u64) -> Option<GovernanceProposal> {
+        self.governance_proposals.get(&id)
+    }
+
+    fn proposals(&self, from_index: u64, limit: u64) -> std::vec::Vec<(u64, GovernanceProposal)> {
+        self.governance_proposals
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    fn proposal_status(&self, id: u64) -> GovernanceStatus {
+        self.governance_status(&self.governance_proposal(id))
+    }
+
+    fn governance_vote(&self, id: u64, account_id: near_sdk::AccountId) -> Option<GovernanceVote> {
+        self.governance_votes.get(&(id, account_id))
+    }
+
+    fn governance_settings(&self) -> GovernanceSettings {
+        self.governance_settings.clone()
+    }
+}
+
+impl StatusMessage {
+    pub const GOVERNANCE_GAS_FOR_EXECUTE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub const GOVERNANCE_MAX_VOTING_PERIOD_SECS: u64 = 365 * 24 * 60 * 60;
+    pub fn setup_governance(&mut self, settings: GovernanceSettings) {
+        Self::governance_assert_settings(&settings);
+        self.governance_settings = settings;
+    }
+
+    fn governance_assert_settings(settings: &GovernanceSettings) {
+        if settings.quorum.0 == 0 {
+            near_sdk::env::panic(b"Quorum must be positive")
+        }
+        if settings.threshold_bps >= 10_000 {
+            near_sdk::env::panic(b"Threshold must be below 10000 basis points")
+        }
+        if settings.voting_period_secs == 0 {
+            near_sdk::env::panic(b"Voting period must be positive")
+        }
+        if settings.voting_period_secs > Self::GOVERNANCE_MAX_VOTING_PERIOD_SECS {
+            near_sdk::env::panic(b"Voting period must be at most 365 days")
+        }
+    }
+
+    fn governance_proposal(&self, id: u64) -> GovernanceProposal {
+        self.governance_proposals.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Proposal {} does not exist", id).as_bytes())
+        })
+    }
+
+    fn governance_status(&self, proposal: &GovernanceProposal) -> GovernanceStatus {
+        if proposal.executed {
+            return GovernanceStatus::Executed;
+        }
+        if near_sdk::env::block_timestamp() < proposal.voting_ends_at {
+            return GovernanceStatus::Active;
+        }
+        let settings = &self.governance_settings;
+        let decisive = proposal
+            .votes_for
+            .0
+            .saturating_add(proposal.votes_against.0);
+        let cast = decisive.saturating_add(proposal.votes_abstain.0);
+        let threshold = settings.threshold_bps as near_sdk::Balance;
+        let required = decisive / 10_000 * threshold + decisive % 10_000 * threshold / 10_000;
+        if cast >= settings.quorum.0 && proposal.votes_for.0 > required {
+            GovernanceStatus::Succeeded
+        } else {
+            GovernanceStatus::Defeated
+        }
+    }
+}
+
+// Voting power is the balance held before the proposal was created
+impl GovernanceVotingPower for StatusMessage {
+    fn voting_power(&self, account_id: &AccountId, created_at: u64) -> Balance {
+        self.checkpoints
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .find(|(at, _)| *at < created_at)
+            .map_or(0, |(_, balance)| *balance)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(holders: Vec<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            checkpoints: LookupMap::new(b"c".to_vec()),
+            governance_proposals: near_sdk::collections::UnorderedMap::new(
+                StorageKeyGovernance::Proposals.into_bytes(),
+            ),
+            governance_votes: near_sdk::collections::LookupMap::new(
+                StorageKeyGovernance::Votes.into_bytes(),
+            ),
+            governance_next_id: 0,
+            governance_settings: GovernanceSettings {
+                quorum: near_sdk::json_types::U128(1),
+                threshold_bps: 5_000,
+                voting_period_secs: 604_800,
+            },
+        };
+
+        // Every holder gets one token
+        for holder in holders.iter() {
+            constructor.set_balance(holder, 1);
+        }
+
+        // Two votes within a minute, and more `For` than `Against` votes
+        constructor.setup_governance(GovernanceSettings {
+            quorum: U128(2),
+            threshold_bps: 5_000,
+            voting_period_secs: 60,
+        });
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn balance_of(&self, account_id: AccountId) -> U128 {
+        self.voting_power(&account_id, u64::MAX).into()
+    }
+
+    pub fn transfer(&mut self, receiver_id: AccountId, amount: U128) {
+        let sender_id = env::predecessor_account_id();
+
+        let sender_balance = self
+            .balance_of(sender_id.clone())
+            .0
+            .checked_sub(amount.0)
+            .unwrap_or_else(|| env::panic(b"Not enough tokens"));
+        let receiver_balance = self.balance_of(receiver_id.clone()).0 + amount.0;
+
+        self.set_balance(&sender_id, sender_balance);
+        self.set_balance(&receiver_id, receiver_balance);
+    }
+
+    // Only proposals can change the data
+    #[private]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+}
+
+impl StatusMessage {
+    fn set_balance(&mut self, account_id: &AccountId, balance: Balance) {
+        let now = env::block_timestamp();
+        let mut checkpoints = self.checkpoints.get(account_id).unwrap_or_default();
+
+        match checkpoints.last_mut() {
+            Some((at, last)) if *at == now => *last = balance,
+            _ => checkpoints.push((now, balance)),
+        }
+
+        self.checkpoints.insert(account_id, &checkpoints);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseOrValue, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn set_data_action() -> GovernanceAction {
+        GovernanceAction::FunctionCall {
+            receiver_id: String::from("alice.testnet"),
+            method_name: String::from("set_data"),
+            args: String::from(r#"{"data": "NEW DATA"}"#),
+            deposit: U128(0),
+            gas: 10_000_000_000_000,
+        }
+    }
+
+    // bob, carol and dave hold a token each. The proposal is created a second later, so the tokens can vote
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(vec![
+            String::from("bob.testnet"),
+            String::from("carol.testnet"),
+            String::from("dave.testnet"),
+        ]);
+
+        at("bob.testnet", 1);
+        contract.create_proposal(String::from("Change the data"), set_data_action());
+
+        contract
+    }
+
+    fn vote(contract: &mut StatusMessage, voter: &str, vote: GovernanceVote) {
+        at(voter, 10);
+        contract.vote(0, vote);
+    }
+
+    #[test]
+    fn should_execute_succeeded_proposal() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+        vote(&mut contract, "dave.testnet", GovernanceVote::Against);
+
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Active);
+
+        at("dave.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+
+        contract.execute_proposal(0);
+
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Executed);
+        assert_eq!(contract.proposal(0).unwrap().votes_for, U128(2));
+    }
+
+    #[test]
+    fn should_defeat_without_majority() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::Against);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Defeated);
+    }
+
+    #[test]
+    fn should_defeat_without_quorum() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Defeated);
+    }
+
+    #[test]
+    fn abstentions_count_for_quorum() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::Abstain);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+    }
+
+    #[test]
+    fn should_execute_text_proposal() {
+        let mut contract = setup();
+
+        at("carol.testnet", 1);
+        let id = contract.create_proposal(String::from("Hello"), GovernanceAction::Text);
+
+        at("bob.testnet", 10);
+        contract.vote(id, GovernanceVote::For);
+        at("carol.testnet", 10);
+        contract.vote(id, GovernanceVote::For);
+
+        at("carol.testnet", 61);
+        assert!(matches!(
+            contract.execute_proposal(id),
+            PromiseOrValue::Value(true)
+        ));
+    }
+
+    #[test]
+    fn should_revert_failed_execution() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 61);
+        contract.execute_proposal(0);
+
+        let mut context = get_context(String::from("alice.testnet"), 0);
+        context.block_timestamp = 61 * SECOND;
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.governance_on_execute(0));
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet has already voted on proposal 0"#)]
+    fn should_not_vote_twice() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet has no voting power"#)]
+    fn only_voters_can_vote() {
+        let mut contract = setup();
+
+        vote(&mut contract, "mallory.testnet", GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Voting on proposal 0 has ended"#)]
+    fn should_not_vote_after_period() {
+        let mut contract = setup();
+
+        at("bob.testnet", 61);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Proposal 0 is Active"#)]
+    fn should_not_execute_active_proposal() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+
+        contract.execute_proposal(0);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account eve.testnet has no voting power"#)]
+    fn moved_tokens_cannot_vote_twice() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 20);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        at("eve.testnet", 30);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account eve.testnet has no voting power"#)]
+    fn tokens_moved_in_the_creation_block_cannot_vote_twice() {
+        let mut contract = setup();
+
+        at("bob.testnet", 1);
+        contract.vote(0, GovernanceVote::For);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        at("eve.testnet", 10);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    fn moved_tokens_vote_on_later_proposals() {
+        let mut contract = setup();
+
+        at("bob.testnet", 20);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        assert_eq!(contract.balance_of(String::from("bob.testnet")), U128(0));
+        assert_eq!(contract.balance_of(String::from("eve.testnet")), U128(1));
+
+        at("eve.testnet", 30);
+        let id = contract.create_proposal(String::from("Hello"), GovernanceAction::Text);
+
+        // The first proposal still counts the token bob held when it was created
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("eve.testnet", 40);
+        contract.vote(id, GovernanceVote::For);
+
+        assert_eq!(contract.proposal(0).unwrap().votes_for, U128(1));
+        assert_eq!(contract.proposal(id).unwrap().votes_for, U128(1));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Voting period must be at most 365 days"#)]
+    fn should_cap_voting_period() {
+        let mut contract = setup();
+
+        at("alice.testnet", 10);
+        contract.set_governance_settings(GovernanceSettings {
+            quorum: U128(2),
+            threshold_bps: 5_000,
+            voting_period_secs: u64::MAX / 1_000_000_000,
+        });
+    }
+}
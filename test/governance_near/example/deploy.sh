@@ -0,0 +1,19 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"holders": ["timurguvenkaya.testnet", "app.timurguvenkaya.testnet"]}' --accountId $ACCOUNT
+
+near call $ACCOUNT create_proposal "{\"description\": \"Change the data\", \"action\": {\"FunctionCall\": {\"receiver_id\": \"$ACCOUNT\", \"method_name\": \"set_data\", \"args\": \"{\\\"data\\\": \\\"NEW DATA\\\"}\", \"deposit\": \"0\", \"gas\": 10000000000000}}}" --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT vote '{"id": 0, "vote": "For"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT vote '{"id": 0, "vote": "For"}' --accountId app.timurguvenkaya.testnet
+
+# Voting lasts a minute
+sleep 70
+
+near call $ACCOUNT execute_proposal '{"id": 0}' --accountId timurguvenkaya.testnet --gas 50000000000000
+
+near view $ACCOUNT get_data '{}'
@@ -0,0 +1,363 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Balance, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Governance(token_weighted = true))]
+pub struct StatusMessage {
+    data: String,
+    // Balance of every account after each block it changed in
+    checkpoints: LookupMap<AccountId, Vec<(u64, Balance)>>,
+}
+
+// Voting power is the balance held before the proposal was created
+impl GovernanceVotingPower for StatusMessage {
+    fn voting_power(&self, account_id: &AccountId, created_at: u64) -> Balance {
+        self.checkpoints
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .find(|(at, _)| *at < created_at)
+            .map_or(0, |(_, balance)| *balance)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(holders: Vec<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "governance",
+            Self {
+                data: String::from("SOME DATA"),
+                checkpoints: LookupMap::new(b"c".to_vec())
+            }
+        ]);
+
+        // Every holder gets one token
+        for holder in holders.iter() {
+            constructor.set_balance(holder, 1);
+        }
+
+        // Two votes within a minute, and more `For` than `Against` votes
+        constructor.setup_governance(GovernanceSettings {
+            quorum: U128(2),
+            threshold_bps: 5_000,
+            voting_period_secs: 60,
+        });
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn balance_of(&self, account_id: AccountId) -> U128 {
+        self.voting_power(&account_id, u64::MAX).into()
+    }
+
+    pub fn transfer(&mut self, receiver_id: AccountId, amount: U128) {
+        let sender_id = env::predecessor_account_id();
+
+        let sender_balance = self
+            .balance_of(sender_id.clone())
+            .0
+            .checked_sub(amount.0)
+            .unwrap_or_else(|| env::panic(b"Not enough tokens"));
+        let receiver_balance = self.balance_of(receiver_id.clone()).0 + amount.0;
+
+        self.set_balance(&sender_id, sender_balance);
+        self.set_balance(&receiver_id, receiver_balance);
+    }
+
+    // Only proposals can change the data
+    #[private]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+}
+
+impl StatusMessage {
+    fn set_balance(&mut self, account_id: &AccountId, balance: Balance) {
+        let now = env::block_timestamp();
+        let mut checkpoints = self.checkpoints.get(account_id).unwrap_or_default();
+
+        match checkpoints.last_mut() {
+            Some((at, last)) if *at == now => *last = balance,
+            _ => checkpoints.push((now, balance)),
+        }
+
+        self.checkpoints.insert(account_id, &checkpoints);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseOrValue, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn set_data_action() -> GovernanceAction {
+        GovernanceAction::FunctionCall {
+            receiver_id: String::from("alice.testnet"),
+            method_name: String::from("set_data"),
+            args: String::from(r#"{"data": "NEW DATA"}"#),
+            deposit: U128(0),
+            gas: 10_000_000_000_000,
+        }
+    }
+
+    // bob, carol and dave hold a token each. The proposal is created a second later, so the tokens can vote
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(vec![
+            String::from("bob.testnet"),
+            String::from("carol.testnet"),
+            String::from("dave.testnet"),
+        ]);
+
+        at("bob.testnet", 1);
+        contract.create_proposal(String::from("Change the data"), set_data_action());
+
+        contract
+    }
+
+    fn vote(contract: &mut StatusMessage, voter: &str, vote: GovernanceVote) {
+        at(voter, 10);
+        contract.vote(0, vote);
+    }
+
+    #[test]
+    fn should_execute_succeeded_proposal() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+        vote(&mut contract, "dave.testnet", GovernanceVote::Against);
+
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Active);
+
+        at("dave.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+
+        contract.execute_proposal(0);
+
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Executed);
+        assert_eq!(contract.proposal(0).unwrap().votes_for, U128(2));
+    }
+
+    #[test]
+    fn should_defeat_without_majority() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::Against);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Defeated);
+    }
+
+    #[test]
+    fn should_defeat_without_quorum() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Defeated);
+    }
+
+    #[test]
+    fn abstentions_count_for_quorum() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::Abstain);
+
+        at("bob.testnet", 61);
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+    }
+
+    #[test]
+    fn should_execute_text_proposal() {
+        let mut contract = setup();
+
+        at("carol.testnet", 1);
+        let id = contract.create_proposal(String::from("Hello"), GovernanceAction::Text);
+
+        at("bob.testnet", 10);
+        contract.vote(id, GovernanceVote::For);
+        at("carol.testnet", 10);
+        contract.vote(id, GovernanceVote::For);
+
+        at("carol.testnet", 61);
+        assert!(matches!(
+            contract.execute_proposal(id),
+            PromiseOrValue::Value(true)
+        ));
+    }
+
+    #[test]
+    fn should_revert_failed_execution() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 61);
+        contract.execute_proposal(0);
+
+        let mut context = get_context(String::from("alice.testnet"), 0);
+        context.block_timestamp = 61 * SECOND;
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.governance_on_execute(0));
+        assert_eq!(contract.proposal_status(0), GovernanceStatus::Succeeded);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet has already voted on proposal 0"#)]
+    fn should_not_vote_twice() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet has no voting power"#)]
+    fn only_voters_can_vote() {
+        let mut contract = setup();
+
+        vote(&mut contract, "mallory.testnet", GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Voting on proposal 0 has ended"#)]
+    fn should_not_vote_after_period() {
+        let mut contract = setup();
+
+        at("bob.testnet", 61);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Proposal 0 is Active"#)]
+    fn should_not_execute_active_proposal() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+        vote(&mut contract, "carol.testnet", GovernanceVote::For);
+
+        contract.execute_proposal(0);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account eve.testnet has no voting power"#)]
+    fn moved_tokens_cannot_vote_twice() {
+        let mut contract = setup();
+
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("bob.testnet", 20);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        at("eve.testnet", 30);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account eve.testnet has no voting power"#)]
+    fn tokens_moved_in_the_creation_block_cannot_vote_twice() {
+        let mut contract = setup();
+
+        at("bob.testnet", 1);
+        contract.vote(0, GovernanceVote::For);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        at("eve.testnet", 10);
+        contract.vote(0, GovernanceVote::For);
+    }
+
+    #[test]
+    fn moved_tokens_vote_on_later_proposals() {
+        let mut contract = setup();
+
+        at("bob.testnet", 20);
+        contract.transfer(String::from("eve.testnet"), U128(1));
+
+        assert_eq!(contract.balance_of(String::from("bob.testnet")), U128(0));
+        assert_eq!(contract.balance_of(String::from("eve.testnet")), U128(1));
+
+        at("eve.testnet", 30);
+        let id = contract.create_proposal(String::from("Hello"), GovernanceAction::Text);
+
+        // The first proposal still counts the token bob held when it was created
+        vote(&mut contract, "bob.testnet", GovernanceVote::For);
+
+        at("eve.testnet", 40);
+        contract.vote(id, GovernanceVote::For);
+
+        assert_eq!(contract.proposal(0).unwrap().votes_for, U128(1));
+        assert_eq!(contract.proposal(id).unwrap().votes_for, U128(1));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Voting period must be at most 365 days"#)]
+    fn should_cap_voting_period() {
+        let mut contract = setup();
+
+        at("alice.testnet", 10);
+        contract.set_governance_settings(GovernanceSettings {
+            quorum: U128(2),
+            threshold_bps: 5_000,
+            voting_period_secs: u64::MAX / 1_000_000_000,
+        });
+    }
+}
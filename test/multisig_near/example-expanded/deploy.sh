@@ -0,0 +1,18 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"members": ["timurguvenkaya.testnet", "app.timurguvenkaya.testnet"], "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+# The first confirmation only creates the request
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT multisig_requests '{"from_index": 0, "limit": 10}'
+
+near view $ACCOUNT get_data
+
+# The second confirmation runs the method
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId app.timurguvenkaya.testnet
+
+near view $ACCOUNT get_data
@@ -0,0 +1,550 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    multisig_members: near_sdk::collections::UnorderedSet<near_sdk::AccountId>,
+    multisig_threshold: u64,
+    multisig_requests: near_sdk::collections::UnorderedMap<String, MultisigRequest>,
+}
+
+pub enum StorageKeyMultisig {
+    Members,
+    Requests,
+}
+
+impl StorageKeyMultisig {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyMultisig::Members => "msm".to_string(),
+            StorageKeyMultisig::Requests => "msr".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MultisigRequest {
+    pub method: String,
+    pub args: String,
+    pub created_by: near_sdk::AccountId,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub confirmations: std::vec::Vec<near_sdk::AccountId>,
+}
+
+pub trait Multisig {
+    fn create_request(&mut self, method: String, args: String) -> String;
+    fn confirm(&mut self, id: String);
+    fn revoke_confirmation(&mut self, id: String);
+    fn multisig_request(&self, id: String) -> Option<MultisigRequest>;
+    fn multisig_requests(
+        &self,
+        from_index: u64,
+        limit: u64,
+    ) -> std::vec::Vec<(String, MultisigRequest)>;
+    fn multisig_members(&self) -> std::vec::Vec<near_sdk::AccountId>;
+    fn multisig_threshold(&self) -> u64;
+    fn add_multisig_member(&mut self, account_id: near_sdk::AccountId);
+    fn remove_multisig_member(&mut self, account_id: near_sdk::AccountId);
+    fn set_multisig_threshold(&mut self, threshold: u64);
+}
+
+#[near_bindgen]
+impl Multisig for StatusMessage {
+    fn create_request(&mut self, method: String, args: String) -> String {
+        let args = near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args)
+            .unwrap_or_else(|_| near_sdk::env::panic(b"Arguments have to be a JSON object"))
+            .to_string();
+        let id = Self::multisig_id(&method, &args);
+        if self.multisig_confirm(&id, &method, args) {
+            self.multisig_execute(&id);
+        }
+        id
+    }
+
+    fn confirm(&mut self, id: String) {
+        let request = self.multisig_requests.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Request {} does not exist", id).as_bytes())
+        });
+        if near_sdk::env::block_timestamp() >= request.expires_at {
+            near_sdk::env::panic(format!("Request {} has expired", id).as_bytes())
+        }
+        if self.multisig_confirm(&id, &request.method, request.args) {
+            self.multisig_execute(&id);
+        }
+    }
+
+    fn revoke_confirmation(&mut self, id: String) {
+        let caller = near_sdk::env::predecessor_account_id();
+        let mut request = self.multisig_requests.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Request {} does not exist", id).as_bytes())
+        });
+        let count = request.confirmations.len();
+        request.confirmations.retain(|account| *account != caller);
+        if request.confirmations.len() == count {
+            near_sdk::env::panic(
+                format!("Account {} has not confirmed request {}", caller, id).as_bytes(),
+            )
+        }
+        near_sdk::env::log(format!("Request {} is revoked by {}", id, caller).as_bytes());
+        if request.confirmations.is_empty() {
+            self.multisig_requests.remove(&id);
+        } else {
+            self.multisig_requests.insert(&id, &request);
+        }
+    }
+
+    fn multisig_request(&self, id: String) -> Option<MultisigRequest> {
+        self.multisig_requests.get(&id)
+    }
+
+    fn multisig_requests(
+        &self,
+        from_index: u64,
+        limit: u64,
+    ) -> std::vec::Vec<(String, MultisigRequest)> {
+        self.multisig_requests
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    fn multisig_members(&self) -> std::vec::Vec<near_sdk::AccountId> {
+        self.multisig_members.to_vec()
+    }
+
+    fn multisig_threshold(&self) -> u64 {
+        self.multisig_threshold
+    }
+
+    fn add_multisig_member(&mut self, account_id: near_sdk::AccountId) {
+        Self::multisig_assert_self();
+        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+        }
+        if !self.multisig_members.insert(&account_id) {
+            near_sdk::env::panic(format!("Account {} is already a member", account_id).as_bytes())
+        }
+        near_sdk::env::log(format!("Account {} is added to the members", account_id).as_bytes())
+    }
+
+    fn remove_multisig_member(&mut self, account_id: near_sdk::AccountId) {
+        Self::multisig_assert_self();
+        if !self.multisig_members.remove(&account_id) {
+            near_sdk::env::panic(format!("Account {} is not a member", account_id).as_bytes())
+        }
+        if self.multisig_members.len() < self.multisig_threshold {
+            near_sdk::env::panic(b"Members cannot be fewer than the threshold")
+        }
+        near_sdk::env::log(format!("Account {} is removed from the members", account_id).as_bytes())
+    }
+
+    fn set_multisig_threshold(&mut self, threshold: u64) {
+        Self::multisig_assert_self();
+        if threshold == 0 || threshold > self.multisig_members.len() {
+            near_sdk::env::panic(b"Threshold has to be between 1 and the number of members")
+        }
+        near_sdk::env::log(format!("Threshold is set to {}", threshold).as_bytes());
+        self.multisig_threshold = threshold;
+    }
+}
+
+impl StatusMessage {
+    pub fn setup_multisig(&mut self, members: std::vec::Vec<near_sdk::AccountId>, threshold: u64) {
+        for member in members.iter() {
+            self.multisig_members.insert(member);
+        }
+        if threshold == 0 || threshold > self.multisig_members.len() {
+            near_sdk::env::panic(b"Threshold has to be between 1 and the number of members")
+        }
+        self.multisig_threshold = threshold;
+    }
+
+    fn multisig_assert_self() {
+        if near_sdk::env::predecessor_account_id() != near_sdk::env::current_account_id() {
+            near_sdk::env::panic(b"Method is private")
+        }
+    }
+
+    fn multisig_id(method: &str, args: &str) -> String {
+        near_sdk::env::sha256(format!("{}:{}", method, args).as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn multisig_confirm(&mut self, id: &str, method: &str, args: String) -> bool {
+        let caller = near_sdk::env::predecessor_account_id();
+        let now = near_sdk::env::block_timestamp();
+        if !self.multisig_members.contains(&caller) {
+            near_sdk::env::panic(format!("Account {} is not a member", caller).as_bytes())
+        }
+        let mut request = match self.multisig_requests.get(&id.to_string()) {
+            Some(request) if now < request.expires_at => request,
+            _ => {
+                near_sdk::env::log(
+                    format!(
+                        "Request {} calling {}({}) is created by {}",
+                        id, method, args, caller
+                    )
+                    .as_bytes(),
+                );
+                MultisigRequest {
+                    method: method.to_string(),
+                    args,
+                    created_by: caller.clone(),
+                    created_at: now,
+                    expires_at: now.saturating_add(3600000000000u64),
+                    confirmations: std::vec::Vec::new(),
+                }
+            }
+        };
+        if request.confirmations.contains(&caller) {
+            near_sdk::env::panic(
+                format!("Account {} has already confirmed request {}", caller, id).as_bytes(),
+            )
+        }
+        request.confirmations.push(caller.clone());
+        let confirmed = request
+            .confirmations
+            .iter()
+            .filter(|account| self.multisig_members.contains(account))
+            .count() as u64;
+        near_sdk::env::log(
+            format!(
+                "Request {} is confirmed by {} ({}/{})",
+                id, caller, confirmed, self.multisig_threshold
+            )
+            .as_bytes(),
+        );
+        self.multisig_requests.insert(&id.to_string(), &request);
+        confirmed >= self.multisig_threshold
+    }
+
+    fn multisig_execute(&mut self, id: &str) {
+        let request = self.multisig_requests.remove(&id.to_string()).unwrap();
+        near_sdk::env::log(format!("Request {} is executed", id).as_bytes());
+        near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+            request.method.into_bytes(),
+            request.args.into_bytes(),
+            0,
+            near_sdk::env::prepaid_gas()
+                .saturating_sub(near_sdk::env::used_gas())
+                .saturating_sub(20_000_000_000_000),
+        );
+    }
+
+    fn multisig_call(&mut self, method: &str, args: String) -> bool {
+        if near_sdk::env::predecessor_account_id() == near_sdk::env::current_account_id() {
+            return true;
+        }
+        let id = Self::multisig_id(method, &args);
+        if !self.multisig_confirm(&id, method, args) {
+            return false;
+        }
+        self.multisig_requests.remove(&id);
+        near_sdk::env::log(format!("Request {} is executed", id).as_bytes());
+        true
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(members: Vec<AccountId>, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: data,
+            multisig_members: near_sdk::collections::UnorderedSet::new(
+                StorageKeyMultisig::Members.into_bytes(),
+            ),
+            multisig_threshold: 1,
+            multisig_requests: near_sdk::collections::UnorderedMap::new(
+                StorageKeyMultisig::Requests.into_bytes(),
+            ),
+        };
+
+        constructor.setup_multisig(members, 2);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        if !self.multisig_call(
+            "set_data",
+            near_sdk::serde_json::json!
+    ({ "data" : & data })
+            .to_string(),
+        ) {
+            return;
+        }
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![
+                String::from("bob.testnet"),
+                String::from("carol.testnet"),
+                String::from("dave.testnet"),
+            ],
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_run_after_threshold() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        let requests = contract.multisig_requests(0, 10);
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].1,
+            MultisigRequest {
+                method: String::from("set_data"),
+                args: String::from(r#"{"data":"NEW DATA"}"#),
+                created_by: String::from("bob.testnet"),
+                created_at: 10 * SECOND,
+                expires_at: 3610 * SECOND,
+                confirmations: vec![String::from("bob.testnet")],
+            }
+        );
+
+        at("carol.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert!(contract.multisig_requests(0, 10).is_empty());
+    }
+
+    #[test]
+    fn should_confirm_by_id() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        let id = contract.create_request(
+            String::from("set_data"),
+            String::from(r#"{ "data": "NEW DATA" }"#),
+        );
+
+        at("carol.testnet", 20);
+
+        contract.confirm(id.clone());
+
+        assert_eq!(contract.multisig_request(id), None);
+
+        // The confirmation calls set_data from the contract account
+        at("alice.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn should_revoke_confirmation() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        let id = contract.multisig_requests(0, 10)[0].0.clone();
+
+        contract.revoke_confirmation(id.clone());
+
+        assert_eq!(contract.multisig_request(id), None);
+
+        at("carol.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"has expired"#)]
+    fn should_not_confirm_expired_request() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        let id = contract.multisig_requests(0, 10)[0].0.clone();
+
+        at("carol.testnet", 3610);
+
+        contract.confirm(id);
+    }
+
+    #[test]
+    fn expired_request_starts_over() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("carol.testnet", 3610);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+        assert_eq!(
+            contract.multisig_requests(0, 10)[0].1.confirmations,
+            vec![String::from("carol.testnet")]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet has already confirmed request"#)]
+    fn should_not_confirm_twice() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet is not a member"#)]
+    fn only_members_can_confirm() {
+        let mut contract = setup();
+
+        at("mallory.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn members_are_changed_by_requests() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        let id = contract.create_request(
+            String::from("set_multisig_threshold"),
+            String::from(r#"{"threshold": 3}"#),
+        );
+
+        at("carol.testnet", 20);
+
+        contract.confirm(id);
+
+        at("alice.testnet", 20);
+
+        contract.set_multisig_threshold(3);
+
+        assert_eq!(contract.multisig_threshold(), 3);
+        assert_eq!(contract.multisig_members().len(), 3);
+    }
+
+    #[test]
+    fn removed_members_do_not_count() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        // A confirmed request removes bob
+        at("alice.testnet", 20);
+
+        contract.remove_multisig_member(String::from("bob.testnet"));
+
+        at("carol.testnet", 30);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        at("dave.testnet", 40);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Method is private"#)]
+    fn members_cannot_be_changed_directly() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.add_multisig_member(String::from("mallory.testnet"));
+    }
+}
@@ -0,0 +1,279 @@
+use near_macros::{init_macro, multisig, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Multisig(expiration_secs = 3600))]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(members: Vec<AccountId>, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!(["multisig", Self { data: data }]);
+
+        constructor.setup_multisig(members, 2);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    #[multisig]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![
+                String::from("bob.testnet"),
+                String::from("carol.testnet"),
+                String::from("dave.testnet"),
+            ],
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_run_after_threshold() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        let requests = contract.multisig_requests(0, 10);
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].1,
+            MultisigRequest {
+                method: String::from("set_data"),
+                args: String::from(r#"{"data":"NEW DATA"}"#),
+                created_by: String::from("bob.testnet"),
+                created_at: 10 * SECOND,
+                expires_at: 3610 * SECOND,
+                confirmations: vec![String::from("bob.testnet")],
+            }
+        );
+
+        at("carol.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert!(contract.multisig_requests(0, 10).is_empty());
+    }
+
+    #[test]
+    fn should_confirm_by_id() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        let id = contract.create_request(
+            String::from("set_data"),
+            String::from(r#"{ "data": "NEW DATA" }"#),
+        );
+
+        at("carol.testnet", 20);
+
+        contract.confirm(id.clone());
+
+        assert_eq!(contract.multisig_request(id), None);
+
+        // The confirmation calls set_data from the contract account
+        at("alice.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn should_revoke_confirmation() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        let id = contract.multisig_requests(0, 10)[0].0.clone();
+
+        contract.revoke_confirmation(id.clone());
+
+        assert_eq!(contract.multisig_request(id), None);
+
+        at("carol.testnet", 20);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"has expired"#)]
+    fn should_not_confirm_expired_request() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        let id = contract.multisig_requests(0, 10)[0].0.clone();
+
+        at("carol.testnet", 3610);
+
+        contract.confirm(id);
+    }
+
+    #[test]
+    fn expired_request_starts_over() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("carol.testnet", 3610);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+        assert_eq!(
+            contract.multisig_requests(0, 10)[0].1.confirmations,
+            vec![String::from("carol.testnet")]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet has already confirmed request"#)]
+    fn should_not_confirm_twice() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mallory.testnet is not a member"#)]
+    fn only_members_can_confirm() {
+        let mut contract = setup();
+
+        at("mallory.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn members_are_changed_by_requests() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        let id = contract.create_request(
+            String::from("set_multisig_threshold"),
+            String::from(r#"{"threshold": 3}"#),
+        );
+
+        at("carol.testnet", 20);
+
+        contract.confirm(id);
+
+        at("alice.testnet", 20);
+
+        contract.set_multisig_threshold(3);
+
+        assert_eq!(contract.multisig_threshold(), 3);
+        assert_eq!(contract.multisig_members().len(), 3);
+    }
+
+    #[test]
+    fn removed_members_do_not_count() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        // A confirmed request removes bob
+        at("alice.testnet", 20);
+
+        contract.remove_multisig_member(String::from("bob.testnet"));
+
+        at("carol.testnet", 30);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        at("dave.testnet", 40);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Method is private"#)]
+    fn members_cannot_be_changed_directly() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.add_multisig_member(String::from("mallory.testnet"));
+    }
+}
@@ -0,0 +1,1386 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const MINTER: &str = "minter";
+const PAUSER: &str = "pauser";
+const UNPAUSER: &str = "unpauser";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    pause_status: bool,
+    nft_owner_by_id: near_sdk::collections::LookupMap<String, near_sdk::AccountId>,
+    nft_token_metadata: near_sdk::collections::LookupMap<String, TokenMetadata>,
+    nft_metadata: near_sdk::collections::LazyOption<NFTContractMetadata>,
+    nft_token_ids: near_sdk::collections::UnorderedSet<String>,
+    nft_tokens_per_owner: near_sdk::collections::LookupMap<
+        near_sdk::AccountId,
+        near_sdk::collections::UnorderedSet<String>,
+    >,
+    nft_approvals: near_sdk::collections::LookupMap<
+        String,
+        std::collections::HashMap<near_sdk::AccountId, u64>,
+    >,
+    nft_next_approval_ids: near_sdk::collections::LookupMap<String, u64>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyPausable {
+    Info,
+    Features,
+    FeatureSet,
+}
+
+impl StorageKeyPausable {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyPausable::Info => "pai".to_string(),
+            StorageKeyPausable::Features => "paf".to_string(),
+            StorageKeyPausable::FeatureSet => "pas".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseInfo {
+    pub paused_by: near_sdk::AccountId,
+    pub paused_at: u64,
+    pub reason: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+pub trait Pausable {
+    fn toggle_pause(&mut self);
+    fn pause(&mut self, reason: Option<String>);
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>);
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>);
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>);
+    fn unpause(&mut self);
+    fn is_paused(&self) -> bool;
+    fn when_not_paused(&self);
+    fn pause_feature(&mut self, feature: &str);
+    fn unpause_feature(&mut self, feature: &str);
+    fn is_feature_paused(&self, feature: &str) -> bool;
+    fn when_not_paused_feature(&self, feature: &str);
+    fn when_paused(&self);
+    fn when_paused_feature(&self, feature: &str);
+}
+
+impl Pausable for StatusMessage {
+    fn toggle_pause(&mut self) {
+        if !self.is_paused() {
+            Pausable::pause(self, None)
+        } else {
+            Pausable::unpause(self)
+        }
+    }
+
+    fn pause(&mut self, reason: Option<String>) {
+        self.pause_with_expiry(reason, None)
+    }
+
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        let expires_at = near_sdk::env::block_timestamp()
+            .checked_add(duration_ns)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Pause duration overflow"));
+        Pausable::pause_until(self, expires_at, reason)
+    }
+
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        if timestamp <= near_sdk::env::block_timestamp() {
+            near_sdk::env::panic(b"Pause deadline must be in the future")
+        }
+        self.pause_with_expiry(reason, Some(timestamp))
+    }
+
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"The system is already paused")
+        }
+        let info = PauseInfo {
+            paused_by: near_sdk::env::predecessor_account_id(),
+            paused_at: near_sdk::env::block_timestamp(),
+            reason,
+            expires_at,
+        };
+        near_sdk::env::log(
+            format!(
+                "The system is paused by {} at {} until {}. Reason: {}",
+                info.paused_by,
+                info.paused_at,
+                info.expires_at
+                    .map_or("unpaused".to_string(), |t| t.to_string()),
+                info.reason.as_deref().unwrap_or("not specified")
+            )
+            .as_bytes(),
+        );
+        self.pause_status = true;
+        Self::pause_record().set(&info);
+    }
+
+    fn unpause(&mut self) {
+        if !self.pause_status {
+            near_sdk::env::panic(b"The system is not paused")
+        }
+        self.pause_status = false;
+        Self::pause_record().remove();
+        near_sdk::env::log(
+            format!(
+                "The system is unpaused by {} at {}",
+                near_sdk::env::predecessor_account_id(),
+                near_sdk::env::block_timestamp()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn is_paused(&self) -> bool {
+        if !self.pause_status {
+            return false;
+        }
+        match Self::pause_record().get().and_then(|info| info.expires_at) {
+            Some(expires_at) => near_sdk::env::block_timestamp() < expires_at,
+            None => true,
+        }
+    }
+
+    fn when_not_paused(&self) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"Function is paused")
+        }
+    }
+
+    fn pause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.insert(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is paused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn unpause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.remove(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is unpaused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn is_feature_paused(&self, feature: &str) -> bool {
+        self.is_paused() || Self::paused_feature_set().contains(&feature.to_string())
+    }
+
+    fn when_not_paused_feature(&self, feature: &str) {
+        self.when_not_paused();
+        if Self::paused_feature_set().contains(&feature.to_string()) {
+            near_sdk::env::panic(format!("Feature {} is paused", feature).as_bytes())
+        }
+    }
+
+    fn when_paused(&self) {
+        if !self.is_paused() {
+            near_sdk::env::panic(b"Function is not paused")
+        }
+    }
+
+    fn when_paused_feature(&self, feature: &str) {
+        if !self.is_feature_paused(feature) {
+            near_sdk::env::panic(format!("Feature {} is not paused", feature).as_bytes())
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause_info(&self) -> Option<PauseInfo> {
+        if self.is_paused() {
+            Self::pause_record().get()
+        } else {
+            None
+        }
+    }
+
+    pub fn paused_features(&self) -> Vec<String> {
+        Self::paused_feature_set().to_vec()
+    }
+}
+
+impl StatusMessage {
+    fn pause_record() -> near_sdk::collections::LazyOption<PauseInfo> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Info.into_bytes(), None)
+    }
+
+    fn paused_feature_set() -> near_sdk::collections::UnorderedSet<String> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .get()
+            .unwrap_or_else(|| {
+                near_sdk::collections::UnorderedSet::new(
+                    StorageKeyPausable::FeatureSet.into_bytes(),
+                )
+            })
+    }
+
+    fn save_paused_feature_set(features: &near_sdk::collections::UnorderedSet<String>) {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .set(features);
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause(&mut self, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause(self, reason)
+    }
+
+    pub fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_for(self, duration_ns, reason)
+    }
+
+    pub fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_until(self, timestamp, reason)
+    }
+
+    pub fn unpause(&mut self) {
+        self.assert_role(&"unpauser".to_string());
+        Pausable::unpause(self)
+    }
+
+    pub fn pause_feature(&mut self, feature: String) {
+        self.assert_role(&"pauser".to_string());
+        Pausable::pause_feature(self, &feature)
+    }
+
+    pub fn unpause_feature(&mut self, feature: String) {
+        self.assert_role(&"unpauser".to_string());
+        Pausable::unpause_feature(self, &feature)
+    }
+}
+
+pub enum StorageKeyNonFungibleToken {
+    OwnerById,
+    TokenMetadata,
+    Metadata,
+    TokenIds,
+    TokensPerOwner,
+    OwnerTokens,
+    Approvals,
+    NextApprovalIds,
+}
+
+impl StorageKeyNonFungibleToken {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyNonFungibleToken::OwnerById => "nfo".to_string(),
+            StorageKeyNonFungibleToken::TokenMetadata => "nfd".to_string(),
+            StorageKeyNonFungibleToken::Metadata => "nfm".to_string(),
+            StorageKeyNonFungibleToken::TokenIds => "nft".to_string(),
+            StorageKeyNonFungibleToken::TokensPerOwner => "nfp".to_string(),
+            StorageKeyNonFungibleToken::OwnerTokens => "nfs".to_string(),
+            StorageKeyNonFungibleToken::Approvals => "nfa".to_string(),
+            StorageKeyNonFungibleToken::NextApprovalIds => "nfn".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NFTContractMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub base_uri: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Default,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<near_sdk::json_types::Base64VecU8>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<near_sdk::json_types::Base64VecU8>,
+}
+
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Token {
+    pub token_id: String,
+    pub owner_id: near_sdk::AccountId,
+    pub metadata: Option<TokenMetadata>,
+    pub approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>,
+}
+
+pub trait NonFungibleToken {
+    fn nft_transfer(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    );
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> near_sdk::PromiseOrValue<bool>;
+    fn nft_token(&self, token_id: String) -> Option<Token>;
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: near_sdk::AccountId,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>,
+    ) -> bool;
+    fn nft_metadata(&self) -> NFTContractMetadata;
+}
+
+#[near_bindgen]
+impl NonFungibleToken for StatusMessage {
+    #[payable]
+    fn nft_transfer(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        Self::nft_assert_one_yocto();
+        self.when_not_paused();
+        let sender_id = near_sdk::env::predecessor_account_id();
+        self.nft_transfer_internal(&sender_id, &receiver_id, &token_id, approval_id, memo);
+    }
+
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> near_sdk::PromiseOrValue<bool> {
+        Self::nft_assert_one_yocto();
+        self.when_not_paused();
+        let sender_id = near_sdk::env::predecessor_account_id();
+        let (previous_owner_id, approvals) =
+            self.nft_transfer_internal(&sender_id, &receiver_id, &token_id, approval_id, memo);
+        let receiver_gas = near_sdk::env::prepaid_gas()
+            .checked_sub(Self::NFT_GAS_FOR_TRANSFER_CALL)
+            .unwrap_or_else(|| {
+                near_sdk::env::panic(b"Prepaid gas is not enough for nft_transfer_call")
+            });
+        near_sdk::Promise::new(receiver_id.clone())
+            .function_call(
+                b"nft_on_transfer".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "sender_id" : sender_id, "previous_owner_id" : previous_owner_id,
+                    "token_id" : token_id, "msg" : msg,
+                })
+                .to_string()
+                .into_bytes(),
+                0,
+                receiver_gas,
+            )
+            .then(
+                near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                    b"nft_resolve_transfer".to_vec(),
+                    near_sdk::serde_json::json!
+                    ({
+                        "previous_owner_id" : previous_owner_id, "receiver_id" :
+                        receiver_id, "token_id" : token_id, "approved_account_ids" :
+                        approvals,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    0,
+                    Self::NFT_GAS_FOR_RESOLVE_TRANSFER,
+                ),
+            )
+            .into()
+    }
+
+    fn nft_token(&self, token_id: String) -> Option<Token> {
+        let owner_id = self.nft_owner_by_id.get(&token_id)?;
+        Some(Token {
+            metadata: self.nft_token_metadata.get(&token_id),
+            approved_account_ids: Some(self.nft_approvals.get(&token_id).unwrap_or_default()),
+            token_id,
+            owner_id,
+        })
+    }
+
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: near_sdk::AccountId,
+        receiver_id: near_sdk::AccountId,
+        token_id: String,
+        approved_account_ids: Option<std::collections::HashMap<near_sdk::AccountId, u64>>,
+    ) -> bool {
+        let keep = match near_sdk::env::promise_result(0) {
+            near_sdk::PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<bool>(&value)
+                    .map_or(false, |return_token| !return_token)
+            }
+            _ => false,
+        };
+        if keep {
+            return true;
+        }
+        if self.nft_owner_by_id.get(&token_id).as_ref() != Some(&receiver_id) {
+            return true;
+        }
+        self.nft_move_token(Some(&receiver_id), &previous_owner_id, &token_id);
+        self.nft_approvals.remove(&token_id);
+        if let Some(approvals) = approved_account_ids {
+            self.nft_approvals.insert(&token_id, &approvals);
+        }
+        Self::nft_event(
+            "nft_transfer",
+            near_sdk::serde_json::json!
+            ({
+                "old_owner_id" : receiver_id, "new_owner_id" : previous_owner_id,
+                "token_ids" : [token_id],
+            }),
+        );
+        false
+    }
+
+    fn nft_metadata(&self) -> NFTContractMetadata {
+        self.nft_metadata
+            .get()
+            .unwrap_or_else(|| near_sdk::env::panic(b"Contract metadata is not set"))
+    }
+}
+
+impl StatusMessage {
+    pub const NFT_GAS_FOR_APPROVE: near_sdk::Gas = 10_000_000_000_000;
+    pub const NFT_GAS_FOR_RESOLVE_TRANSFER: near_sdk::Gas = 5_000_000_000_000;
+    pub const NFT_GAS_FOR_TRANSFER_CALL: near_sdk::Gas =
+        25_000_000_000_000 + Self::NFT_GAS_FOR_RESOLVE_TRANSFER;
+    pub fn setup_non_fungible_token(&mut self, metadata: NFTContractMetadata) {
+        if metadata.spec != "nft-1.0.0" {
+            near_sdk::env::panic(b"Contract metadata spec has to be nft-1.0.0")
+        }
+        if metadata.reference.is_some() != metadata.reference_hash.is_some() {
+            near_sdk::env::panic(
+                b"Contract metadata reference and reference_hash have to be set together",
+            )
+        }
+        if let Some(reference_hash) = &metadata.reference_hash {
+            if reference_hash.0.len() != 32 {
+                near_sdk::env::panic(b"Contract metadata reference_hash has to be 32 bytes")
+            }
+        }
+        self.nft_metadata.set(&metadata);
+    }
+
+    pub fn nft_mint(
+        &mut self,
+        token_id: String,
+        owner_id: &near_sdk::AccountId,
+        metadata: Option<TokenMetadata>,
+    ) -> Token {
+        if self.nft_owner_by_id.get(&token_id).is_some() {
+            near_sdk::env::panic(format!("Token {} already exists", token_id).as_bytes())
+        }
+        if !near_sdk::env::is_valid_account_id(owner_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", owner_id).as_bytes())
+        }
+        self.nft_move_token(None, owner_id, &token_id);
+        if let Some(metadata) = &metadata {
+            self.nft_token_metadata.insert(&token_id, metadata);
+        }
+        self.nft_token_ids.insert(&token_id);
+        Self::nft_event(
+            "nft_mint",
+            near_sdk::serde_json::json!
+        ({ "owner_id" : owner_id, "token_ids" : [token_id], }),
+        );
+        self.nft_token(token_id).unwrap()
+    }
+
+    fn nft_assert_one_yocto() {
+        if near_sdk::env::attached_deposit() != 1 {
+            near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+        }
+    }
+
+    fn nft_owner(&self, token_id: &String) -> near_sdk::AccountId {
+        self.nft_owner_by_id.get(token_id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Token {} does not exist", token_id).as_bytes())
+        })
+    }
+
+    fn nft_assert_owner(&self, token_id: &String) -> near_sdk::AccountId {
+        let owner_id = self.nft_owner(token_id);
+        let caller = near_sdk::env::predecessor_account_id();
+        if caller != owner_id {
+            near_sdk::env::panic(
+                format!("Sender {} is not the owner of token {}", caller, token_id).as_bytes(),
+            )
+        }
+        owner_id
+    }
+
+    fn nft_refund_deposit(storage_before: near_sdk::StorageUsage) {
+        let required = near_sdk::env::storage_usage().saturating_sub(storage_before)
+            as near_sdk::Balance
+            * near_sdk::env::storage_byte_cost();
+        let attached = near_sdk::env::attached_deposit();
+        if attached < required {
+            near_sdk::env::panic(
+                format!("Must attach {} yoctoNEAR to cover storage", required).as_bytes(),
+            )
+        }
+        if attached > required {
+            near_sdk::Promise::new(near_sdk::env::predecessor_account_id())
+                .transfer(attached - required);
+        }
+    }
+
+    fn nft_move_token(
+        &mut self,
+        from: Option<&near_sdk::AccountId>,
+        to: &near_sdk::AccountId,
+        token_id: &String,
+    ) {
+        self.nft_owner_by_id.insert(token_id, to);
+        if let Some(from) = from {
+            let mut tokens = self.nft_tokens_per_owner.get(from).unwrap();
+            tokens.remove(token_id);
+            if tokens.is_empty() {
+                self.nft_tokens_per_owner.remove(from);
+            } else {
+                self.nft_tokens_per_owner.insert(from, &tokens);
+            }
+        }
+        let mut tokens = self.nft_tokens_per_owner.get(to).unwrap_or_else(|| {
+            near_sdk::collections::UnorderedSet::new(
+                [
+                    StorageKeyNonFungibleToken::OwnerTokens.into_bytes(),
+                    near_sdk::env::sha256(to.as_bytes()),
+                ]
+                .concat(),
+            )
+        });
+        tokens.insert(token_id);
+        self.nft_tokens_per_owner.insert(to, &tokens);
+    }
+
+    fn nft_transfer_internal(
+        &mut self,
+        sender_id: &near_sdk::AccountId,
+        receiver_id: &near_sdk::AccountId,
+        token_id: &String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) -> (
+        near_sdk::AccountId,
+        Option<std::collections::HashMap<near_sdk::AccountId, u64>>,
+    ) {
+        let owner_id = self.nft_owner(token_id);
+        let approvals: Option<std::collections::HashMap<near_sdk::AccountId, u64>> = {
+            let approvals = self.nft_approvals.remove(token_id);
+            if sender_id != &owner_id {
+                let approved_id = approvals
+                    .as_ref()
+                    .and_then(|approvals| approvals.get(sender_id).copied())
+                    .unwrap_or_else(|| {
+                        near_sdk::env::panic(
+                            format!(
+                                "Sender {} is not approved for token {}",
+                                sender_id, token_id
+                            )
+                            .as_bytes(),
+                        )
+                    });
+                if approval_id.map_or(false, |approval_id| approval_id != approved_id) {
+                    near_sdk::env::panic(
+                        format!(
+                            "Approval of {} for token {} has changed",
+                            sender_id, token_id
+                        )
+                        .as_bytes(),
+                    )
+                }
+            }
+            approvals
+        };
+        if &owner_id == receiver_id {
+            near_sdk::env::panic(b"The token owner and the receiver should be different")
+        }
+        if !near_sdk::env::is_valid_account_id(receiver_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", receiver_id).as_bytes())
+        }
+        self.nft_move_token(Some(&owner_id), receiver_id, token_id);
+        let authorized_id = if sender_id != &owner_id {
+            Some(sender_id)
+        } else {
+            None
+        };
+        Self::nft_event(
+            "nft_transfer",
+            near_sdk::serde_json::json!
+            ({
+                "authorized_id" : authorized_id, "old_owner_id" : owner_id,
+                "new_owner_id" : receiver_id, "token_ids" : [token_id], "memo" :
+                memo,
+            }),
+        );
+        (owner_id, approvals)
+    }
+
+    fn nft_event(event: &str, mut data: near_sdk::serde_json::Value) {
+        if let Some(data) = data.as_object_mut() {
+            data.retain(|_, value| !value.is_null());
+        }
+        near_sdk::env::log(
+            format!(
+                "EVENT_JSON:{}",
+                near_sdk::serde_json::json!
+                ({
+                    "standard" : "nep171", "version" : "1.0.0", "event" : event,
+                    "data" : [data],
+                })
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+pub trait NonFungibleTokenApproval {
+    fn nft_approve(
+        &mut self,
+        token_id: String,
+        account_id: near_sdk::AccountId,
+        msg: Option<String>,
+    ) -> Option<near_sdk::Promise>;
+    fn nft_revoke(&mut self, token_id: String, account_id: near_sdk::AccountId);
+    fn nft_revoke_all(&mut self, token_id: String);
+    fn nft_is_approved(
+        &self,
+        token_id: String,
+        approved_account_id: near_sdk::AccountId,
+        approval_id: Option<u64>,
+    ) -> bool;
+}
+
+#[near_bindgen]
+impl NonFungibleTokenApproval for StatusMessage {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: String,
+        account_id: near_sdk::AccountId,
+        msg: Option<String>,
+    ) -> Option<near_sdk::Promise> {
+        self.when_not_paused();
+        if near_sdk::env::attached_deposit() == 0 {
+            near_sdk::env::panic(b"Requires attached deposit of at least 1 yoctoNEAR")
+        }
+        let owner_id = self.nft_assert_owner(&token_id);
+        let storage_before = near_sdk::env::storage_usage();
+        let approval_id = self.nft_next_approval_ids.get(&token_id).unwrap_or(0);
+        let mut approvals = self.nft_approvals.get(&token_id).unwrap_or_default();
+        approvals.insert(account_id.clone(), approval_id);
+        self.nft_approvals.insert(&token_id, &approvals);
+        self.nft_next_approval_ids
+            .insert(&token_id, &(approval_id + 1));
+        Self::nft_refund_deposit(storage_before);
+        msg.map(|msg| {
+            near_sdk::Promise::new(account_id).function_call(
+                b"nft_on_approve".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "token_id" : token_id, "owner_id" : owner_id, "approval_id" :
+                    approval_id, "msg" : msg,
+                })
+                .to_string()
+                .into_bytes(),
+                0,
+                near_sdk::env::prepaid_gas()
+                    .saturating_sub(near_sdk::env::used_gas())
+                    .saturating_sub(Self::NFT_GAS_FOR_APPROVE),
+            )
+        })
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: String, account_id: near_sdk::AccountId) {
+        Self::nft_assert_one_yocto();
+        self.nft_assert_owner(&token_id);
+        if let Some(mut approvals) = self.nft_approvals.get(&token_id) {
+            if approvals.remove(&account_id).is_some() {
+                if approvals.is_empty() {
+                    self.nft_approvals.remove(&token_id);
+                } else {
+                    self.nft_approvals.insert(&token_id, &approvals);
+                }
+            }
+        }
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: String) {
+        Self::nft_assert_one_yocto();
+        self.nft_assert_owner(&token_id);
+        self.nft_approvals.remove(&token_id);
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: String,
+        approved_account_id: near_sdk::AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        self.nft_owner(&token_id);
+        match self
+            .nft_approvals
+            .get(&token_id)
+            .and_then(|approvals| approvals.get(&approved_account_id).copied())
+        {
+            Some(approved_id) => approval_id.map_or(true, |approval_id| approval_id == approved_id),
+            None => false,
+        }
+    }
+}
+
+pub trait NonFungibleTokenEnumeration {
+    fn nft_total_supply(&self) -> near_sdk::json_types::U128;
+    fn nft_tokens(
+        &self,
+        from_index: Option<near_sdk::json_types::U128>,
+        limit: Option<u64>,
+    ) -> std::vec::Vec<Token>;
+    fn nft_supply_for_owner(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+    fn nft_tokens_for_owner(
+        &self,
+        account_id: near_sdk::AccountId,
+        from_index: Option<near_sdk::json_types::U128>,
+        limit: Option<u64>,
+    ) -> std::vec::Vec<Token>;
+}
+
+#[near_bindgen]
+impl NonFungibleTokenEnumeration for StatusMessage {
+    fn nft_total_supply(&self) -> near_sdk::json_types::U128 {
+        (self.nft_token_ids.len() as u128).into()
+    }
+
+    fn nft_tokens(
+        &self,
+        from_index: Option<near_sdk::json_types::U128>,
+        limit: Option<u64>,
+    ) -> std::vec::Vec<Token> {
+        let (from_index, limit) = Self::nft_page(from_index, limit);
+        self.nft_token_ids
+            .iter()
+            .skip(from_index)
+            .take(limit)
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
+    fn nft_supply_for_owner(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+        let supply = self
+            .nft_tokens_per_owner
+            .get(&account_id)
+            .map_or(0, |tokens| tokens.len());
+        (supply as u128).into()
+    }
+
+    fn nft_tokens_for_owner(
+        &self,
+        account_id: near_sdk::AccountId,
+        from_index: Option<near_sdk::json_types::U128>,
+        limit: Option<u64>,
+    ) -> std::vec::Vec<Token> {
+        let (from_index, limit) = Self::nft_page(from_index, limit);
+        match self.nft_tokens_per_owner.get(&account_id) {
+            Some(tokens) => tokens
+                .iter()
+                .skip(from_index)
+                .take(limit)
+                .map(|token_id| self.nft_token(token_id).unwrap())
+                .collect(),
+            None => std::vec::Vec::new(),
+        }
+    }
+}
+
+impl StatusMessage {
+    fn nft_page(
+        from_index: Option<near_sdk::json_types::U128>,
+        limit: Option<u64>,
+    ) -> (usize, usize) {
+        if limit == Some(0) {
+            near_sdk::env::panic(b"Cannot provide limit of 0")
+        }
+        (
+            from_index.map_or(0, |from_index| from_index.0 as usize),
+            limit.map_or(usize::MAX, |limit| limit as usize),
+        )
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[payable]
+    pub fn nft_mint_token(
+        &mut self,
+        token_id: String,
+        receiver_id: near_sdk::AccountId,
+        metadata: Option<TokenMetadata>,
+    ) -> Token {
+        self.assert_role(&"minter".to_string());
+        self.when_not_paused();
+        let storage_before = near_sdk::env::storage_usage();
+        let token = self.nft_mint(token_id, &receiver_id, metadata);
+        Self::nft_refund_deposit(storage_before);
+        token
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            pause_status: false,
+            nft_owner_by_id: near_sdk::collections::LookupMap::new(
+                StorageKeyNonFungibleToken::OwnerById.into_bytes(),
+            ),
+            nft_token_metadata: near_sdk::collections::LookupMap::new(
+                StorageKeyNonFungibleToken::TokenMetadata.into_bytes(),
+            ),
+            nft_metadata: near_sdk::collections::LazyOption::new(
+                StorageKeyNonFungibleToken::Metadata.into_bytes(),
+                None,
+            ),
+            nft_token_ids: near_sdk::collections::UnorderedSet::new(
+                StorageKeyNonFungibleToken::TokenIds.into_bytes(),
+            ),
+            nft_tokens_per_owner: near_sdk::collections::LookupMap::new(
+                StorageKeyNonFungibleToken::TokensPerOwner.into_bytes(),
+            ),
+            nft_approvals: near_sdk::collections::LookupMap::new(
+                StorageKeyNonFungibleToken::Approvals.into_bytes(),
+            ),
+            nft_next_approval_ids: near_sdk::collections::LookupMap::new(
+                StorageKeyNonFungibleToken::NextApprovalIds.into_bytes(),
+            ),
+        };
+
+        for role in [DEFAULT_ADMIN, MINTER, PAUSER, UNPAUSER].iter() {
+            constructor.setup_account_role(&role.to_string(), &owner);
+        }
+
+        constructor.setup_non_fungible_token(NFTContractMetadata {
+            spec: String::from("nft-1.0.0"),
+            name: String::from("Status Collection"),
+            symbol: String::from("STATUS"),
+            icon: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+        });
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::U128;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const NEAR: Balance = 1_000_000_000_000_000_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    fn owner(contract: &StatusMessage, token_id: &str) -> AccountId {
+        contract.nft_token(String::from(token_id)).unwrap().owner_id
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"));
+
+        call("bob.testnet", NEAR);
+
+        for token_id in ["1", "2", "3"].iter() {
+            contract.nft_mint_token(
+                token_id.to_string(),
+                String::from("bob.testnet"),
+                Some(TokenMetadata {
+                    title: Some(format!("Status #{}", token_id)),
+                    ..Default::default()
+                }),
+            );
+        }
+
+        contract
+    }
+
+    #[test]
+    fn should_mint() {
+        let contract = setup();
+
+        let token = contract.nft_token(String::from("2")).unwrap();
+
+        assert_eq!(token.owner_id, "bob.testnet");
+        assert_eq!(token.metadata.unwrap().title.unwrap(), "Status #2");
+        assert_eq!(token.approved_account_ids, Some(Default::default()));
+        assert_eq!(contract.nft_metadata().symbol, "STATUS");
+        assert_eq!(contract.nft_total_supply(), U128(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Must attach")]
+    fn should_panic_on_mint_without_deposit() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_mint_token(String::from("4"), String::from("bob.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: minter role")]
+    fn should_panic_on_mint_without_role() {
+        let mut contract = setup();
+
+        call("carol.testnet", NEAR);
+        contract.nft_mint_token(String::from("4"), String::from("carol.testnet"), None);
+    }
+
+    #[test]
+    fn should_transfer_and_enumerate() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("2"), None, None);
+
+        assert_eq!(owner(&contract, "2"), "carol.testnet");
+        assert_eq!(
+            contract.nft_supply_for_owner(String::from("bob.testnet")),
+            U128(2)
+        );
+
+        let tokens = contract.nft_tokens_for_owner(String::from("carol.testnet"), None, None);
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token_id, "2");
+
+        let page = contract.nft_tokens(Some(U128(1)), Some(1));
+
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].token_id, "2");
+    }
+
+    #[test]
+    fn should_transfer_with_approval() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        assert!(contract.nft_is_approved(
+            String::from("1"),
+            String::from("market.testnet"),
+            Some(0)
+        ));
+
+        call("market.testnet", 1);
+        contract.nft_transfer(
+            String::from("carol.testnet"),
+            String::from("1"),
+            Some(0),
+            Some(String::from("sale")),
+        );
+
+        assert_eq!(owner(&contract, "1"), "carol.testnet");
+        assert!(!contract.nft_is_approved(String::from("1"), String::from("market.testnet"), None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sender market.testnet is not approved for token 1")]
+    fn should_panic_on_revoked_approval() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        call("bob.testnet", 1);
+        contract.nft_revoke(String::from("1"), String::from("market.testnet"));
+
+        call("market.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("1"), None, None);
+    }
+
+    #[test]
+    fn should_return_token_from_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        call("bob.testnet", 1);
+        contract.nft_transfer_call(
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+            None,
+            String::new(),
+        );
+
+        assert_eq!(owner(&contract, "1"), "game.testnet");
+
+        // The receiver asks to return the token
+        resolve(PromiseResult::Successful(b"true".to_vec()));
+        let kept = contract.nft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("game.testnet"),
+            String::from("1"),
+            Some(
+                [(String::from("market.testnet"), 0)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+        );
+
+        assert!(!kept);
+        assert_eq!(owner(&contract, "1"), "bob.testnet");
+        assert!(contract.nft_is_approved(
+            String::from("1"),
+            String::from("market.testnet"),
+            Some(0)
+        ));
+        assert_eq!(
+            contract.nft_supply_for_owner(String::from("game.testnet")),
+            U128(0)
+        );
+    }
+
+    #[test]
+    fn should_keep_token_after_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_transfer_call(
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+            None,
+            String::new(),
+        );
+
+        resolve(PromiseResult::Successful(b"false".to_vec()));
+        let kept = contract.nft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+        );
+
+        assert!(kept);
+        assert_eq!(owner(&contract, "1"), "game.testnet");
+    }
+
+    #[test]
+    #[should_panic(expected = "Function is paused")]
+    fn should_panic_when_paused() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.pause(None);
+
+        call("bob.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("1"), None, None);
+    }
+}
@@ -0,0 +1,14 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet"}' --accountId $ACCOUNT
+
+near call $ACCOUNT nft_mint_token '{"token_id": "1", "receiver_id": "timurguvenkaya.testnet", "metadata": {"title": "First"}}' --accountId timurguvenkaya.testnet --deposit 0.1
+
+near call $ACCOUNT nft_approve '{"token_id": "1", "account_id": "app.timurguvenkaya.testnet"}' --accountId timurguvenkaya.testnet --deposit 0.01
+
+near call $ACCOUNT nft_transfer '{"receiver_id": "app.timurguvenkaya.testnet", "token_id": "1", "approval_id": 0}' --accountId app.timurguvenkaya.testnet --depositYocto 1
+
+near view $ACCOUNT nft_tokens_for_owner '{"account_id": "app.timurguvenkaya.testnet"}'
@@ -0,0 +1,304 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const MINTER: &str = "minter";
+const PAUSER: &str = "pauser";
+const UNPAUSER: &str = "unpauser";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Pausable, NonFungibleToken)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "access",
+            "pausable",
+            "non_fungible_token",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        for role in [DEFAULT_ADMIN, MINTER, PAUSER, UNPAUSER].iter() {
+            constructor.setup_account_role(&role.to_string(), &owner);
+        }
+
+        constructor.setup_non_fungible_token(NFTContractMetadata {
+            spec: String::from("nft-1.0.0"),
+            name: String::from("Status Collection"),
+            symbol: String::from("STATUS"),
+            icon: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+        });
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::U128;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const NEAR: Balance = 1_000_000_000_000_000_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn resolve(result: PromiseResult) {
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    fn owner(contract: &StatusMessage, token_id: &str) -> AccountId {
+        contract.nft_token(String::from(token_id)).unwrap().owner_id
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"));
+
+        call("bob.testnet", NEAR);
+
+        for token_id in ["1", "2", "3"].iter() {
+            contract.nft_mint_token(
+                token_id.to_string(),
+                String::from("bob.testnet"),
+                Some(TokenMetadata {
+                    title: Some(format!("Status #{}", token_id)),
+                    ..Default::default()
+                }),
+            );
+        }
+
+        contract
+    }
+
+    #[test]
+    fn should_mint() {
+        let contract = setup();
+
+        let token = contract.nft_token(String::from("2")).unwrap();
+
+        assert_eq!(token.owner_id, "bob.testnet");
+        assert_eq!(token.metadata.unwrap().title.unwrap(), "Status #2");
+        assert_eq!(token.approved_account_ids, Some(Default::default()));
+        assert_eq!(contract.nft_metadata().symbol, "STATUS");
+        assert_eq!(contract.nft_total_supply(), U128(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Must attach")]
+    fn should_panic_on_mint_without_deposit() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_mint_token(String::from("4"), String::from("bob.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: minter role")]
+    fn should_panic_on_mint_without_role() {
+        let mut contract = setup();
+
+        call("carol.testnet", NEAR);
+        contract.nft_mint_token(String::from("4"), String::from("carol.testnet"), None);
+    }
+
+    #[test]
+    fn should_transfer_and_enumerate() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("2"), None, None);
+
+        assert_eq!(owner(&contract, "2"), "carol.testnet");
+        assert_eq!(
+            contract.nft_supply_for_owner(String::from("bob.testnet")),
+            U128(2)
+        );
+
+        let tokens = contract.nft_tokens_for_owner(String::from("carol.testnet"), None, None);
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token_id, "2");
+
+        let page = contract.nft_tokens(Some(U128(1)), Some(1));
+
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].token_id, "2");
+    }
+
+    #[test]
+    fn should_transfer_with_approval() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        assert!(contract.nft_is_approved(
+            String::from("1"),
+            String::from("market.testnet"),
+            Some(0)
+        ));
+
+        call("market.testnet", 1);
+        contract.nft_transfer(
+            String::from("carol.testnet"),
+            String::from("1"),
+            Some(0),
+            Some(String::from("sale")),
+        );
+
+        assert_eq!(owner(&contract, "1"), "carol.testnet");
+        assert!(!contract.nft_is_approved(String::from("1"), String::from("market.testnet"), None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sender market.testnet is not approved for token 1")]
+    fn should_panic_on_revoked_approval() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        call("bob.testnet", 1);
+        contract.nft_revoke(String::from("1"), String::from("market.testnet"));
+
+        call("market.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("1"), None, None);
+    }
+
+    #[test]
+    fn should_return_token_from_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", NEAR);
+        contract.nft_approve(String::from("1"), String::from("market.testnet"), None);
+
+        call("bob.testnet", 1);
+        contract.nft_transfer_call(
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+            None,
+            String::new(),
+        );
+
+        assert_eq!(owner(&contract, "1"), "game.testnet");
+
+        // The receiver asks to return the token
+        resolve(PromiseResult::Successful(b"true".to_vec()));
+        let kept = contract.nft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("game.testnet"),
+            String::from("1"),
+            Some(
+                [(String::from("market.testnet"), 0)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+        );
+
+        assert!(!kept);
+        assert_eq!(owner(&contract, "1"), "bob.testnet");
+        assert!(contract.nft_is_approved(
+            String::from("1"),
+            String::from("market.testnet"),
+            Some(0)
+        ));
+        assert_eq!(
+            contract.nft_supply_for_owner(String::from("game.testnet")),
+            U128(0)
+        );
+    }
+
+    #[test]
+    fn should_keep_token_after_transfer_call() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1);
+        contract.nft_transfer_call(
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+            None,
+            String::new(),
+        );
+
+        resolve(PromiseResult::Successful(b"false".to_vec()));
+        let kept = contract.nft_resolve_transfer(
+            String::from("bob.testnet"),
+            String::from("game.testnet"),
+            String::from("1"),
+            None,
+        );
+
+        assert!(kept);
+        assert_eq!(owner(&contract, "1"), "game.testnet");
+    }
+
+    #[test]
+    #[should_panic(expected = "Function is paused")]
+    fn should_panic_when_paused() {
+        let mut contract = setup();
+
+        call("bob.testnet", 0);
+        contract.pause(None);
+
+        call("bob.testnet", 1);
+        contract.nft_transfer(String::from("carol.testnet"), String::from("1"), None, None);
+    }
+}
@@ -0,0 +1,337 @@
+use near_sdk::borsh;
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8};
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    signer: AccountId,
+    signer_key: Vec<u8>,
+    nonces: near_sdk::collections::LookupMap<near_sdk::AccountId, u64>,
+}
+
+pub enum StorageKeyNonces {
+    Nonces,
+}
+
+impl StorageKeyNonces {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyNonces::Nonces => "nnc".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NoncePayload<'a, T> {
+    pub contract_id: near_sdk::AccountId,
+    pub signer_id: &'a near_sdk::AccountId,
+    pub nonce: u64,
+    pub message: &'a T,
+}
+
+pub trait Nonces {
+    fn use_nonce(&mut self, account_id: &near_sdk::AccountId, nonce: u64);
+    fn verify_signature(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool;
+    fn verify_borsh_payload<T: near_sdk::borsh::BorshSerialize>(
+        &self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) -> bool;
+    fn verify_json_payload<T: near_sdk::serde::Serialize>(
+        &self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) -> bool;
+    fn use_borsh_payload<T: near_sdk::borsh::BorshSerialize>(
+        &mut self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    );
+    fn use_json_payload<T: near_sdk::serde::Serialize>(
+        &mut self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    );
+}
+
+impl Nonces for StatusMessage {
+    fn use_nonce(&mut self, account_id: &near_sdk::AccountId, nonce: u64) {
+        let last = self.nonces.get(account_id).unwrap_or(0);
+        if nonce <= last {
+            near_sdk::env::panic(
+                format!("Nonce {} of {} is already used", nonce, account_id).as_bytes(),
+            )
+        }
+        self.nonces.insert(account_id, &nonce);
+    }
+
+    fn verify_signature(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let public_key = match public_key.len() {
+            33 if public_key[0] == 0 => &public_key[1..],
+            _ => public_key,
+        };
+        let public_key = match ed25519_dalek::PublicKey::from_bytes(public_key) {
+            Ok(public_key) => public_key,
+            Err(_) => return false,
+        };
+        let signature =
+            match <ed25519_dalek::Signature as std::convert::TryFrom<&[u8]>>::try_from(signature) {
+                Ok(signature) => signature,
+                Err(_) => return false,
+            };
+        public_key.verify_strict(message, &signature).is_ok()
+    }
+
+    fn verify_borsh_payload<T: near_sdk::borsh::BorshSerialize>(
+        &self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) -> bool {
+        let payload = NoncePayload {
+            contract_id: near_sdk::env::current_account_id(),
+            signer_id,
+            nonce,
+            message,
+        };
+        let payload = near_sdk::borsh::BorshSerialize::try_to_vec(&payload)
+            .unwrap_or_else(|_| near_sdk::env::panic(b"Failed to serialize the payload"));
+        self.verify_signature(public_key, &payload, signature)
+    }
+
+    fn verify_json_payload<T: near_sdk::serde::Serialize>(
+        &self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) -> bool {
+        let payload = NoncePayload {
+            contract_id: near_sdk::env::current_account_id(),
+            signer_id,
+            nonce,
+            message,
+        };
+        let payload = near_sdk::serde_json::to_vec(&payload)
+            .unwrap_or_else(|_| near_sdk::env::panic(b"Failed to serialize the payload"));
+        self.verify_signature(public_key, &payload, signature)
+    }
+
+    fn use_borsh_payload<T: near_sdk::borsh::BorshSerialize>(
+        &mut self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) {
+        if !self.verify_borsh_payload(signer_id, public_key, nonce, message, signature) {
+            near_sdk::env::panic(format!("Signature of {} is invalid", signer_id).as_bytes())
+        }
+        self.use_nonce(signer_id, nonce)
+    }
+
+    fn use_json_payload<T: near_sdk::serde::Serialize>(
+        &mut self,
+        signer_id: &near_sdk::AccountId,
+        public_key: &[u8],
+        nonce: u64,
+        message: &T,
+        signature: &[u8],
+    ) {
+        if !self.verify_json_payload(signer_id, public_key, nonce, message, signature) {
+            near_sdk::env::panic(format!("Signature of {} is invalid", signer_id).as_bytes())
+        }
+        self.use_nonce(signer_id, nonce)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn nonce(&self, account_id: near_sdk::AccountId) -> u64 {
+        self.nonces.get(&account_id).unwrap_or(0)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(signer: AccountId, signer_key: Base58PublicKey, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: data,
+            signer: signer,
+            signer_key: signer_key.into(),
+            nonces: near_sdk::collections::LookupMap::new(StorageKeyNonces::Nonces.into_bytes()),
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Anyone can relay the call, the data is accepted only if the signer signed it
+    pub fn set_data_signed(&mut self, nonce: u64, data: String, signature: Base64VecU8) {
+        let signer = self.signer.clone();
+        let signer_key = self.signer_key.clone();
+
+        self.use_json_payload(&signer, &signer_key, nonce, &data, &signature.0);
+
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    // RFC 8032, test 1
+    const PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const EMPTY_SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+    // {"contract_id":"alice.testnet","signer_id":"bob.testnet","nonce":1,"message":"NEW DATA"}
+    const JSON_SIGNATURE_1: &str = "7365ed14be2833a46dabf6e865fc330a70a045b31a5625273e862d2b9fd1fe1bc66c8998981b5f61836e10824d0ee5a56d9831cf03a63d4ef8f79c7680740000";
+    // The same payload with nonce 2
+    const JSON_SIGNATURE_2: &str = "574629771d9eb0ca90ee0c9312f6be503552755e54de2aa294663041f5ed40d249b89fb9bcc59fec059fb9e1a9bfcb0fafe3584f1057e32663e153b8571b9103";
+    // Borsh encoding of the nonce 1 payload
+    const BORSH_SIGNATURE_1: &str = "0ff742c70aadde27373259dbe8523837bfaf1a0cb8d58fd814f722a7ceb7c5de7e35834b0a8ab269a0d4d99373ef37aee98d276ac7f72934da5419eb544c1007";
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn setup() -> StatusMessage {
+        testing_env!(get_context(String::from("alice.testnet"), 0));
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            // The leading 0 is the ed25519 curve type
+            Base58PublicKey([vec![0], hex(PUBLIC_KEY)].concat()),
+            String::from("SOME DATA"),
+        )
+    }
+
+    fn set_data(contract: &mut StatusMessage, nonce: u64, signature: &str) {
+        testing_env!(get_context(String::from("carol.testnet"), 0));
+
+        contract.set_data_signed(nonce, String::from("NEW DATA"), Base64VecU8(hex(signature)));
+    }
+
+    #[test]
+    fn should_verify_rfc_vector() {
+        let contract = setup();
+
+        assert!(contract.verify_signature(&hex(PUBLIC_KEY), b"", &hex(EMPTY_SIGNATURE)));
+        assert!(!contract.verify_signature(&hex(PUBLIC_KEY), b"x", &hex(EMPTY_SIGNATURE)));
+        assert!(!contract.verify_signature(&hex(PUBLIC_KEY), b"", &[0; 10]));
+    }
+
+    #[test]
+    fn should_accept_signed_json_payload() {
+        let mut contract = setup();
+
+        set_data(&mut contract, 1, JSON_SIGNATURE_1);
+
+        assert_eq!(contract.get_data(), "NEW DATA");
+        assert_eq!(contract.nonce(String::from("bob.testnet")), 1);
+
+        set_data(&mut contract, 2, JSON_SIGNATURE_2);
+
+        assert_eq!(contract.nonce(String::from("bob.testnet")), 2);
+    }
+
+    #[test]
+    fn should_verify_borsh_payload() {
+        let contract = setup();
+        let data = String::from("NEW DATA");
+        let signer = String::from("bob.testnet");
+
+        assert!(contract.verify_borsh_payload(
+            &signer,
+            &hex(PUBLIC_KEY),
+            1,
+            &data,
+            &hex(BORSH_SIGNATURE_1)
+        ));
+        // A JSON signature does not verify the borsh encoding of the same payload
+        assert!(!contract.verify_borsh_payload(
+            &signer,
+            &hex(PUBLIC_KEY),
+            1,
+            &data,
+            &hex(JSON_SIGNATURE_1)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Nonce 1 of bob.testnet is already used")]
+    fn should_panic_on_nonce_reuse() {
+        let mut contract = setup();
+
+        set_data(&mut contract, 1, JSON_SIGNATURE_1);
+        set_data(&mut contract, 1, JSON_SIGNATURE_1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Signature of bob.testnet is invalid")]
+    fn should_panic_on_wrong_nonce() {
+        let mut contract = setup();
+
+        // Signed for nonce 1
+        set_data(&mut contract, 3, JSON_SIGNATURE_1);
+    }
+}
@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+# The signer key is the public key of a full access key of timurguvenkaya.testnet
+near call $ACCOUNT new '{"signer": "timurguvenkaya.testnet", "signer_key": "ed25519:FWz5Ef4uF6uCNRDcuzEMxbBCZ3DFvBGJCGfnTCaaGgjo", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near view $ACCOUNT nonce '{"account_id": "timurguvenkaya.testnet"}'
+
+# The signature is made off-chain over {"contract_id":"<ACCOUNT>","signer_id":"timurguvenkaya.testnet","nonce":1,"message":"NEW_DATA"}
+# and can be submitted by any account
+near call $ACCOUNT set_data_signed '{"nonce": 1, "data": "NEW_DATA", "signature": "<BASE64_SIGNATURE>"}' --accountId app.timurguvenkaya.testnet
+
+near view $ACCOUNT get_data
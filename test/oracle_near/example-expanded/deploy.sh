@@ -0,0 +1,12 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"admin": "timurguvenkaya.testnet", "reporters": ["timurguvenkaya.testnet", "app.timurguvenkaya.testnet"]}' --accountId $ACCOUNT
+
+near call $ACCOUNT report_price '{"asset": "NEAR/USD", "value": "1020", "decimals": 3}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT report_price '{"asset": "NEAR/USD", "value": "1030", "decimals": 3}' --accountId app.timurguvenkaya.testnet
+
+near view $ACCOUNT get_fresh_price '{"asset": "NEAR/USD", "max_age_secs": 300}'
@@ -0,0 +1,603 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const REPORTER: &str = "reporter";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    oracle_assets: near_sdk::collections::UnorderedMap<String, OracleAsset>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyOracle {
+    Assets,
+}
+
+impl StorageKeyOracle {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyOracle::Assets => "orc".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OraclePrice {
+    pub value: near_sdk::json_types::U128,
+    pub decimals: u8,
+    pub timestamp: u64,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OracleReport {
+    pub reporter: near_sdk::AccountId,
+    pub value: near_sdk::json_types::U128,
+    pub timestamp: u64,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OracleAsset {
+    pub decimals: u8,
+    pub price: Option<OraclePrice>,
+    pub reports: std::vec::Vec<OracleReport>,
+}
+
+pub trait Oracle {
+    fn report_price(&mut self, asset: String, value: near_sdk::json_types::U128, decimals: u8);
+    fn get_price(&self, asset: String) -> Option<OraclePrice>;
+    fn get_fresh_price(&self, asset: String, max_age_secs: u64) -> OraclePrice;
+    fn oracle_reports(&self, asset: String) -> std::vec::Vec<OracleReport>;
+    fn oracle_assets(&self) -> std::vec::Vec<String>;
+}
+
+#[near_bindgen]
+impl Oracle for StatusMessage {
+    fn report_price(&mut self, asset: String, value: near_sdk::json_types::U128, decimals: u8) {
+        let reporter = near_sdk::env::predecessor_account_id();
+        self.check_role(&"reporter".to_string(), &reporter);
+        let now = near_sdk::env::block_timestamp();
+        let mut oracle_asset = self.oracle_assets.get(&asset).unwrap_or(OracleAsset {
+            decimals,
+            price: None,
+            reports: std::vec::Vec::new(),
+        });
+        if oracle_asset.decimals != decimals {
+            near_sdk::env::panic(
+                format!("Asset {} has {} decimals", asset, oracle_asset.decimals).as_bytes(),
+            )
+        }
+        oracle_asset
+            .reports
+            .retain(|report| report.reporter != reporter);
+        oracle_asset.reports.push(OracleReport {
+            reporter: reporter.clone(),
+            value,
+            timestamp: now,
+        });
+        oracle_asset.reports.retain(|report| {
+            now - report.timestamp < Self::ORACLE_REPORT_TTL
+                && self.has_role(&"reporter".to_string(), &report.reporter)
+        });
+        oracle_asset.price = Self::oracle_aggregate(&oracle_asset.reports, decimals);
+        self.oracle_assets.insert(&asset, &oracle_asset);
+        near_sdk::env::log(
+            format!(
+                "Price of {} is reported as {} by {}",
+                asset, value.0, reporter
+            )
+            .as_bytes(),
+        );
+    }
+
+    fn get_price(&self, asset: String) -> Option<OraclePrice> {
+        let oracle_asset = self.oracle_assets.get(&asset)?;
+        let reports = self.oracle_reports(asset);
+        if reports.len() == oracle_asset.reports.len() {
+            oracle_asset.price
+        } else {
+            Self::oracle_aggregate(&reports, oracle_asset.decimals)
+        }
+    }
+
+    fn get_fresh_price(&self, asset: String, max_age_secs: u64) -> OraclePrice {
+        let price = self.get_price(asset.clone()).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("There is no price of {}", asset).as_bytes())
+        });
+        let age = near_sdk::env::block_timestamp() - price.timestamp;
+        if age > max_age_secs.saturating_mul(1_000_000_000) {
+            near_sdk::env::panic(
+                format!(
+                    "Price of {} is stale, it is {} seconds old",
+                    asset,
+                    age / 1_000_000_000
+                )
+                .as_bytes(),
+            )
+        }
+        price
+    }
+
+    fn oracle_reports(&self, asset: String) -> std::vec::Vec<OracleReport> {
+        self.oracle_assets
+            .get(&asset)
+            .map(|oracle_asset| oracle_asset.reports)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|report| self.has_role(&"reporter".to_string(), &report.reporter))
+            .collect()
+    }
+
+    fn oracle_assets(&self) -> std::vec::Vec<String> {
+        self.oracle_assets.keys().collect()
+    }
+}
+
+impl StatusMessage {
+    pub const ORACLE_MIN_REPORTS: u64 = 2u64;
+    pub const ORACLE_REPORT_TTL: u64 = 600000000000u64;
+    fn oracle_aggregate(reports: &[OracleReport], decimals: u8) -> Option<OraclePrice> {
+        if (reports.len() as u64) < Self::ORACLE_MIN_REPORTS {
+            return None;
+        }
+        Some(OraclePrice {
+            value: Self::oracle_median(reports).into(),
+            decimals,
+            timestamp: reports.iter().map(|report| report.timestamp).min().unwrap(),
+        })
+    }
+
+    fn oracle_median(reports: &[OracleReport]) -> near_sdk::Balance {
+        let mut values: std::vec::Vec<near_sdk::Balance> =
+            reports.iter().map(|report| report.value.0).collect();
+        values.sort_unstable();
+        let middle = values.len() / 2;
+        if values.len() % 2 == 1 {
+            values[middle]
+        } else {
+            let (a, b) = (values[middle - 1], values[middle]);
+            a / 2 + b / 2 + (a % 2 + b % 2) / 2
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, reporters: Vec<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            oracle_assets: near_sdk::collections::UnorderedMap::new(
+                StorageKeyOracle::Assets.into_bytes(),
+            ),
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+
+        for reporter in reporters.iter() {
+            constructor.setup_account_role(&REPORTER.to_string(), reporter);
+        }
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // The data can only be changed with a price of NEAR that is at most a minute old
+    pub fn set_data(&mut self, data: String) -> U128 {
+        let price = self.get_fresh_price(String::from("NEAR/USD"), 60);
+
+        self.data = data;
+
+        price.value
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn report(contract: &mut StatusMessage, reporter: &str, seconds: u64, value: u128) {
+        at(reporter, seconds);
+        contract.report_price(String::from("NEAR/USD"), U128(value), 3);
+    }
+
+    fn price(contract: &StatusMessage) -> Option<OraclePrice> {
+        contract.get_price(String::from("NEAR/USD"))
+    }
+
+    // bob, carol and dave are reporters
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 100);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            vec![
+                String::from("bob.testnet"),
+                String::from("carol.testnet"),
+                String::from("dave.testnet"),
+            ],
+        )
+    }
+
+    #[test]
+    fn should_aggregate_with_enough_reports() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        assert_eq!(price(&contract), None);
+
+        report(&mut contract, "carol.testnet", 110, 1031);
+
+        assert_eq!(
+            price(&contract),
+            Some(OraclePrice {
+                value: U128(1025),
+                decimals: 3,
+                timestamp: 100 * SECOND,
+            })
+        );
+    }
+
+    #[test]
+    fn should_take_median() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 9999);
+        report(&mut contract, "dave.testnet", 120, 1030);
+
+        assert_eq!(price(&contract).unwrap().value, U128(1030));
+    }
+
+    #[test]
+    fn should_replace_own_report() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "bob.testnet", 110, 1040);
+
+        assert_eq!(price(&contract), None);
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 1);
+    }
+
+    #[test]
+    fn should_drop_reports_of_revoked_reporters() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 9999);
+        report(&mut contract, "dave.testnet", 120, 1030);
+
+        at("bob.testnet", 130);
+        contract.revoke_role(&REPORTER.to_string(), &String::from("carol.testnet"));
+
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 2);
+        assert_eq!(price(&contract).unwrap().value, U128(1025));
+
+        report(&mut contract, "bob.testnet", 140, 1020);
+
+        assert_eq!(price(&contract).unwrap().value, U128(1025));
+    }
+
+    #[test]
+    fn should_not_price_with_too_few_reporters_left() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("bob.testnet", 130);
+        contract.revoke_role(&REPORTER.to_string(), &String::from("carol.testnet"));
+
+        assert_eq!(price(&contract), None);
+    }
+
+    #[test]
+    fn should_drop_expired_reports() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 100, 1030);
+        report(&mut contract, "dave.testnet", 700, 2000);
+
+        // The reports of bob and carol expired and dave alone is not enough
+        assert_eq!(price(&contract), None);
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 1);
+    }
+
+    #[test]
+    fn should_use_fresh_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("eve.testnet", 160);
+
+        assert_eq!(contract.set_data(String::from("NEW DATA")), U128(1025));
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Price of NEAR/USD is stale, it is 61 seconds old"#)]
+    fn should_not_use_stale_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("eve.testnet", 161);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"There is no price of NEAR/USD"#)]
+    fn should_not_use_missing_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is missing: reporter role"#)]
+    fn only_reporters_can_report() {
+        let mut contract = setup();
+
+        report(&mut contract, "mallory.testnet", 100, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Asset NEAR/USD has 3 decimals"#)]
+    fn should_keep_decimals() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        at("carol.testnet", 110);
+        contract.report_price(String::from("NEAR/USD"), U128(10300), 4);
+    }
+}
@@ -0,0 +1,255 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const REPORTER: &str = "reporter";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Oracle(min_reports = 2, report_ttl_secs = 600))]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, reporters: Vec<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "access",
+            "oracle",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+
+        for reporter in reporters.iter() {
+            constructor.setup_account_role(&REPORTER.to_string(), reporter);
+        }
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // The data can only be changed with a price of NEAR that is at most a minute old
+    pub fn set_data(&mut self, data: String) -> U128 {
+        let price = self.get_fresh_price(String::from("NEAR/USD"), 60);
+
+        self.data = data;
+
+        price.value
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn report(contract: &mut StatusMessage, reporter: &str, seconds: u64, value: u128) {
+        at(reporter, seconds);
+        contract.report_price(String::from("NEAR/USD"), U128(value), 3);
+    }
+
+    fn price(contract: &StatusMessage) -> Option<OraclePrice> {
+        contract.get_price(String::from("NEAR/USD"))
+    }
+
+    // bob, carol and dave are reporters
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 100);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            vec![
+                String::from("bob.testnet"),
+                String::from("carol.testnet"),
+                String::from("dave.testnet"),
+            ],
+        )
+    }
+
+    #[test]
+    fn should_aggregate_with_enough_reports() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        assert_eq!(price(&contract), None);
+
+        report(&mut contract, "carol.testnet", 110, 1031);
+
+        assert_eq!(
+            price(&contract),
+            Some(OraclePrice {
+                value: U128(1025),
+                decimals: 3,
+                timestamp: 100 * SECOND,
+            })
+        );
+    }
+
+    #[test]
+    fn should_take_median() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 9999);
+        report(&mut contract, "dave.testnet", 120, 1030);
+
+        assert_eq!(price(&contract).unwrap().value, U128(1030));
+    }
+
+    #[test]
+    fn should_replace_own_report() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "bob.testnet", 110, 1040);
+
+        assert_eq!(price(&contract), None);
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 1);
+    }
+
+    #[test]
+    fn should_drop_reports_of_revoked_reporters() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 9999);
+        report(&mut contract, "dave.testnet", 120, 1030);
+
+        at("bob.testnet", 130);
+        contract.revoke_role(&REPORTER.to_string(), &String::from("carol.testnet"));
+
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 2);
+        assert_eq!(price(&contract).unwrap().value, U128(1025));
+
+        report(&mut contract, "bob.testnet", 140, 1020);
+
+        assert_eq!(price(&contract).unwrap().value, U128(1025));
+    }
+
+    #[test]
+    fn should_not_price_with_too_few_reporters_left() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("bob.testnet", 130);
+        contract.revoke_role(&REPORTER.to_string(), &String::from("carol.testnet"));
+
+        assert_eq!(price(&contract), None);
+    }
+
+    #[test]
+    fn should_drop_expired_reports() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 100, 1030);
+        report(&mut contract, "dave.testnet", 700, 2000);
+
+        // The reports of bob and carol expired and dave alone is not enough
+        assert_eq!(price(&contract), None);
+        assert_eq!(contract.oracle_reports(String::from("NEAR/USD")).len(), 1);
+    }
+
+    #[test]
+    fn should_use_fresh_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("eve.testnet", 160);
+
+        assert_eq!(contract.set_data(String::from("NEW DATA")), U128(1025));
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Price of NEAR/USD is stale, it is 61 seconds old"#)]
+    fn should_not_use_stale_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+        report(&mut contract, "carol.testnet", 110, 1030);
+
+        at("eve.testnet", 161);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"There is no price of NEAR/USD"#)]
+    fn should_not_use_missing_price() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is missing: reporter role"#)]
+    fn only_reporters_can_report() {
+        let mut contract = setup();
+
+        report(&mut contract, "mallory.testnet", 100, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Asset NEAR/USD has 3 decimals"#)]
+    fn should_keep_decimals() {
+        let mut contract = setup();
+
+        report(&mut contract, "bob.testnet", 100, 1020);
+
+        at("carol.testnet", 110);
+        contract.report_price(String::from("NEAR/USD"), U128(10300), 4);
+    }
+}
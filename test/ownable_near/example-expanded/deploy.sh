@@ -0,0 +1,21 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near view $ACCOUNT owner
+
+# Should fail because app.timurguvenkaya.testnet is not the owner
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId app.timurguvenkaya.testnet
+
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT transfer_ownership '{"new_owner": "app.timurguvenkaya.testnet"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT pending_owner
+
+near call $ACCOUNT accept_ownership '{}' --accountId app.timurguvenkaya.testnet
+
+near view $ACCOUNT owner
@@ -0,0 +1,161 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Ownable)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "ownable",
+            Self {
+                data: data,
+                owner: owner
+            }
+        ]);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        self.assert_owner();
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    #[test]
+    fn owner_is_set() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        );
+
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+        assert_eq!(contract.pending_owner(), None);
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mike.testnet is not the owner"#)]
+    fn only_owner_can_set_data() {
+        let context = get_context(String::from("mike.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        );
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn two_step_transfer_works() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        );
+
+        contract.transfer_ownership(String::from("mike.testnet"));
+
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+        assert_eq!(contract.pending_owner(), Some(String::from("mike.testnet")));
+
+        testing_env!(get_context(String::from("mike.testnet"), 0));
+
+        contract.accept_ownership();
+
+        assert_eq!(contract.owner(), String::from("mike.testnet"));
+        assert_eq!(contract.pending_owner(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account jane.testnet is not the pending owner"#)]
+    fn only_pending_owner_can_accept() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        );
+
+        contract.transfer_ownership(String::from("mike.testnet"));
+
+        testing_env!(get_context(String::from("jane.testnet"), 0));
+
+        contract.accept_ownership();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account timurguvenkaya.testnet is not the owner"#)]
+    fn renounce_ownership_works() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("SOME DATA"),
+        );
+
+        contract.renounce_ownership();
+
+        assert_eq!(contract.owner(), String::new());
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+}
@@ -1,5 +1,7 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+use near_sdk::collections::{LazyOption, UnorderedSet};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
 
 setup_alloc!();
 
@@ -30,45 +32,287 @@ impl StatusMessage {
     pub fn pub_toggle_pause(&mut self) {
         self.toggle_pause()
     }
+
+    pub fn pub_pause(&mut self, reason: Option<String>) {
+        self.pause(reason)
+    }
+
+    pub fn pub_pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        self.pause_for(duration_ns, reason)
+    }
+
+    pub fn pub_unpause(&mut self) {
+        self.unpause()
+    }
+
+    pub fn pub_pause_feature(&mut self, feature: String) {
+        self.pause_feature(&feature)
+    }
+
+    pub fn pub_unpause_feature(&mut self, feature: String) {
+        self.unpause_feature(&feature)
+    }
+
+    pub fn transfer(&self) -> String {
+        self.when_not_paused_feature("transfer");
+        self.data.clone()
+    }
+
+    pub fn withdraw(&self) -> String {
+        self.when_not_paused_feature("withdraw");
+        self.data.clone()
+    }
+
+    pub fn rescue(&mut self) -> String {
+        self.when_paused();
+        self.data.clone()
+    }
+
+    pub fn rescue_transfer(&mut self) -> String {
+        self.when_paused_feature("transfer");
+        self.data.clone()
+    }
+}
+
+pub enum StorageKeyPausable {
+    Info,
+    Features,
+    FeatureSet,
+}
+
+impl StorageKeyPausable {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyPausable::Info => "pai".to_string(),
+            StorageKeyPausable::Features => "paf".to_string(),
+            StorageKeyPausable::FeatureSet => "pas".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseInfo {
+    pub paused_by: AccountId,
+    pub paused_at: u64,
+    pub reason: Option<String>,
+    pub expires_at: Option<u64>,
 }
 
 pub trait Pausable {
     fn toggle_pause(&mut self);
-    fn pause(&mut self);
+    fn pause(&mut self, reason: Option<String>);
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>);
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>);
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>);
     fn unpause(&mut self);
+    fn is_paused(&self) -> bool;
     fn when_not_paused(&self);
+    fn pause_feature(&mut self, feature: &str);
+    fn unpause_feature(&mut self, feature: &str);
+    fn is_feature_paused(&self, feature: &str) -> bool;
+    fn when_not_paused_feature(&self, feature: &str);
+    fn when_paused(&self);
+    fn when_paused_feature(&self, feature: &str);
 }
 
 impl Pausable for StatusMessage {
     fn toggle_pause(&mut self) {
-        if !self.pause_status {
-            self.pause()
+        if !self.is_paused() {
+            Pausable::pause(self, None)
         } else {
-            self.unpause()
+            Pausable::unpause(self)
+        }
+    }
+
+    fn pause(&mut self, reason: Option<String>) {
+        self.pause_with_expiry(reason, None)
+    }
+
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        let expires_at = near_sdk::env::block_timestamp()
+            .checked_add(duration_ns)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Pause duration overflow"));
+
+        Pausable::pause_until(self, expires_at, reason)
+    }
+
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        if timestamp <= near_sdk::env::block_timestamp() {
+            near_sdk::env::panic(b"Pause deadline must be in the future")
         }
+
+        self.pause_with_expiry(reason, Some(timestamp))
     }
 
-    fn pause(&mut self) {
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"The system is already paused")
+        }
+
+        let info = PauseInfo {
+            paused_by: near_sdk::env::predecessor_account_id(),
+            paused_at: near_sdk::env::block_timestamp(),
+            reason,
+            expires_at,
+        };
+
+        near_sdk::env::log(
+            format!(
+                "The system is paused by {} at {} until {}. Reason: {}",
+                info.paused_by,
+                info.paused_at,
+                info.expires_at
+                    .map_or("unpaused".to_string(), |t| t.to_string()),
+                info.reason.as_deref().unwrap_or("not specified")
+            )
+            .as_bytes(),
+        );
+
         self.pause_status = true;
-        near_sdk::env::log(b"The system is paused")
+        Self::pause_record().set(&info);
     }
 
     fn unpause(&mut self) {
+        // An expired pause is still stored until it is cleared here, so it can be unpaused as well
+        if !self.pause_status {
+            near_sdk::env::panic(b"The system is not paused")
+        }
+
         self.pause_status = false;
-        near_sdk::env::log(b"The system is unpaused")
+        Self::pause_record().remove();
+
+        near_sdk::env::log(
+            format!(
+                "The system is unpaused by {} at {}",
+                near_sdk::env::predecessor_account_id(),
+                near_sdk::env::block_timestamp()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn is_paused(&self) -> bool {
+        if !self.pause_status {
+            return false;
+        }
+
+        match Self::pause_record().get().and_then(|info| info.expires_at) {
+            Some(expires_at) => near_sdk::env::block_timestamp() < expires_at,
+            None => true,
+        }
     }
 
     fn when_not_paused(&self) {
-        if self.pause_status {
+        if self.is_paused() {
             near_sdk::env::panic(b"Function is paused")
         }
     }
+
+    fn pause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+
+        if features.insert(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is paused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn unpause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+
+        if features.remove(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is unpaused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn is_feature_paused(&self, feature: &str) -> bool {
+        self.is_paused() || Self::paused_feature_set().contains(&feature.to_string())
+    }
+
+    fn when_not_paused_feature(&self, feature: &str) {
+        self.when_not_paused();
+
+        if Self::paused_feature_set().contains(&feature.to_string()) {
+            near_sdk::env::panic(format!("Feature {} is paused", feature).as_bytes())
+        }
+    }
+
+    fn when_paused(&self) {
+        if !self.is_paused() {
+            near_sdk::env::panic(b"Function is not paused")
+        }
+    }
+
+    fn when_paused_feature(&self, feature: &str) {
+        if !self.is_feature_paused(feature) {
+            near_sdk::env::panic(format!("Feature {} is not paused", feature).as_bytes())
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause_info(&self) -> Option<PauseInfo> {
+        if self.is_paused() {
+            Self::pause_record().get()
+        } else {
+            None
+        }
+    }
+
+    pub fn paused_features(&self) -> Vec<String> {
+        Self::paused_feature_set().to_vec()
+    }
+}
+
+impl StatusMessage {
+    // The record and the paused features are stored under their own keys instead of fields,
+    // so the layout of the main struct stays the same as before they were added
+    fn pause_record() -> LazyOption<PauseInfo> {
+        LazyOption::new(StorageKeyPausable::Info.into_bytes(), None)
+    }
+
+    // The set keeps its length in its own struct, so the struct is stored as well
+    fn paused_feature_set() -> UnorderedSet<String> {
+        LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .get()
+            .unwrap_or_else(|| UnorderedSet::new(StorageKeyPausable::FeatureSet.into_bytes()))
+    }
+
+    fn save_paused_feature_set(features: &UnorderedSet<String>) {
+        LazyOption::new(StorageKeyPausable::Features.into_bytes(), None).set(features);
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
     use super::*;
+    use near_sdk::borsh::BorshSerialize;
     use near_sdk::MockedBlockchain;
     use near_sdk::{testing_env, VMContext};
 
@@ -133,4 +377,219 @@ mod tests {
 
         assert_eq!(contract.get_data(), String::from("SOME DATA"));
     }
+
+    #[test]
+    fn should_record_pause_info() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        assert_eq!(contract.pause_info(), None);
+
+        contract.pub_pause(Some(String::from("Incident")));
+
+        assert_eq!(
+            contract.pause_info(),
+            Some(PauseInfo {
+                paused_by: String::from("timurguvenkaya.testnet"),
+                paused_at: 1_000,
+                reason: Some(String::from("Incident")),
+                expires_at: None,
+            })
+        );
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.pause_status, false);
+        assert_eq!(contract.pause_info(), None);
+    }
+
+    #[test]
+    fn should_pause_feature() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        assert_eq!(contract.paused_features(), vec![String::from("transfer")]);
+        assert_eq!(contract.is_feature_paused("transfer"), true);
+        assert_eq!(contract.is_feature_paused("withdraw"), false);
+
+        assert_eq!(contract.withdraw(), String::from("SOME DATA"));
+
+        contract.pub_unpause_feature(String::from("transfer"));
+
+        assert!(contract.paused_features().is_empty());
+        assert_eq!(contract.transfer(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Feature transfer is paused"#)]
+    fn should_block_paused_feature() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        contract.transfer();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Function is paused"#)]
+    fn global_pause_blocks_features() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.is_feature_paused("withdraw"), true);
+
+        contract.withdraw();
+    }
+
+    #[test]
+    fn when_paused_allows_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        assert_eq!(contract.rescue_transfer(), String::from("SOME DATA"));
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.rescue(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Function is not paused"#)]
+    fn when_paused_blocks_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.rescue();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Feature transfer is not paused"#)]
+    fn when_paused_feature_blocks_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.rescue_transfer();
+    }
+
+    #[test]
+    fn should_lift_pause_after_deadline() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context.clone());
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_for(500, Some(String::from("Maintenance")));
+
+        assert_eq!(contract.pause_info().unwrap().expires_at, Some(1_500));
+
+        context.block_timestamp = 1_499;
+        testing_env!(context.clone());
+
+        assert_eq!(contract.is_paused(), true);
+
+        context.block_timestamp = 1_500;
+        testing_env!(context);
+
+        assert_eq!(contract.is_paused(), false);
+        assert_eq!(contract.pause_info(), None);
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        contract.pub_pause(None);
+
+        assert_eq!(contract.is_paused(), true);
+    }
+
+    #[test]
+    fn should_unpause_after_deadline() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context.clone());
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_for(500, None);
+
+        context.block_timestamp = 2_000;
+        testing_env!(context);
+
+        contract.pub_unpause();
+
+        assert_eq!(contract.is_paused(), false);
+        assert_eq!(contract.pause_info(), None);
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(contract.pause_info().unwrap().expires_at, None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"The system is already paused"#)]
+    fn should_not_pause_twice() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause(None);
+        contract.pub_pause(None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"The system is not paused"#)]
+    fn should_not_unpause_twice() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_unpause();
+    }
+
+    #[test]
+    fn should_read_state_of_previous_layout() {
+        // Layout of the contract before the pause record and the paused scopes were added
+        #[derive(BorshSerialize)]
+        struct StatusMessageV0 {
+            data: String,
+            pause_status: bool,
+        }
+
+        let context = get_context(String::from("bob.testnet"), 0);
+        testing_env!(context);
+
+        env::state_write(&StatusMessageV0 {
+            data: String::from("SOME DATA"),
+            pause_status: true,
+        });
+
+        let contract: StatusMessage = env::state_read().unwrap();
+
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(contract.pause_info(), None);
+        assert!(contract.paused_features().is_empty());
+    }
 }
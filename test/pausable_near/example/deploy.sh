@@ -14,3 +14,7 @@ near view $ACCOUNT get_data --accountId $ACCOUNT
 near call $ACCOUNT pub_toggle_pause '{}' --accountId $ACCOUNT
 
 near view $ACCOUNT get_data --accountId $ACCOUNT
+
+near call $ACCOUNT pub_pause '{"reason": "Maintenance"}' --accountId $ACCOUNT
+
+near view $ACCOUNT pause_info
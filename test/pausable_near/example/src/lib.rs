@@ -1,4 +1,4 @@
-use near_macros::{init_macro, require};
+use near_macros::{init_macro, require, when_not_paused, when_paused};
 use near_sdk::borsh;
 use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
 
@@ -22,20 +22,61 @@ impl StatusMessage {
         constructor
     }
 
+    #[when_not_paused]
     pub fn get_data(&self) -> String {
-        self.when_not_paused();
         self.data.clone()
     }
 
     pub fn pub_toggle_pause(&mut self) {
         self.toggle_pause()
     }
+
+    pub fn pub_pause(&mut self, reason: Option<String>) {
+        self.pause(reason)
+    }
+
+    pub fn pub_pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        self.pause_for(duration_ns, reason)
+    }
+
+    pub fn pub_unpause(&mut self) {
+        self.unpause()
+    }
+
+    pub fn pub_pause_feature(&mut self, feature: String) {
+        self.pause_feature(&feature)
+    }
+
+    pub fn pub_unpause_feature(&mut self, feature: String) {
+        self.unpause_feature(&feature)
+    }
+
+    #[when_not_paused("transfer")]
+    pub fn transfer(&self) -> String {
+        self.data.clone()
+    }
+
+    #[when_not_paused("withdraw")]
+    pub fn withdraw(&self) -> String {
+        self.data.clone()
+    }
+
+    #[when_paused]
+    pub fn rescue(&mut self) -> String {
+        self.data.clone()
+    }
+
+    #[when_paused("transfer")]
+    pub fn rescue_transfer(&mut self) -> String {
+        self.data.clone()
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
     use super::*;
+    use near_sdk::borsh::BorshSerialize;
     use near_sdk::MockedBlockchain;
     use near_sdk::{testing_env, VMContext};
 
@@ -100,4 +141,219 @@ mod tests {
 
         assert_eq!(contract.get_data(), String::from("SOME DATA"));
     }
+
+    #[test]
+    fn should_record_pause_info() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        assert_eq!(contract.pause_info(), None);
+
+        contract.pub_pause(Some(String::from("Incident")));
+
+        assert_eq!(
+            contract.pause_info(),
+            Some(PauseInfo {
+                paused_by: String::from("timurguvenkaya.testnet"),
+                paused_at: 1_000,
+                reason: Some(String::from("Incident")),
+                expires_at: None,
+            })
+        );
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.pause_status, false);
+        assert_eq!(contract.pause_info(), None);
+    }
+
+    #[test]
+    fn should_pause_feature() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        assert_eq!(contract.paused_features(), vec![String::from("transfer")]);
+        assert_eq!(contract.is_feature_paused("transfer"), true);
+        assert_eq!(contract.is_feature_paused("withdraw"), false);
+
+        assert_eq!(contract.withdraw(), String::from("SOME DATA"));
+
+        contract.pub_unpause_feature(String::from("transfer"));
+
+        assert!(contract.paused_features().is_empty());
+        assert_eq!(contract.transfer(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Feature transfer is paused"#)]
+    fn should_block_paused_feature() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        contract.transfer();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Function is paused"#)]
+    fn global_pause_blocks_features() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.is_feature_paused("withdraw"), true);
+
+        contract.withdraw();
+    }
+
+    #[test]
+    fn when_paused_allows_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_feature(String::from("transfer"));
+
+        assert_eq!(contract.rescue_transfer(), String::from("SOME DATA"));
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.rescue(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Function is not paused"#)]
+    fn when_paused_blocks_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.rescue();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Feature transfer is not paused"#)]
+    fn when_paused_feature_blocks_rescue() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.rescue_transfer();
+    }
+
+    #[test]
+    fn should_lift_pause_after_deadline() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context.clone());
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_for(500, Some(String::from("Maintenance")));
+
+        assert_eq!(contract.pause_info().unwrap().expires_at, Some(1_500));
+
+        context.block_timestamp = 1_499;
+        testing_env!(context.clone());
+
+        assert_eq!(contract.is_paused(), true);
+
+        context.block_timestamp = 1_500;
+        testing_env!(context);
+
+        assert_eq!(contract.is_paused(), false);
+        assert_eq!(contract.pause_info(), None);
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        contract.pub_pause(None);
+
+        assert_eq!(contract.is_paused(), true);
+    }
+
+    #[test]
+    fn should_unpause_after_deadline() {
+        let mut context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        context.block_timestamp = 1_000;
+        testing_env!(context.clone());
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause_for(500, None);
+
+        context.block_timestamp = 2_000;
+        testing_env!(context);
+
+        contract.pub_unpause();
+
+        assert_eq!(contract.is_paused(), false);
+        assert_eq!(contract.pause_info(), None);
+
+        contract.pub_toggle_pause();
+
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(contract.pause_info().unwrap().expires_at, None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"The system is already paused"#)]
+    fn should_not_pause_twice() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_pause(None);
+        contract.pub_pause(None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"The system is not paused"#)]
+    fn should_not_unpause_twice() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract = StatusMessage::new(String::from("SOME DATA"));
+
+        contract.pub_unpause();
+    }
+
+    #[test]
+    fn should_read_state_of_previous_layout() {
+        // Layout of the contract before the pause record and the paused scopes were added
+        #[derive(BorshSerialize)]
+        struct StatusMessageV0 {
+            data: String,
+            pause_status: bool,
+        }
+
+        let context = get_context(String::from("bob.testnet"), 0);
+        testing_env!(context);
+
+        env::state_write(&StatusMessageV0 {
+            data: String::from("SOME DATA"),
+            pause_status: true,
+        });
+
+        let contract: StatusMessage = env::state_read().unwrap();
+
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(contract.pause_info(), None);
+        assert!(contract.paused_features().is_empty());
+    }
 }
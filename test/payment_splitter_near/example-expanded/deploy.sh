@@ -0,0 +1,12 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"payees": ["timurguvenkaya.testnet", "app.timurguvenkaya.testnet"], "shares": [3, 1]}' --accountId $ACCOUNT
+
+near call $ACCOUNT set_data '{"data": "NEW DATA"}' --accountId timurguvenkaya.testnet --deposit 1
+
+near view $ACCOUNT pending_payment '{"account_id": "app.timurguvenkaya.testnet", "token_id": null}'
+
+near call $ACCOUNT splitter_release '{"payee": "app.timurguvenkaya.testnet", "token_id": null}' --accountId timurguvenkaya.testnet --gas 50000000000000
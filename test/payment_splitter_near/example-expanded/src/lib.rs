@@ -0,0 +1,442 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    splitter_shares: near_sdk::collections::UnorderedMap<near_sdk::AccountId, u64>,
+    splitter_total_shares: u64,
+    splitter_received:
+        near_sdk::collections::LookupMap<Option<near_sdk::AccountId>, near_sdk::Balance>,
+    splitter_released: near_sdk::collections::LookupMap<
+        (Option<near_sdk::AccountId>, near_sdk::AccountId),
+        near_sdk::Balance,
+    >,
+}
+
+pub enum StorageKeyPaymentSplitter {
+    Shares,
+    Received,
+    Released,
+}
+
+impl StorageKeyPaymentSplitter {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyPaymentSplitter::Shares => "sps".to_string(),
+            StorageKeyPaymentSplitter::Received => "spr".to_string(),
+            StorageKeyPaymentSplitter::Released => "spl".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait PaymentSplitter {
+    fn splitter_deposit(&mut self);
+    fn splitter_release(
+        &mut self,
+        payee: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::Promise;
+    fn splitter_on_release(
+        &mut self,
+        payee: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::json_types::U128,
+    ) -> bool;
+    fn pending_payment(
+        &self,
+        account_id: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::json_types::U128;
+    fn released(
+        &self,
+        account_id: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::json_types::U128;
+    fn total_received(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128;
+    fn splitter_payees(&self) -> std::vec::Vec<(near_sdk::AccountId, u64)>;
+    fn splitter_total_shares(&self) -> u64;
+}
+
+#[near_bindgen]
+impl PaymentSplitter for StatusMessage {
+    #[payable]
+    fn splitter_deposit(&mut self) {
+        let amount = near_sdk::env::attached_deposit();
+        if amount == 0 {
+            near_sdk::env::panic(b"Requires attached deposit")
+        }
+        self.splitter_receive(None, amount);
+    }
+
+    fn splitter_release(
+        &mut self,
+        payee: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::Promise {
+        if self.splitter_shares.get(&payee).is_none() {
+            near_sdk::env::panic(format!("Account {} is not a payee", payee).as_bytes())
+        }
+        let amount = self.splitter_pending(&payee, &token_id);
+        if amount == 0 {
+            near_sdk::env::panic(format!("Account {} is not due any payment", payee).as_bytes())
+        }
+        let key = (token_id.clone(), payee.clone());
+        let released = self.splitter_released.get(&key).unwrap_or(0);
+        self.splitter_released.insert(&key, &(released + amount));
+        near_sdk::env::log(
+            format!(
+                "{} {} is released to {}",
+                amount,
+                token_id.as_deref().unwrap_or("yoctoNEAR"),
+                payee
+            )
+            .as_bytes(),
+        );
+        let
+        transfer = match & token_id
+        {
+            Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(b"ft_transfer".to_vec(),
+            near_sdk::serde_json::json!
+            ({
+                "receiver_id" : payee, "amount" : near_sdk::json_types::U128(amount), "memo" : "Payment",
+            }).to_string().into_bytes(), 1, Self::SPLITTER_GAS_FOR_FT_TRANSFER,), None => near_sdk::Promise::new(payee.clone()).transfer(amount),
+        };
+        transfer.then(near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(b"splitter_on_release".to_vec(),
+        near_sdk::serde_json::json!
+        ({
+            "payee" : payee, "token_id" : token_id, "amount" : near_sdk::json_types::U128(amount),
+        }).to_string().into_bytes(), 0, Self::SPLITTER_GAS_FOR_RELEASE_CALLBACK,))
+    }
+
+    #[private]
+    fn splitter_on_release(
+        &mut self,
+        payee: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::json_types::U128,
+    ) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            return true;
+        }
+        let key = (token_id, payee.clone());
+        let released = self.splitter_released.get(&key).unwrap_or(0);
+        self.splitter_released.insert(&key, &(released - amount.0));
+        near_sdk::env::log(format!("Release of {} to {} failed", amount.0, payee).as_bytes());
+        false
+    }
+
+    fn pending_payment(
+        &self,
+        account_id: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::json_types::U128 {
+        self.splitter_pending(&account_id, &token_id).into()
+    }
+
+    fn released(
+        &self,
+        account_id: near_sdk::AccountId,
+        token_id: Option<near_sdk::AccountId>,
+    ) -> near_sdk::json_types::U128 {
+        self.splitter_released
+            .get(&(token_id, account_id))
+            .unwrap_or(0)
+            .into()
+    }
+
+    fn total_received(&self, token_id: Option<near_sdk::AccountId>) -> near_sdk::json_types::U128 {
+        self.splitter_received.get(&token_id).unwrap_or(0).into()
+    }
+
+    fn splitter_payees(&self) -> std::vec::Vec<(near_sdk::AccountId, u64)> {
+        self.splitter_shares.to_vec()
+    }
+
+    fn splitter_total_shares(&self) -> u64 {
+        self.splitter_total_shares
+    }
+}
+
+impl StatusMessage {
+    pub const SPLITTER_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+    pub const SPLITTER_GAS_FOR_RELEASE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub fn setup_payment_splitter(
+        &mut self,
+        payees: std::vec::Vec<near_sdk::AccountId>,
+        shares: std::vec::Vec<u64>,
+    ) {
+        if self.splitter_total_shares > 0 {
+            near_sdk::env::panic(b"Payees are already set up")
+        }
+        if payees.is_empty() || payees.len() != shares.len() {
+            near_sdk::env::panic(b"Payees and shares must be non-empty and of the same length")
+        }
+        for (payee, shares) in payees.iter().zip(shares) {
+            if !near_sdk::env::is_valid_account_id(payee.as_bytes()) {
+                near_sdk::env::panic(format!("Account {} is invalid", payee).as_bytes())
+            }
+            if shares == 0 {
+                near_sdk::env::panic(format!("Shares of {} must be positive", payee).as_bytes())
+            }
+            if self.splitter_shares.insert(payee, &shares).is_some() {
+                near_sdk::env::panic(format!("Account {} is already a payee", payee).as_bytes())
+            }
+            self.splitter_total_shares = self
+                .splitter_total_shares
+                .checked_add(shares)
+                .unwrap_or_else(|| near_sdk::env::panic(b"Total shares overflow"));
+        }
+    }
+
+    pub fn splitter_receive(
+        &mut self,
+        token_id: Option<near_sdk::AccountId>,
+        amount: near_sdk::Balance,
+    ) {
+        let received = self
+            .splitter_received
+            .get(&token_id)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Received amount overflow"));
+        self.splitter_received.insert(&token_id, &received);
+    }
+
+    fn splitter_pending(
+        &self,
+        account_id: &near_sdk::AccountId,
+        token_id: &Option<near_sdk::AccountId>,
+    ) -> near_sdk::Balance {
+        let shares = match self.splitter_shares.get(account_id) {
+            Some(shares) => shares as near_sdk::Balance,
+            None => return 0,
+        };
+        let total_shares = self.splitter_total_shares as near_sdk::Balance;
+        let received = self.splitter_received.get(token_id).unwrap_or(0);
+        let due =
+            received / total_shares * shares + received % total_shares * shares / total_shares;
+        due - self
+            .splitter_released
+            .get(&(token_id.clone(), account_id.clone()))
+            .unwrap_or(0)
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(payees: Vec<AccountId>, shares: Vec<u64>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            splitter_shares: near_sdk::collections::UnorderedMap::new(
+                StorageKeyPaymentSplitter::Shares.into_bytes(),
+            ),
+            splitter_total_shares: 0,
+            splitter_received: near_sdk::collections::LookupMap::new(
+                StorageKeyPaymentSplitter::Received.into_bytes(),
+            ),
+            splitter_released: near_sdk::collections::LookupMap::new(
+                StorageKeyPaymentSplitter::Released.into_bytes(),
+            ),
+        };
+
+        constructor.setup_payment_splitter(payees, shares);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // The payment for the data is split among the payees
+    #[payable]
+    pub fn set_data(&mut self, data: String) {
+        self.splitter_receive(None, env::attached_deposit());
+
+        self.data = data;
+    }
+
+    #[allow(unused_variables)]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.splitter_receive(Some(env::predecessor_account_id()), amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn token() -> Option<AccountId> {
+        Some(String::from("token.testnet"))
+    }
+
+    fn pending(contract: &StatusMessage, account_id: &str, token_id: Option<AccountId>) -> u128 {
+        contract
+            .pending_payment(String::from(account_id), token_id)
+            .0
+    }
+
+    // bob gets 3/4 and carol 1/4
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![String::from("bob.testnet"), String::from("carol.testnet")],
+            vec![3, 1],
+        )
+    }
+
+    #[test]
+    fn should_split_payments() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        call("dave.testnet", 1003);
+        contract.splitter_deposit();
+
+        assert_eq!(contract.total_received(None), U128(2003));
+        assert_eq!(pending(&contract, "bob.testnet", None), 1502);
+        assert_eq!(pending(&contract, "carol.testnet", None), 500);
+        assert_eq!(pending(&contract, "dave.testnet", None), 0);
+    }
+
+    #[test]
+    fn should_split_tokens_separately() {
+        let mut contract = setup();
+
+        call("token.testnet", 0);
+        contract.ft_on_transfer(String::from("dave.testnet"), U128(400), String::new());
+
+        assert_eq!(pending(&contract, "carol.testnet", token()), 100);
+        assert_eq!(pending(&contract, "carol.testnet", None), 0);
+    }
+
+    #[test]
+    fn should_release() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("carol.testnet"), None);
+
+        assert_eq!(pending(&contract, "carol.testnet", None), 0);
+        assert_eq!(
+            contract.released(String::from("carol.testnet"), None),
+            U128(250)
+        );
+
+        call("dave.testnet", 2000);
+        contract.set_data(String::from("NEWER DATA"));
+
+        assert_eq!(pending(&contract, "carol.testnet", None), 500);
+        assert_eq!(pending(&contract, "bob.testnet", None), 2250);
+    }
+
+    #[test]
+    fn should_roll_back_failed_release() {
+        let mut contract = setup();
+
+        call("token.testnet", 0);
+        contract.ft_on_transfer(String::from("dave.testnet"), U128(400), String::new());
+
+        contract.splitter_release(String::from("bob.testnet"), token());
+
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.splitter_on_release(String::from("bob.testnet"), token(), U128(300)));
+        assert_eq!(pending(&contract, "bob.testnet", token()), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account carol.testnet is not due any payment"#)]
+    fn should_not_release_nothing() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("carol.testnet"), None);
+        contract.splitter_release(String::from("carol.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account dave.testnet is not a payee"#)]
+    fn should_not_release_to_others() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("dave.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is already a payee"#)]
+    fn should_not_set_up_duplicate_payees() {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![String::from("bob.testnet"), String::from("bob.testnet")],
+            vec![3, 1],
+        );
+    }
+}
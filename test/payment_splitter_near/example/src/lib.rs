@@ -0,0 +1,216 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(PaymentSplitter)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(payees: Vec<AccountId>, shares: Vec<u64>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "payment_splitter",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        constructor.setup_payment_splitter(payees, shares);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // The payment for the data is split among the payees
+    #[payable]
+    pub fn set_data(&mut self, data: String) {
+        self.splitter_receive(None, env::attached_deposit());
+
+        self.data = data;
+    }
+
+    #[allow(unused_variables)]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.splitter_receive(Some(env::predecessor_account_id()), amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn token() -> Option<AccountId> {
+        Some(String::from("token.testnet"))
+    }
+
+    fn pending(contract: &StatusMessage, account_id: &str, token_id: Option<AccountId>) -> u128 {
+        contract
+            .pending_payment(String::from(account_id), token_id)
+            .0
+    }
+
+    // bob gets 3/4 and carol 1/4
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![String::from("bob.testnet"), String::from("carol.testnet")],
+            vec![3, 1],
+        )
+    }
+
+    #[test]
+    fn should_split_payments() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        call("dave.testnet", 1003);
+        contract.splitter_deposit();
+
+        assert_eq!(contract.total_received(None), U128(2003));
+        assert_eq!(pending(&contract, "bob.testnet", None), 1502);
+        assert_eq!(pending(&contract, "carol.testnet", None), 500);
+        assert_eq!(pending(&contract, "dave.testnet", None), 0);
+    }
+
+    #[test]
+    fn should_split_tokens_separately() {
+        let mut contract = setup();
+
+        call("token.testnet", 0);
+        contract.ft_on_transfer(String::from("dave.testnet"), U128(400), String::new());
+
+        assert_eq!(pending(&contract, "carol.testnet", token()), 100);
+        assert_eq!(pending(&contract, "carol.testnet", None), 0);
+    }
+
+    #[test]
+    fn should_release() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("carol.testnet"), None);
+
+        assert_eq!(pending(&contract, "carol.testnet", None), 0);
+        assert_eq!(
+            contract.released(String::from("carol.testnet"), None),
+            U128(250)
+        );
+
+        call("dave.testnet", 2000);
+        contract.set_data(String::from("NEWER DATA"));
+
+        assert_eq!(pending(&contract, "carol.testnet", None), 500);
+        assert_eq!(pending(&contract, "bob.testnet", None), 2250);
+    }
+
+    #[test]
+    fn should_roll_back_failed_release() {
+        let mut contract = setup();
+
+        call("token.testnet", 0);
+        contract.ft_on_transfer(String::from("dave.testnet"), U128(400), String::new());
+
+        contract.splitter_release(String::from("bob.testnet"), token());
+
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.splitter_on_release(String::from("bob.testnet"), token(), U128(300)));
+        assert_eq!(pending(&contract, "bob.testnet", token()), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account carol.testnet is not due any payment"#)]
+    fn should_not_release_nothing() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("carol.testnet"), None);
+        contract.splitter_release(String::from("carol.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account dave.testnet is not a payee"#)]
+    fn should_not_release_to_others() {
+        let mut contract = setup();
+
+        call("dave.testnet", 1000);
+        contract.set_data(String::from("NEW DATA"));
+
+        contract.splitter_release(String::from("dave.testnet"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet is already a payee"#)]
+    fn should_not_set_up_duplicate_payees() {
+        call("alice.testnet", 0);
+
+        StatusMessage::new(
+            vec![String::from("bob.testnet"), String::from("bob.testnet")],
+            vec![3, 1],
+        );
+    }
+}
@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near call $ACCOUNT claim '{}' --accountId timurguvenkaya.testnet
+near call $ACCOUNT claim '{}' --accountId timurguvenkaya.testnet
+near call $ACCOUNT claim '{}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT remaining_quota '{"account_id": "timurguvenkaya.testnet", "action": "claim"}'
+
+# Should fail because the limit is exceeded
+near call $ACCOUNT claim '{}' --accountId timurguvenkaya.testnet
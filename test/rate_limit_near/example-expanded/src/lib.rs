@@ -0,0 +1,394 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    claims: u64,
+    rate_limit_configs: near_sdk::collections::LookupMap<String, RateLimitConfig>,
+    rate_limit_usages:
+        near_sdk::collections::LookupMap<(near_sdk::AccountId, String), RateLimitUsage>,
+}
+
+pub enum StorageKeyRateLimit {
+    Configs,
+    Usages,
+}
+
+impl StorageKeyRateLimit {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyRateLimit::Configs => "rlc".to_string(),
+            StorageKeyRateLimit::Usages => "rlu".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RateLimitPolicy {
+    Fixed,
+    Sliding,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RateLimitConfig {
+    pub max_calls: u64,
+    pub window_ns: u64,
+    pub policy: RateLimitPolicy,
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RateLimitUsage {
+    pub window_start: u64,
+    pub count: u64,
+    pub last_call: u64,
+    pub calls: std::vec::Vec<u64>,
+}
+
+pub trait RateLimit {
+    fn rate_limit(&mut self, action: &str, config: RateLimitConfig);
+    fn rate_limit_current(
+        &self,
+        account_id: &near_sdk::AccountId,
+        action: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitUsage;
+}
+
+impl RateLimit for StatusMessage {
+    fn rate_limit(&mut self, action: &str, config: RateLimitConfig) {
+        if self.rate_limit_configs.get(&action.to_string()).as_ref() != Some(&config) {
+            self.rate_limit_configs.insert(&action.to_string(), &config);
+        }
+        let account_id = near_sdk::env::predecessor_account_id();
+        let mut usage = self.rate_limit_current(&account_id, action, &config);
+        if usage.count >= config.max_calls {
+            near_sdk::env::panic(
+                format!(
+                    "Rate limit for {} is exceeded, try again at {}",
+                    action,
+                    usage.window_start.saturating_add(config.window_ns)
+                )
+                .as_bytes(),
+            )
+        }
+        let now = near_sdk::env::block_timestamp();
+        usage.count += 1;
+        usage.last_call = now;
+        if config.policy == RateLimitPolicy::Sliding {
+            usage.calls.push(now);
+        }
+        self.rate_limit_usages
+            .insert(&(account_id, action.to_string()), &usage);
+    }
+
+    fn rate_limit_current(
+        &self,
+        account_id: &near_sdk::AccountId,
+        action: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitUsage {
+        let now = near_sdk::env::block_timestamp();
+        let mut usage = self
+            .rate_limit_usages
+            .get(&(account_id.clone(), action.to_string()))
+            .unwrap_or(RateLimitUsage {
+                window_start: now,
+                count: 0,
+                last_call: 0,
+                calls: std::vec::Vec::new(),
+            });
+        match config.policy {
+            RateLimitPolicy::Fixed => {
+                usage.calls.clear();
+                if now >= usage.window_start.saturating_add(config.window_ns) {
+                    usage.window_start = now;
+                    usage.count = 0;
+                }
+            }
+            RateLimitPolicy::Sliding => {
+                usage
+                    .calls
+                    .retain(|call| now < call.saturating_add(config.window_ns));
+                usage.count = usage.calls.len() as u64;
+                usage.window_start = usage.calls.first().copied().unwrap_or(now);
+            }
+        }
+        usage
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn rate_limit_config(&self, action: String) -> Option<RateLimitConfig> {
+        self.rate_limit_configs.get(&action)
+    }
+
+    pub fn rate_limit_usage(
+        &self,
+        account_id: near_sdk::AccountId,
+        action: String,
+    ) -> Option<RateLimitUsage> {
+        let config = self.rate_limit_configs.get(&action)?;
+        Some(self.rate_limit_current(&account_id, &action, &config))
+    }
+
+    pub fn remaining_quota(&self, account_id: near_sdk::AccountId, action: String) -> Option<u64> {
+        let config = self.rate_limit_configs.get(&action)?;
+        let usage = self.rate_limit_current(&account_id, &action, &config);
+        Some(config.max_calls.saturating_sub(usage.count))
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: data,
+            claims: 0,
+            rate_limit_configs: near_sdk::collections::LookupMap::new(
+                StorageKeyRateLimit::Configs.into_bytes(),
+            ),
+            rate_limit_usages: near_sdk::collections::LookupMap::new(
+                StorageKeyRateLimit::Usages.into_bytes(),
+            ),
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn claim(&mut self) -> u64 {
+        self.rate_limit(
+            "claim",
+            RateLimitConfig {
+                max_calls: 3u64,
+                window_ns: 3600000000000u64,
+                policy: RateLimitPolicy::Fixed,
+            },
+        );
+        self.claims += 1;
+        self.claims
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        self.rate_limit(
+            "set_data",
+            RateLimitConfig {
+                max_calls: 2u64,
+                window_ns: 60000000000u64,
+                policy: RateLimitPolicy::Sliding,
+            },
+        );
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(String::from("SOME DATA"))
+    }
+
+    #[test]
+    fn should_report_remaining_quota() {
+        let mut contract = setup();
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            None
+        );
+
+        at("bob.testnet", 10);
+
+        contract.claim();
+
+        assert_eq!(
+            contract.rate_limit_config(String::from("claim")),
+            Some(RateLimitConfig {
+                max_calls: 3,
+                window_ns: 3600 * SECOND,
+                policy: RateLimitPolicy::Fixed,
+            })
+        );
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            Some(2)
+        );
+        assert_eq!(
+            contract.remaining_quota(String::from("carol.testnet"), String::from("claim")),
+            Some(3)
+        );
+
+        let usage = contract
+            .rate_limit_usage(String::from("bob.testnet"), String::from("claim"))
+            .unwrap();
+
+        assert_eq!(usage.window_start, 10 * SECOND);
+        assert_eq!(usage.count, 1);
+        assert_eq!(usage.last_call, 10 * SECOND);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Rate limit for claim is exceeded, try again at 3600000000000"#)]
+    fn should_limit_calls_in_fixed_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+
+        contract.claim();
+        contract.claim();
+        contract.claim();
+
+        at("bob.testnet", 3599);
+
+        contract.claim();
+    }
+
+    #[test]
+    fn should_reset_fixed_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+
+        contract.claim();
+        contract.claim();
+        contract.claim();
+
+        // Other accounts have their own quota
+        at("carol.testnet", 1);
+
+        contract.claim();
+
+        at("bob.testnet", 3600);
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            Some(3)
+        );
+        assert_eq!(contract.claim(), 5);
+    }
+
+    #[test]
+    fn should_slide_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+        contract.set_data(String::from("A"));
+
+        at("bob.testnet", 30);
+        contract.set_data(String::from("B"));
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("set_data")),
+            Some(0)
+        );
+
+        // Only the call at 0 has left the window
+        at("bob.testnet", 60);
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("set_data")),
+            Some(1)
+        );
+
+        contract.set_data(String::from("C"));
+
+        assert_eq!(contract.get_data(), String::from("C"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Rate limit for set_data is exceeded, try again at 90000000000"#)]
+    fn should_limit_calls_in_sliding_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+        contract.set_data(String::from("A"));
+
+        at("bob.testnet", 30);
+        contract.set_data(String::from("B"));
+
+        at("bob.testnet", 60);
+        contract.set_data(String::from("C"));
+
+        at("bob.testnet", 89);
+        contract.set_data(String::from("D"));
+    }
+}
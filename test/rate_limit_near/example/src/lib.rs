@@ -0,0 +1,214 @@
+use near_macros::{init_macro, rate_limit, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(RateLimit)]
+pub struct StatusMessage {
+    data: String,
+    claims: u64,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "rate_limit",
+            Self {
+                data: data,
+                claims: 0
+            }
+        ]);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    #[rate_limit(action = "claim", max = 3, window_secs = 3600)]
+    pub fn claim(&mut self) -> u64 {
+        self.claims += 1;
+        self.claims
+    }
+
+    #[rate_limit(action = "set_data", max = 2, window_secs = 60, policy = "sliding")]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(String::from("SOME DATA"))
+    }
+
+    #[test]
+    fn should_report_remaining_quota() {
+        let mut contract = setup();
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            None
+        );
+
+        at("bob.testnet", 10);
+
+        contract.claim();
+
+        assert_eq!(
+            contract.rate_limit_config(String::from("claim")),
+            Some(RateLimitConfig {
+                max_calls: 3,
+                window_ns: 3600 * SECOND,
+                policy: RateLimitPolicy::Fixed,
+            })
+        );
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            Some(2)
+        );
+        assert_eq!(
+            contract.remaining_quota(String::from("carol.testnet"), String::from("claim")),
+            Some(3)
+        );
+
+        let usage = contract
+            .rate_limit_usage(String::from("bob.testnet"), String::from("claim"))
+            .unwrap();
+
+        assert_eq!(usage.window_start, 10 * SECOND);
+        assert_eq!(usage.count, 1);
+        assert_eq!(usage.last_call, 10 * SECOND);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Rate limit for claim is exceeded, try again at 3600000000000"#)]
+    fn should_limit_calls_in_fixed_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+
+        contract.claim();
+        contract.claim();
+        contract.claim();
+
+        at("bob.testnet", 3599);
+
+        contract.claim();
+    }
+
+    #[test]
+    fn should_reset_fixed_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+
+        contract.claim();
+        contract.claim();
+        contract.claim();
+
+        // Other accounts have their own quota
+        at("carol.testnet", 1);
+
+        contract.claim();
+
+        at("bob.testnet", 3600);
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("claim")),
+            Some(3)
+        );
+        assert_eq!(contract.claim(), 5);
+    }
+
+    #[test]
+    fn should_slide_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+        contract.set_data(String::from("A"));
+
+        at("bob.testnet", 30);
+        contract.set_data(String::from("B"));
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("set_data")),
+            Some(0)
+        );
+
+        // Only the call at 0 has left the window
+        at("bob.testnet", 60);
+
+        assert_eq!(
+            contract.remaining_quota(String::from("bob.testnet"), String::from("set_data")),
+            Some(1)
+        );
+
+        contract.set_data(String::from("C"));
+
+        assert_eq!(contract.get_data(), String::from("C"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Rate limit for set_data is exceeded, try again at 90000000000"#)]
+    fn should_limit_calls_in_sliding_window() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0);
+        contract.set_data(String::from("A"));
+
+        at("bob.testnet", 30);
+        contract.set_data(String::from("B"));
+
+        at("bob.testnet", 60);
+        contract.set_data(String::from("C"));
+
+        at("bob.testnet", 89);
+        contract.set_data(String::from("D"));
+    }
+}
@@ -0,0 +1,271 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, Promise};
+
+setup_alloc!();
+
+const GAS_FOR_CALL: u64 = 10_000_000_000_000;
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    oracle: AccountId,
+    reentrancy_lock: bool,
+    reentrancy_locks: near_sdk::collections::LookupSet<String>,
+}
+
+pub enum StorageKeyReentrancyGuard {
+    Locks,
+}
+
+impl StorageKeyReentrancyGuard {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyReentrancyGuard::Locks => "rlk".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait ReentrancyGuard {
+    fn acquire_lock(&mut self);
+    fn release_lock(&mut self);
+    fn is_locked(&self) -> bool;
+    fn acquire_lock_for(&mut self, key: &str);
+    fn release_lock_for(&mut self, key: &str);
+    fn is_locked_for(&self, key: &str) -> bool;
+}
+
+impl ReentrancyGuard for StatusMessage {
+    fn acquire_lock(&mut self) {
+        if self.reentrancy_lock {
+            near_sdk::env::panic(b"Reentrant call")
+        }
+        self.reentrancy_lock = true;
+    }
+
+    fn release_lock(&mut self) {
+        self.reentrancy_lock = false;
+    }
+
+    fn is_locked(&self) -> bool {
+        self.reentrancy_lock
+    }
+
+    fn acquire_lock_for(&mut self, key: &str) {
+        if !self.reentrancy_locks.insert(&key.to_string()) {
+            near_sdk::env::panic(format!("Reentrant call for {}", key).as_bytes())
+        }
+    }
+
+    fn release_lock_for(&mut self, key: &str) {
+        self.reentrancy_locks.remove(&key.to_string());
+    }
+
+    fn is_locked_for(&self, key: &str) -> bool {
+        self.reentrancy_locks.contains(&key.to_string())
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(data: String, oracle: AccountId) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: data,
+            oracle: oracle,
+            reentrancy_lock: false,
+            reentrancy_locks: near_sdk::collections::LookupSet::new(
+                StorageKeyReentrancyGuard::Locks.into_bytes(),
+            ),
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn sync_data(&mut self) -> Promise {
+        self.acquire_lock();
+        Promise::new(self.oracle.clone())
+            .function_call(b"get_data".to_vec(), vec![], 0, GAS_FOR_CALL)
+            .then(Promise::new(env::current_account_id()).function_call(
+                b"on_sync_data".to_vec(),
+                vec![],
+                0,
+                GAS_FOR_CALL,
+            ))
+    }
+
+    #[private]
+    pub fn on_sync_data(&mut self) {
+        self.release_lock();
+        if let near_sdk::PromiseResult::Successful(data) = env::promise_result(0) {
+            self.data = String::from_utf8(data).unwrap_or_default();
+        }
+    }
+
+    pub fn report(&mut self) -> Promise {
+        self.acquire_lock_for(&(env::predecessor_account_id()).to_string());
+        let account_id = env::predecessor_account_id();
+        Promise::new(self.oracle.clone())
+            .function_call(b"report".to_vec(), vec![], 0, GAS_FOR_CALL)
+            .then(Promise::new(env::current_account_id()).function_call(
+                b"on_report".to_vec(),
+                format!("{{\"account_id\": \"{}\"}}", account_id).into_bytes(),
+                0,
+                GAS_FOR_CALL,
+            ))
+    }
+
+    #[private]
+    pub fn on_report(&mut self, account_id: AccountId) {
+        self.release_lock_for(&(account_id).to_string());
+        env::log(format!("Report of {} is processed", account_id).as_bytes());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseResult, VMContext};
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    #[test]
+    fn lock_is_released_in_callback() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract =
+            StatusMessage::new(String::from("SOME DATA"), String::from("oracle.testnet"));
+
+        contract.sync_data();
+
+        assert_eq!(contract.is_locked(), true);
+
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Successful(b"NEW DATA".to_vec())]
+        );
+
+        contract.on_sync_data();
+
+        assert_eq!(contract.is_locked(), false);
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+
+        testing_env!(get_context(String::from("timurguvenkaya.testnet"), 0));
+
+        contract.sync_data();
+
+        assert_eq!(contract.is_locked(), true);
+    }
+
+    #[test]
+    fn lock_is_released_when_call_fails() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract =
+            StatusMessage::new(String::from("SOME DATA"), String::from("oracle.testnet"));
+
+        contract.sync_data();
+
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        contract.on_sync_data();
+
+        assert_eq!(contract.is_locked(), false);
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Reentrant call"#)]
+    fn second_call_before_callback_fails() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract =
+            StatusMessage::new(String::from("SOME DATA"), String::from("oracle.testnet"));
+
+        contract.sync_data();
+        contract.sync_data();
+    }
+
+    #[test]
+    fn per_key_lock_works() {
+        let context = get_context(String::from("mike.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract =
+            StatusMessage::new(String::from("SOME DATA"), String::from("oracle.testnet"));
+
+        contract.report();
+
+        assert_eq!(contract.is_locked_for("mike.testnet"), true);
+        assert_eq!(contract.is_locked_for("jane.testnet"), false);
+
+        testing_env!(get_context(String::from("jane.testnet"), 0));
+
+        contract.report();
+
+        assert_eq!(contract.is_locked_for("jane.testnet"), true);
+
+        testing_env!(get_context(String::from("alice.testnet"), 0));
+
+        contract.on_report(String::from("mike.testnet"));
+
+        assert_eq!(contract.is_locked_for("mike.testnet"), false);
+        assert_eq!(contract.is_locked_for("jane.testnet"), true);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Reentrant call for mike.testnet"#)]
+    fn per_key_second_call_fails() {
+        let context = get_context(String::from("mike.testnet"), 0);
+        testing_env!(context);
+
+        let mut contract =
+            StatusMessage::new(String::from("SOME DATA"), String::from("oracle.testnet"));
+
+        contract.report();
+        contract.report();
+    }
+}
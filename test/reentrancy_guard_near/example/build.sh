@@ -0,0 +1,11 @@
+#!/bin/bash
+cargo fmt --all
+RUSTFLAGS='-C link-arg=-s' cargo build --all --target wasm32-unknown-unknown --release
+if [ ! -d "out" ]; then
+    mkdir -p "out"
+fi
+if [ ! -d "res" ]; then
+    mkdir -p "res"
+fi
+cp target/wasm32-unknown-unknown/release/*.wasm ./res/
+cp target/wasm32-unknown-unknown/release/status_message.wasm ./out/main.wasm
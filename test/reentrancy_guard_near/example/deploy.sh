@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"data": "SOME_DATA", "oracle": "oracle.timurguvenkaya.testnet"}' --accountId $ACCOUNT
+
+near call $ACCOUNT sync_data '{}' --accountId timurguvenkaya.testnet --gas 100000000000000
+
+near view $ACCOUNT get_data
+
+# Should fail because on_report is a private callback
+near call $ACCOUNT on_report '{"account_id": "timurguvenkaya.testnet"}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT report '{}' --accountId timurguvenkaya.testnet --gas 100000000000000
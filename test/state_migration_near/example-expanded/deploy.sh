@@ -0,0 +1,18 @@
+#!/bin/bash
+# Usage: ./deploy.sh [wasm of the previous version, the Pausable example by default]
+# Deploys the previous version first, then upgrades it in place and migrates the state
+PREVIOUS=${1:-../../pausable_near/example/res/status_message.wasm}
+
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy $PREVIOUS | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"data": "SOME_DATA"}' --accountId $ACCOUNT
+
+near deploy $ACCOUNT ./res/status_message.wasm --initFunction migrate --initArgs '{}'
+
+near view $ACCOUNT state_version
+
+near view $ACCOUNT owner
+
+near view $ACCOUNT get_data
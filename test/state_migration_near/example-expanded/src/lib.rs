@@ -0,0 +1,505 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+
+setup_alloc!();
+
+// The previous version of this contract is the Pausable example. This version adds Ownable,
+// so `migrate` reads the old layout and sets the owner given below
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    pause_status: bool,
+    owner: near_sdk::AccountId,
+    pending_owner: Option<near_sdk::AccountId>,
+    state_version: u16,
+}
+
+pub enum StorageKeyPausable {
+    Info,
+    Features,
+    FeatureSet,
+}
+
+impl StorageKeyPausable {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyPausable::Info => "pai".to_string(),
+            StorageKeyPausable::Features => "paf".to_string(),
+            StorageKeyPausable::FeatureSet => "pas".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseInfo {
+    pub paused_by: near_sdk::AccountId,
+    pub paused_at: u64,
+    pub reason: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+pub trait Pausable {
+    fn toggle_pause(&mut self);
+    fn pause(&mut self, reason: Option<String>);
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>);
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>);
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>);
+    fn unpause(&mut self);
+    fn is_paused(&self) -> bool;
+    fn when_not_paused(&self);
+    fn pause_feature(&mut self, feature: &str);
+    fn unpause_feature(&mut self, feature: &str);
+    fn is_feature_paused(&self, feature: &str) -> bool;
+    fn when_not_paused_feature(&self, feature: &str);
+    fn when_paused(&self);
+    fn when_paused_feature(&self, feature: &str);
+}
+
+impl Pausable for StatusMessage {
+    fn toggle_pause(&mut self) {
+        if !self.is_paused() {
+            Pausable::pause(self, None)
+        } else {
+            Pausable::unpause(self)
+        }
+    }
+
+    fn pause(&mut self, reason: Option<String>) {
+        self.pause_with_expiry(reason, None)
+    }
+
+    fn pause_for(&mut self, duration_ns: u64, reason: Option<String>) {
+        let expires_at = near_sdk::env::block_timestamp()
+            .checked_add(duration_ns)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Pause duration overflow"));
+        Pausable::pause_until(self, expires_at, reason)
+    }
+
+    fn pause_until(&mut self, timestamp: u64, reason: Option<String>) {
+        if timestamp <= near_sdk::env::block_timestamp() {
+            near_sdk::env::panic(b"Pause deadline must be in the future")
+        }
+        self.pause_with_expiry(reason, Some(timestamp))
+    }
+
+    fn pause_with_expiry(&mut self, reason: Option<String>, expires_at: Option<u64>) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"The system is already paused")
+        }
+        let info = PauseInfo {
+            paused_by: near_sdk::env::predecessor_account_id(),
+            paused_at: near_sdk::env::block_timestamp(),
+            reason,
+            expires_at,
+        };
+        near_sdk::env::log(
+            format!(
+                "The system is paused by {} at {} until {}. Reason: {}",
+                info.paused_by,
+                info.paused_at,
+                info.expires_at
+                    .map_or("unpaused".to_string(), |t| t.to_string()),
+                info.reason.as_deref().unwrap_or("not specified")
+            )
+            .as_bytes(),
+        );
+        self.pause_status = true;
+        Self::pause_record().set(&info);
+    }
+
+    fn unpause(&mut self) {
+        if !self.pause_status {
+            near_sdk::env::panic(b"The system is not paused")
+        }
+        self.pause_status = false;
+        Self::pause_record().remove();
+        near_sdk::env::log(
+            format!(
+                "The system is unpaused by {} at {}",
+                near_sdk::env::predecessor_account_id(),
+                near_sdk::env::block_timestamp()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn is_paused(&self) -> bool {
+        if !self.pause_status {
+            return false;
+        }
+        match Self::pause_record().get().and_then(|info| info.expires_at) {
+            Some(expires_at) => near_sdk::env::block_timestamp() < expires_at,
+            None => true,
+        }
+    }
+
+    fn when_not_paused(&self) {
+        if self.is_paused() {
+            near_sdk::env::panic(b"Function is paused")
+        }
+    }
+
+    fn pause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.insert(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is paused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn unpause_feature(&mut self, feature: &str) {
+        let mut features = Self::paused_feature_set();
+        if features.remove(&feature.to_string()) {
+            Self::save_paused_feature_set(&features);
+            near_sdk::env::log(
+                format!(
+                    "Feature {} is unpaused by {} at {}",
+                    feature,
+                    near_sdk::env::predecessor_account_id(),
+                    near_sdk::env::block_timestamp()
+                )
+                .as_bytes(),
+            )
+        }
+    }
+
+    fn is_feature_paused(&self, feature: &str) -> bool {
+        self.is_paused() || Self::paused_feature_set().contains(&feature.to_string())
+    }
+
+    fn when_not_paused_feature(&self, feature: &str) {
+        self.when_not_paused();
+        if Self::paused_feature_set().contains(&feature.to_string()) {
+            near_sdk::env::panic(format!("Feature {} is paused", feature).as_bytes())
+        }
+    }
+
+    fn when_paused(&self) {
+        if !self.is_paused() {
+            near_sdk::env::panic(b"Function is not paused")
+        }
+    }
+
+    fn when_paused_feature(&self, feature: &str) {
+        if !self.is_feature_paused(feature) {
+            near_sdk::env::panic(format!("Feature {} is not paused", feature).as_bytes())
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn pause_info(&self) -> Option<PauseInfo> {
+        if self.is_paused() {
+            Self::pause_record().get()
+        } else {
+            None
+        }
+    }
+
+    pub fn paused_features(&self) -> Vec<String> {
+        Self::paused_feature_set().to_vec()
+    }
+}
+
+impl StatusMessage {
+    fn pause_record() -> near_sdk::collections::LazyOption<PauseInfo> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Info.into_bytes(), None)
+    }
+
+    fn paused_feature_set() -> near_sdk::collections::UnorderedSet<String> {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .get()
+            .unwrap_or_else(|| {
+                near_sdk::collections::UnorderedSet::new(
+                    StorageKeyPausable::FeatureSet.into_bytes(),
+                )
+            })
+    }
+
+    fn save_paused_feature_set(features: &near_sdk::collections::UnorderedSet<String>) {
+        near_sdk::collections::LazyOption::new(StorageKeyPausable::Features.into_bytes(), None)
+            .set(features);
+    }
+}
+
+pub trait Ownable {
+    fn owner(&self) -> near_sdk::AccountId;
+    fn pending_owner(&self) -> Option<near_sdk::AccountId>;
+    fn assert_owner(&self);
+    fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId);
+    fn accept_ownership(&mut self);
+    fn renounce_ownership(&mut self);
+}
+
+#[near_bindgen]
+impl Ownable for StatusMessage {
+    fn owner(&self) -> near_sdk::AccountId {
+        self.owner.clone()
+    }
+
+    fn pending_owner(&self) -> Option<near_sdk::AccountId> {
+        self.pending_owner.clone()
+    }
+
+    fn assert_owner(&self) {
+        let caller = near_sdk::env::predecessor_account_id();
+        if self.owner.is_empty() || caller != self.owner {
+            near_sdk::env::panic(format!("Account {} is not the owner", caller).as_bytes())
+        }
+    }
+
+    fn transfer_ownership(&mut self, new_owner: near_sdk::AccountId) {
+        self.assert_owner();
+        if !near_sdk::env::is_valid_account_id(new_owner.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", new_owner).as_bytes())
+        }
+        near_sdk::env::log(
+            format!(
+                "Ownership transfer from {} to {} is started",
+                self.owner, new_owner
+            )
+            .as_bytes(),
+        );
+        self.pending_owner = Some(new_owner);
+    }
+
+    fn accept_ownership(&mut self) {
+        let caller = near_sdk::env::predecessor_account_id();
+        if self.pending_owner.as_ref() != Some(&caller) {
+            near_sdk::env::panic(format!("Account {} is not the pending owner", caller).as_bytes())
+        }
+        near_sdk::env::log(
+            format!("Ownership is transferred from {} to {}", self.owner, caller).as_bytes(),
+        );
+        self.owner = caller;
+        self.pending_owner = None;
+    }
+
+    fn renounce_ownership(&mut self) {
+        self.assert_owner();
+        near_sdk::env::log(format!("Ownership is renounced by {}", self.owner).as_bytes());
+        self.owner = String::new();
+        self.pending_owner = None;
+    }
+}
+
+impl StatusMessage {
+    pub const STATE_VERSION: u16 = 2u16;
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    pub fn state_version(&self) -> u16 {
+        self.state_version
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize)]
+pub struct OldStatusMessage {
+    data: String,
+    pause_status: bool,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate() -> Self {
+        let old: OldStatusMessage = near_sdk::env::state_read()
+            .unwrap_or_else(|| near_sdk::env::panic(b"No state to migrate"));
+        near_sdk::env::log(
+            format!("State is migrated to version {}", Self::STATE_VERSION).as_bytes(),
+        );
+        Self {
+            data: old.data,
+            pause_status: old.pause_status,
+            owner: "timurguvenkaya.testnet".to_string(),
+            pending_owner: None,
+            state_version: Self::STATE_VERSION,
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            data: data,
+            pause_status: false,
+            owner: near_sdk::env::predecessor_account_id(),
+            pending_owner: None,
+            state_version: Self::STATE_VERSION,
+        };
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.when_not_paused();
+        self.data.clone()
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        self.assert_owner();
+        self.data = data;
+    }
+
+    pub fn pub_unpause(&mut self) {
+        self.assert_owner();
+        self.unpause()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::borsh::BorshSerialize;
+    use near_sdk::collections::{LazyOption, UnorderedSet};
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    // Layout of the deployed Pausable example
+    #[derive(BorshSerialize)]
+    struct StatusMessageV1 {
+        data: String,
+        pause_status: bool,
+    }
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn write_v1_state(paused: bool) {
+        // The pause record and the paused features are stored under their own keys
+        if paused {
+            LazyOption::new(
+                StorageKeyPausable::Info.into_bytes(),
+                Some(&PauseInfo {
+                    paused_by: String::from("timurguvenkaya.testnet"),
+                    paused_at: 1_000,
+                    reason: Some(String::from("Upgrade")),
+                    expires_at: None,
+                }),
+            );
+        }
+
+        let mut paused_features = UnorderedSet::new(StorageKeyPausable::FeatureSet.into_bytes());
+        paused_features.insert(&String::from("transfer"));
+
+        LazyOption::new(
+            StorageKeyPausable::Features.into_bytes(),
+            Some(&paused_features),
+        );
+
+        env::state_write(&StatusMessageV1 {
+            data: String::from("SOME DATA"),
+            pause_status: paused,
+        });
+    }
+
+    #[test]
+    fn new_records_state_version() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let contract = StatusMessage::new(String::from("SOME DATA"));
+
+        assert_eq!(contract.state_version(), 2);
+        assert_eq!(StatusMessage::STATE_VERSION, 2);
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+    }
+
+    #[test]
+    fn should_migrate_previous_state() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        write_v1_state(true);
+
+        let contract = StatusMessage::migrate();
+
+        assert_eq!(contract.state_version(), 2);
+        assert_eq!(contract.data, String::from("SOME DATA"));
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(
+            contract.pause_info().unwrap().reason,
+            Some(String::from("Upgrade"))
+        );
+        assert_eq!(contract.paused_features(), vec![String::from("transfer")]);
+
+        // Ownable was not part of the previous layout, so the owner option becomes the owner
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+        assert_eq!(contract.pending_owner(), None);
+    }
+
+    #[test]
+    fn migrated_contract_keeps_working() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        write_v1_state(true);
+
+        let mut contract = StatusMessage::migrate();
+
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        contract.pub_unpause();
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"No state to migrate"#)]
+    fn should_not_migrate_without_state() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::migrate();
+    }
+}
@@ -0,0 +1,182 @@
+use near_macros::{init_macro, require, when_not_paused};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, PanicOnDefault};
+
+setup_alloc!();
+
+// The previous version of this contract is the Pausable example. This version adds Ownable,
+// so `migrate` reads the old layout and sets the owner given below
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(
+    Pausable,
+    Ownable,
+    StateMigration(
+        version = 2,
+        old_fields = "data: String, pause_status: bool",
+        owner = "timurguvenkaya.testnet"
+    )
+)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "pausable",
+            "ownable",
+            "state_migration",
+            Self { data: data }
+        ]);
+
+        constructor
+    }
+
+    #[when_not_paused]
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        self.assert_owner();
+        self.data = data;
+    }
+
+    pub fn pub_unpause(&mut self) {
+        self.assert_owner();
+        self.unpause()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::borsh::BorshSerialize;
+    use near_sdk::collections::{LazyOption, UnorderedSet};
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    // Layout of the deployed Pausable example
+    #[derive(BorshSerialize)]
+    struct StatusMessageV1 {
+        data: String,
+        pause_status: bool,
+    }
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn write_v1_state(paused: bool) {
+        // The pause record and the paused features are stored under their own keys
+        if paused {
+            LazyOption::new(
+                StorageKeyPausable::Info.into_bytes(),
+                Some(&PauseInfo {
+                    paused_by: String::from("timurguvenkaya.testnet"),
+                    paused_at: 1_000,
+                    reason: Some(String::from("Upgrade")),
+                    expires_at: None,
+                }),
+            );
+        }
+
+        let mut paused_features = UnorderedSet::new(StorageKeyPausable::FeatureSet.into_bytes());
+        paused_features.insert(&String::from("transfer"));
+
+        LazyOption::new(
+            StorageKeyPausable::Features.into_bytes(),
+            Some(&paused_features),
+        );
+
+        env::state_write(&StatusMessageV1 {
+            data: String::from("SOME DATA"),
+            pause_status: paused,
+        });
+    }
+
+    #[test]
+    fn new_records_state_version() {
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        let contract = StatusMessage::new(String::from("SOME DATA"));
+
+        assert_eq!(contract.state_version(), 2);
+        assert_eq!(StatusMessage::STATE_VERSION, 2);
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+    }
+
+    #[test]
+    fn should_migrate_previous_state() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        write_v1_state(true);
+
+        let contract = StatusMessage::migrate();
+
+        assert_eq!(contract.state_version(), 2);
+        assert_eq!(contract.data, String::from("SOME DATA"));
+        assert_eq!(contract.is_paused(), true);
+        assert_eq!(
+            contract.pause_info().unwrap().reason,
+            Some(String::from("Upgrade"))
+        );
+        assert_eq!(contract.paused_features(), vec![String::from("transfer")]);
+
+        // Ownable was not part of the previous layout, so the owner option becomes the owner
+        assert_eq!(contract.owner(), String::from("timurguvenkaya.testnet"));
+        assert_eq!(contract.pending_owner(), None);
+    }
+
+    #[test]
+    fn migrated_contract_keeps_working() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        write_v1_state(true);
+
+        let mut contract = StatusMessage::migrate();
+
+        let context = get_context(String::from("timurguvenkaya.testnet"), 0);
+        testing_env!(context);
+
+        contract.pub_unpause();
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"No state to migrate"#)]
+    fn should_not_migrate_without_state() {
+        let context = get_context(String::from("alice.testnet"), 0);
+        testing_env!(context);
+
+        StatusMessage::migrate();
+    }
+}
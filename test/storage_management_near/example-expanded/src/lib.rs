@@ -0,0 +1,482 @@
+use near_sdk::borsh;
+use near_sdk::collections::LookupMap;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    messages: LookupMap<AccountId, String>,
+    storage_accounts: near_sdk::collections::LookupMap<near_sdk::AccountId, StorageAccount>,
+    storage_min_bytes: near_sdk::StorageUsage,
+    storage_max_bytes: Option<near_sdk::StorageUsage>,
+}
+
+pub enum StorageKeyStorageManagement {
+    Accounts,
+}
+
+impl StorageKeyStorageManagement {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyStorageManagement::Accounts => "stm".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize, Clone, Debug, PartialEq,
+)]
+pub struct StorageAccount {
+    pub total: near_sdk::Balance,
+    pub used_bytes: near_sdk::StorageUsage,
+}
+
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: near_sdk::json_types::U128,
+    pub available: near_sdk::json_types::U128,
+}
+
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: near_sdk::json_types::U128,
+    pub max: Option<near_sdk::json_types::U128>,
+}
+
+pub trait StorageManagement {
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<near_sdk::AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance;
+    fn storage_withdraw(&mut self, amount: Option<near_sdk::json_types::U128>) -> StorageBalance;
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool;
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+    fn storage_balance_of(&self, account_id: near_sdk::AccountId) -> Option<StorageBalance>;
+}
+
+#[near_bindgen]
+impl StorageManagement for StatusMessage {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<near_sdk::AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = near_sdk::env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(near_sdk::env::predecessor_account_id);
+        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+        }
+        let min = self.storage_min_balance();
+        let (mut account, deposit) = match self.storage_accounts.get(&account_id) {
+            Some(account) if registration_only.unwrap_or(false) => (account, 0),
+            Some(account) => (account, amount),
+            None => {
+                if amount < min {
+                    near_sdk::env::panic(
+                        format!(
+                            "The attached deposit is less than the minimum storage balance {}",
+                            min
+                        )
+                        .as_bytes(),
+                    )
+                }
+                near_sdk::env::log(format!("Account {} is registered", account_id).as_bytes());
+                let account = StorageAccount {
+                    total: 0,
+                    used_bytes: 0,
+                };
+                if registration_only.unwrap_or(false) {
+                    (account, min)
+                } else {
+                    (account, amount)
+                }
+            }
+        };
+        let deposit = match self.storage_max_balance() {
+            Some(max) => deposit.min(max.saturating_sub(account.total)),
+            None => deposit,
+        };
+        account.total += deposit;
+        self.storage_accounts.insert(&account_id, &account);
+        let refund = amount - deposit;
+        if refund > 0 {
+            near_sdk::Promise::new(near_sdk::env::predecessor_account_id()).transfer(refund);
+        }
+        self.storage_to_balance(&account)
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<near_sdk::json_types::U128>) -> StorageBalance {
+        Self::storage_assert_one_yocto();
+        let account_id = near_sdk::env::predecessor_account_id();
+        let mut account = self.storage_account(&account_id);
+        let available = self.storage_available(&account);
+        let amount = amount.map_or(available, |amount| amount.0);
+        if amount > available {
+            near_sdk::env::panic(
+                format!(
+                    "The amount is greater than the available storage balance {}",
+                    available
+                )
+                .as_bytes(),
+            )
+        }
+        account.total -= amount;
+        self.storage_accounts.insert(&account_id, &account);
+        if amount > 0 {
+            near_sdk::Promise::new(account_id).transfer(amount);
+        }
+        self.storage_to_balance(&account)
+    }
+
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        Self::storage_assert_one_yocto();
+        let account_id = near_sdk::env::predecessor_account_id();
+        let account = match self.storage_accounts.get(&account_id) {
+            Some(account) => account,
+            None => {
+                near_sdk::env::log(format!("Account {} is not registered", account_id).as_bytes());
+                return false;
+            }
+        };
+        if account.used_bytes > 0 && !force.unwrap_or(false) {
+            near_sdk::env::panic(
+                format!
+            ("Account {} still uses {} bytes of storage, unregister with force to give them up",
+            account_id, account.used_bytes)
+                .as_bytes(),
+            )
+        }
+        let refund = account.total.saturating_sub(
+            account.used_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost(),
+        );
+        self.storage_accounts.remove(&account_id);
+        if refund > 0 {
+            near_sdk::Promise::new(account_id.clone()).transfer(refund);
+        }
+        near_sdk::env::log(format!("Account {} is unregistered", account_id).as_bytes());
+        true
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: self.storage_min_balance().into(),
+            max: self.storage_max_balance().map(|max| max.into()),
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: near_sdk::AccountId) -> Option<StorageBalance> {
+        self.storage_accounts
+            .get(&account_id)
+            .map(|account| self.storage_to_balance(&account))
+    }
+}
+
+impl StatusMessage {
+    pub const STORAGE_MIN_BYTES: near_sdk::StorageUsage = 250u64;
+    pub const STORAGE_MAX_BYTES: Option<near_sdk::StorageUsage> = Some(10000u64);
+    pub fn charge_storage<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let account_id = near_sdk::env::predecessor_account_id();
+        let mut account = self.storage_account(&account_id);
+        let before = near_sdk::env::storage_usage();
+        let result = f(self);
+        let after = near_sdk::env::storage_usage();
+        if after >= before {
+            account.used_bytes += after - before;
+        } else {
+            account.used_bytes = account.used_bytes.saturating_sub(before - after);
+        }
+        let locked = self.storage_locked(&account);
+        if locked > account.total {
+            near_sdk::env::panic(
+                format!(
+                    "Account {} needs {} more yoctoNEAR of storage balance",
+                    account_id,
+                    locked - account.total
+                )
+                .as_bytes(),
+            )
+        }
+        self.storage_accounts.insert(&account_id, &account);
+        result
+    }
+
+    fn storage_assert_one_yocto() {
+        if near_sdk::env::attached_deposit() != 1 {
+            near_sdk::env::panic(b"Requires attached deposit of exactly 1 yoctoNEAR")
+        }
+    }
+
+    fn storage_account(&self, account_id: &near_sdk::AccountId) -> StorageAccount {
+        self.storage_accounts.get(account_id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Account {} is not registered", account_id).as_bytes())
+        })
+    }
+
+    fn storage_min_balance(&self) -> near_sdk::Balance {
+        self.storage_min_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost()
+    }
+
+    fn storage_max_balance(&self) -> Option<near_sdk::Balance> {
+        self.storage_max_bytes
+            .map(|max_bytes| max_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost())
+    }
+
+    fn storage_locked(&self, account: &StorageAccount) -> near_sdk::Balance {
+        self.storage_min_balance()
+            + account.used_bytes as near_sdk::Balance * near_sdk::env::storage_byte_cost()
+    }
+
+    fn storage_available(&self, account: &StorageAccount) -> near_sdk::Balance {
+        account.total.saturating_sub(self.storage_locked(account))
+    }
+
+    fn storage_to_balance(&self, account: &StorageAccount) -> StorageBalance {
+        StorageBalance {
+            total: account.total.into(),
+            available: self.storage_available(account).into(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new() -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = Self {
+            messages: LookupMap::new(b"m".to_vec()),
+            storage_accounts: near_sdk::collections::LookupMap::new(
+                StorageKeyStorageManagement::Accounts.into_bytes(),
+            ),
+            storage_min_bytes: Self::STORAGE_MIN_BYTES,
+            storage_max_bytes: Self::STORAGE_MAX_BYTES,
+        };
+
+        constructor
+    }
+
+    pub fn get_message(&self, account_id: AccountId) -> Option<String> {
+        self.messages.get(&account_id)
+    }
+
+    // The storage of the message is paid from the storage balance of the caller
+    pub fn set_message(&mut self, message: String) {
+        self.charge_storage(|contract| {
+            contract
+                .messages
+                .insert(&env::predecessor_account_id(), &message);
+        })
+    }
+
+    pub fn delete_message(&mut self) {
+        self.charge_storage(|contract| {
+            contract.messages.remove(&env::predecessor_account_id());
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::U128;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, VMContext};
+
+    const BYTE: Balance = 10_000_000_000_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new()
+    }
+
+    fn available(contract: &StatusMessage) -> Balance {
+        contract
+            .storage_balance_of(String::from("bob.testnet"))
+            .unwrap()
+            .available
+            .0
+    }
+
+    #[test]
+    fn should_register() {
+        let mut contract = setup();
+
+        assert_eq!(
+            contract.storage_balance_bounds(),
+            StorageBalanceBounds {
+                min: U128(250 * BYTE),
+                max: Some(U128(10000 * BYTE)),
+            }
+        );
+
+        call("bob.testnet", 1000 * BYTE);
+        contract.storage_deposit(None, Some(true));
+
+        // Only the minimum is kept
+        assert_eq!(
+            contract.storage_balance_of(String::from("bob.testnet")),
+            Some(StorageBalance {
+                total: U128(250 * BYTE),
+                available: U128(0),
+            })
+        );
+
+        // Deposits above the maximum are refunded
+        call("carol.testnet", 20000 * BYTE);
+        let balance = contract.storage_deposit(Some(String::from("dave.testnet")), None);
+
+        assert_eq!(balance.total, U128(10000 * BYTE));
+        assert_eq!(balance.available, U128(9750 * BYTE));
+    }
+
+    #[test]
+    #[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+    fn should_panic_below_minimum() {
+        let mut contract = setup();
+
+        call("bob.testnet", 100 * BYTE);
+        contract.storage_deposit(None, None);
+    }
+
+    #[test]
+    fn should_charge_and_refund_storage() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        assert_eq!(available(&contract), 1000 * BYTE);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        let charged = 1000 * BYTE - available(&contract);
+        assert!(charged > 0);
+
+        contract.set_message(String::from("HELLO, WORLD"));
+
+        assert_eq!(1000 * BYTE - available(&contract), charged + 7 * BYTE);
+
+        contract.delete_message();
+
+        assert_eq!(available(&contract), 1000 * BYTE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet needs")]
+    fn should_panic_without_storage_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+    }
+
+    #[test]
+    fn should_withdraw() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 1);
+        let balance = contract.storage_withdraw(Some(U128(400 * BYTE)));
+
+        assert_eq!(balance.total, U128(850 * BYTE));
+
+        let balance = contract.storage_withdraw(None);
+
+        assert_eq!(balance.total, U128(250 * BYTE));
+        assert_eq!(balance.available, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "The amount is greater than the available storage balance")]
+    fn should_panic_on_withdrawing_locked_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 1);
+        contract.storage_withdraw(Some(U128(1001 * BYTE)));
+    }
+
+    #[test]
+    fn should_unregister() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        call("bob.testnet", 1);
+        assert!(contract.storage_unregister(Some(true)));
+        assert!(!contract.storage_unregister(None));
+        assert_eq!(
+            contract.storage_balance_of(String::from("bob.testnet")),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet still uses")]
+    fn should_panic_on_unregistering_with_data() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        call("bob.testnet", 1);
+        contract.storage_unregister(None);
+    }
+}
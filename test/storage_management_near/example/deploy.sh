@@ -0,0 +1,19 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{}' --accountId $ACCOUNT
+
+near view $ACCOUNT storage_balance_bounds
+
+# Registers and deposits 0.1 NEAR for messages
+near call $ACCOUNT storage_deposit '{}' --accountId timurguvenkaya.testnet --deposit 0.1
+
+near call $ACCOUNT set_message '{"message": "HELLO"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT storage_balance_of '{"account_id": "timurguvenkaya.testnet"}'
+
+near call $ACCOUNT delete_message '{}' --accountId timurguvenkaya.testnet
+
+near call $ACCOUNT storage_unregister '{}' --accountId timurguvenkaya.testnet --depositYocto 1
@@ -0,0 +1,242 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::collections::LookupMap;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(StorageManagement(min_bytes = 250, max_bytes = 10000))]
+pub struct StatusMessage {
+    messages: LookupMap<AccountId, String>,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new() -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let constructor = init_macro!([
+            "storage_management",
+            Self {
+                messages: LookupMap::new(b"m".to_vec())
+            }
+        ]);
+
+        constructor
+    }
+
+    pub fn get_message(&self, account_id: AccountId) -> Option<String> {
+        self.messages.get(&account_id)
+    }
+
+    // The storage of the message is paid from the storage balance of the caller
+    pub fn set_message(&mut self, message: String) {
+        self.charge_storage(|contract| {
+            contract
+                .messages
+                .insert(&env::predecessor_account_id(), &message);
+        })
+    }
+
+    pub fn delete_message(&mut self) {
+        self.charge_storage(|contract| {
+            contract.messages.remove(&env::predecessor_account_id());
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::U128;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, VMContext};
+
+    const BYTE: Balance = 10_000_000_000_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn call(predecessor_account_id: &str, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn setup() -> StatusMessage {
+        call("alice.testnet", 0);
+
+        StatusMessage::new()
+    }
+
+    fn available(contract: &StatusMessage) -> Balance {
+        contract
+            .storage_balance_of(String::from("bob.testnet"))
+            .unwrap()
+            .available
+            .0
+    }
+
+    #[test]
+    fn should_register() {
+        let mut contract = setup();
+
+        assert_eq!(
+            contract.storage_balance_bounds(),
+            StorageBalanceBounds {
+                min: U128(250 * BYTE),
+                max: Some(U128(10000 * BYTE)),
+            }
+        );
+
+        call("bob.testnet", 1000 * BYTE);
+        contract.storage_deposit(None, Some(true));
+
+        // Only the minimum is kept
+        assert_eq!(
+            contract.storage_balance_of(String::from("bob.testnet")),
+            Some(StorageBalance {
+                total: U128(250 * BYTE),
+                available: U128(0),
+            })
+        );
+
+        // Deposits above the maximum are refunded
+        call("carol.testnet", 20000 * BYTE);
+        let balance = contract.storage_deposit(Some(String::from("dave.testnet")), None);
+
+        assert_eq!(balance.total, U128(10000 * BYTE));
+        assert_eq!(balance.available, U128(9750 * BYTE));
+    }
+
+    #[test]
+    #[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+    fn should_panic_below_minimum() {
+        let mut contract = setup();
+
+        call("bob.testnet", 100 * BYTE);
+        contract.storage_deposit(None, None);
+    }
+
+    #[test]
+    fn should_charge_and_refund_storage() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        assert_eq!(available(&contract), 1000 * BYTE);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        let charged = 1000 * BYTE - available(&contract);
+        assert!(charged > 0);
+
+        contract.set_message(String::from("HELLO, WORLD"));
+
+        assert_eq!(1000 * BYTE - available(&contract), charged + 7 * BYTE);
+
+        contract.delete_message();
+
+        assert_eq!(available(&contract), 1000 * BYTE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet needs")]
+    fn should_panic_without_storage_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+    }
+
+    #[test]
+    fn should_withdraw() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 1);
+        let balance = contract.storage_withdraw(Some(U128(400 * BYTE)));
+
+        assert_eq!(balance.total, U128(850 * BYTE));
+
+        let balance = contract.storage_withdraw(None);
+
+        assert_eq!(balance.total, U128(250 * BYTE));
+        assert_eq!(balance.available, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "The amount is greater than the available storage balance")]
+    fn should_panic_on_withdrawing_locked_balance() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 1);
+        contract.storage_withdraw(Some(U128(1001 * BYTE)));
+    }
+
+    #[test]
+    fn should_unregister() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        call("bob.testnet", 1);
+        assert!(contract.storage_unregister(Some(true)));
+        assert!(!contract.storage_unregister(None));
+        assert_eq!(
+            contract.storage_balance_of(String::from("bob.testnet")),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.testnet still uses")]
+    fn should_panic_on_unregistering_with_data() {
+        let mut contract = setup();
+
+        call("bob.testnet", 1250 * BYTE);
+        contract.storage_deposit(None, None);
+
+        call("bob.testnet", 0);
+        contract.set_message(String::from("HELLO"));
+
+        call("bob.testnet", 1);
+        contract.storage_unregister(None);
+    }
+}
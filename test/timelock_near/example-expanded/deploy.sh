@@ -0,0 +1,20 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "proposer": "app.timurguvenkaya.testnet", "executor": "timurguvenkaya.testnet", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+# Schedules the call instead of running it
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId app.timurguvenkaya.testnet
+
+near view $ACCOUNT operations '{"from_index": 0, "limit": 10}'
+
+# Should fail because the delay has not passed yet
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId timurguvenkaya.testnet
+
+sleep 60
+
+near call $ACCOUNT set_data '{"data": "NEW_DATA"}' --accountId timurguvenkaya.testnet
+
+near view $ACCOUNT get_data
@@ -0,0 +1,713 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const PROPOSER: &str = "proposer";
+const EXECUTOR: &str = "executor";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    fee: u64,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    timelock_operations: near_sdk::collections::UnorderedMap<String, TimelockOperation>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyTimelock {
+    Operations,
+}
+
+impl StorageKeyTimelock {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyTimelock::Operations => "tlo".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TimelockOperation {
+    pub method: String,
+    pub args: String,
+    pub proposer: near_sdk::AccountId,
+    pub scheduled_at: u64,
+    pub ready_at: u64,
+    pub executing: bool,
+}
+
+pub trait Timelock {
+    fn schedule(&mut self, method: String, args: String) -> String;
+    fn cancel(&mut self, id: String);
+    fn execute(&mut self, id: String) -> near_sdk::Promise;
+    fn timelock_on_execute(&mut self, id: String) -> bool;
+    fn operation(&self, id: String) -> Option<TimelockOperation>;
+    fn operations(&self, from_index: u64, limit: u64)
+        -> std::vec::Vec<(String, TimelockOperation)>;
+    fn timelock_delay(&self) -> u64;
+}
+
+#[near_bindgen]
+impl Timelock for StatusMessage {
+    fn schedule(&mut self, method: String, args: String) -> String {
+        let args = near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args)
+            .unwrap_or_else(|_| near_sdk::env::panic(b"Arguments have to be a JSON object"))
+            .to_string();
+        self.timelock_schedule(&method, args, 60000000000u64)
+    }
+
+    fn cancel(&mut self, id: String) {
+        self.assert_role(&"proposer".to_string());
+        if self.timelock_operations.remove(&id).is_none() {
+            near_sdk::env::panic(format!("Operation {} is not scheduled", id).as_bytes())
+        }
+        near_sdk::env::log(
+            format!(
+                "Operation {} is cancelled by {}",
+                id,
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn execute(&mut self, id: String) -> near_sdk::Promise {
+        self.assert_role(&"executor".to_string());
+        let mut operation = self.timelock_operations.get(&id).unwrap_or_else(|| {
+            near_sdk::env::panic(format!("Operation {} is not scheduled", id).as_bytes())
+        });
+        if operation.executing {
+            near_sdk::env::panic(format!("Operation {} is being executed", id).as_bytes())
+        }
+        if near_sdk::env::block_timestamp() < operation.ready_at {
+            near_sdk::env::panic(
+                format!("Operation {} is not ready until {}", id, operation.ready_at).as_bytes(),
+            )
+        }
+        operation.executing = true;
+        self.timelock_operations.insert(&id, &operation);
+        near_sdk::Promise::new(near_sdk::env::current_account_id())
+            .function_call(
+                operation.method.into_bytes(),
+                operation.args.into_bytes(),
+                0,
+                near_sdk::env::prepaid_gas()
+                    .saturating_sub(near_sdk::env::used_gas())
+                    .saturating_sub(20_000_000_000_000)
+                    .saturating_sub(Self::TIMELOCK_GAS_FOR_EXECUTE_CALLBACK),
+            )
+            .then(
+                near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                    b"timelock_on_execute".to_vec(),
+                    near_sdk::serde_json::json!
+        ({ "id" : id })
+                    .to_string()
+                    .into_bytes(),
+                    0,
+                    Self::TIMELOCK_GAS_FOR_EXECUTE_CALLBACK,
+                ),
+            )
+    }
+
+    #[private]
+    fn timelock_on_execute(&mut self, id: String) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            if self.timelock_operations.remove(&id).is_some() {
+                near_sdk::env::log(format!("Operation {} is executed", id).as_bytes());
+            }
+            return true;
+        }
+        if let Some(mut operation) = self.timelock_operations.get(&id) {
+            operation.executing = false;
+            self.timelock_operations.insert(&id, &operation);
+        }
+        near_sdk::env::log(format!("Execution of operation {} failed", id).as_bytes());
+        false
+    }
+
+    fn operation(&self, id: String) -> Option<TimelockOperation> {
+        self.timelock_operations.get(&id)
+    }
+
+    fn operations(
+        &self,
+        from_index: u64,
+        limit: u64,
+    ) -> std::vec::Vec<(String, TimelockOperation)> {
+        self.timelock_operations
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    fn timelock_delay(&self) -> u64 {
+        60000000000u64
+    }
+}
+
+impl StatusMessage {
+    pub const TIMELOCK_GAS_FOR_EXECUTE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    fn timelock_id(method: &str, args: &str) -> String {
+        near_sdk::env::sha256(format!("{}:{}", method, args).as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn timelock_schedule(&mut self, method: &str, args: String, delay_ns: u64) -> String {
+        self.assert_role(&"proposer".to_string());
+        let id = Self::timelock_id(method, &args);
+        if self.timelock_operations.get(&id).is_some() {
+            near_sdk::env::panic(format!("Operation {} is already scheduled", id).as_bytes())
+        }
+        let now = near_sdk::env::block_timestamp();
+        let operation = TimelockOperation {
+            method: method.to_string(),
+            args,
+            proposer: near_sdk::env::predecessor_account_id(),
+            scheduled_at: now,
+            ready_at: now.saturating_add(delay_ns),
+            executing: false,
+        };
+        near_sdk::env::log(
+            format!(
+                "Operation {} calling {}({}) is scheduled by {} and is ready at {}",
+                id, operation.method, operation.args, operation.proposer, operation.ready_at
+            )
+            .as_bytes(),
+        );
+        self.timelock_operations.insert(&id, &operation);
+        id
+    }
+
+    fn timelock_call(&mut self, method: &str, args: String, delay_ns: Option<u64>) -> bool {
+        let id = Self::timelock_id(method, &args);
+        let operation = match self.timelock_operations.get(&id) {
+            Some(operation) => operation,
+            None => {
+                self.timelock_schedule(method, args, delay_ns.unwrap_or(60000000000u64));
+                return false;
+            }
+        };
+        if near_sdk::env::predecessor_account_id() != near_sdk::env::current_account_id() {
+            self.assert_role(&"executor".to_string());
+            if operation.executing {
+                near_sdk::env::panic(format!("Operation {} is being executed", id).as_bytes())
+            }
+        }
+        let ready_at = operation.ready_at.max(
+            operation
+                .scheduled_at
+                .saturating_add(delay_ns.unwrap_or(60000000000u64)),
+        );
+        if near_sdk::env::block_timestamp() < ready_at {
+            near_sdk::env::panic(
+                format!("Operation {} is not ready until {}", id, ready_at).as_bytes(),
+            )
+        }
+        self.timelock_operations.remove(&id);
+        near_sdk::env::log(
+            format!(
+                "Operation {} is executed by {}",
+                id,
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        );
+        true
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, proposer: AccountId, executor: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: data,
+            fee: 0,
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            timelock_operations: near_sdk::collections::UnorderedMap::new(
+                StorageKeyTimelock::Operations.into_bytes(),
+            ),
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&PROPOSER.to_string(), &proposer);
+        constructor.setup_account_role(&EXECUTOR.to_string(), &executor);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn get_fee(&self) -> u64 {
+        self.fee
+    }
+
+    pub fn set_data(&mut self, data: String) {
+        if !self.timelock_call(
+            "set_data",
+            near_sdk::serde_json::json!
+    ({ "data" : & data })
+            .to_string(),
+            None,
+        ) {
+            return;
+        }
+        self.data = data;
+    }
+
+    pub fn set_fee(&mut self, fee: u64) {
+        if !self.timelock_call(
+            "set_fee",
+            near_sdk::serde_json::json!
+    ({ "fee" : & fee })
+            .to_string(),
+            Some(3600000000000u64),
+        ) {
+            return;
+        }
+        self.fee = fee;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn callback(seconds: u64, result: PromiseResult) {
+        let mut context = get_context(String::from("alice.testnet"), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    // Any method of the contract can be scheduled, not only timelocked ones
+    fn schedule_grant_role(contract: &mut StatusMessage) -> String {
+        at("proposer.testnet", 10);
+
+        let id = contract.schedule(
+            String::from("grant_role"),
+            String::from(r#"{"role": "proposer", "account": "bob.testnet"}"#),
+        );
+
+        at("executor.testnet", 70);
+
+        contract.execute(id.clone());
+
+        id
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("proposer.testnet"),
+            String::from("executor.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_schedule_and_execute_call() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        let operations = contract.operations(0, 10);
+
+        assert_eq!(operations.len(), 1);
+        assert_eq!(
+            operations[0].1,
+            TimelockOperation {
+                method: String::from("set_data"),
+                args: String::from(r#"{"data":"NEW DATA"}"#),
+                proposer: String::from("proposer.testnet"),
+                scheduled_at: 10 * SECOND,
+                ready_at: 70 * SECOND,
+                executing: false,
+            }
+        );
+
+        at("executor.testnet", 70);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert_eq!(contract.operation(operations[0].0.clone()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not ready until 70000000000"#)]
+    fn should_not_execute_before_delay() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("executor.testnet", 69);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet , is missing: proposer role"#)]
+    fn only_proposer_can_schedule() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account proposer.testnet , is missing: executor role"#)]
+    fn only_executor_can_execute() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("proposer.testnet", 70);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn should_cancel_operation() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        let id = contract.schedule(
+            String::from("set_data"),
+            String::from(r#"{ "data": "NEW DATA" }"#),
+        );
+
+        contract.cancel(id.clone());
+
+        assert_eq!(contract.operation(id), None);
+
+        // The call is scheduled again instead of being executed
+        at("proposer.testnet", 100);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+        assert_eq!(contract.operations(0, 10)[0].1.ready_at, 160 * SECOND);
+    }
+
+    #[test]
+    fn should_execute_scheduled_operation() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        // Arguments are normalized, so the id matches the one of the timelocked method
+        let id = contract.schedule(String::from("set_fee"), String::from(r#"{ "fee": 5 }"#));
+
+        assert_eq!(
+            contract.operation(id.clone()).unwrap().ready_at,
+            70 * SECOND
+        );
+
+        at("executor.testnet", 70);
+
+        contract.execute(id.clone());
+
+        // The promise calls set_fee from the contract account. set_fee has a longer delay than the module
+        at("alice.testnet", 3610);
+
+        contract.set_fee(5);
+
+        assert_eq!(contract.get_fee(), 5);
+        assert_eq!(contract.operation(id), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not ready until 3610000000000"#)]
+    fn method_delay_applies_to_scheduled_operations() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.schedule(String::from("set_fee"), String::from(r#"{"fee": 5}"#));
+
+        at("alice.testnet", 70);
+
+        contract.set_fee(5);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is being executed"#)]
+    fn should_not_execute_twice_in_flight() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        contract.execute(id);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not scheduled"#)]
+    fn should_not_execute_twice() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        callback(70, PromiseResult::Successful(vec![]));
+        assert!(contract.timelock_on_execute(id.clone()));
+        assert_eq!(contract.operation(id.clone()), None);
+
+        at("executor.testnet", 80);
+
+        contract.execute(id);
+    }
+
+    #[test]
+    fn should_restore_failed_execution() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        callback(70, PromiseResult::Failed);
+        assert!(!contract.timelock_on_execute(id.clone()));
+        assert!(!contract.operation(id.clone()).unwrap().executing);
+
+        at("executor.testnet", 80);
+
+        contract.execute(id.clone());
+
+        assert!(contract.operation(id).unwrap().executing);
+    }
+}
@@ -0,0 +1,306 @@
+use near_macros::{init_macro, require, timelocked};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const PROPOSER: &str = "proposer";
+const EXECUTOR: &str = "executor";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Timelock(delay_secs = 60))]
+pub struct StatusMessage {
+    data: String,
+    fee: u64,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, proposer: AccountId, executor: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!(["access", "timelock", Self { data: data, fee: 0 }]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&PROPOSER.to_string(), &proposer);
+        constructor.setup_account_role(&EXECUTOR.to_string(), &executor);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    pub fn get_fee(&self) -> u64 {
+        self.fee
+    }
+
+    #[timelocked]
+    pub fn set_data(&mut self, data: String) {
+        self.data = data;
+    }
+
+    #[timelocked(delay_secs = 3600)]
+    pub fn set_fee(&mut self, fee: u64) {
+        self.fee = fee;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(context);
+    }
+
+    fn callback(seconds: u64, result: PromiseResult) {
+        let mut context = get_context(String::from("alice.testnet"), 0);
+        context.block_timestamp = seconds * SECOND;
+        testing_env!(
+            context,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    // Any method of the contract can be scheduled, not only timelocked ones
+    fn schedule_grant_role(contract: &mut StatusMessage) -> String {
+        at("proposer.testnet", 10);
+
+        let id = contract.schedule(
+            String::from("grant_role"),
+            String::from(r#"{"role": "proposer", "account": "bob.testnet"}"#),
+        );
+
+        at("executor.testnet", 70);
+
+        contract.execute(id.clone());
+
+        id
+    }
+
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0);
+
+        StatusMessage::new(
+            String::from("timurguvenkaya.testnet"),
+            String::from("proposer.testnet"),
+            String::from("executor.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn should_schedule_and_execute_call() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+
+        let operations = contract.operations(0, 10);
+
+        assert_eq!(operations.len(), 1);
+        assert_eq!(
+            operations[0].1,
+            TimelockOperation {
+                method: String::from("set_data"),
+                args: String::from(r#"{"data":"NEW DATA"}"#),
+                proposer: String::from("proposer.testnet"),
+                scheduled_at: 10 * SECOND,
+                ready_at: 70 * SECOND,
+                executing: false,
+            }
+        );
+
+        at("executor.testnet", 70);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("NEW DATA"));
+        assert_eq!(contract.operation(operations[0].0.clone()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not ready until 70000000000"#)]
+    fn should_not_execute_before_delay() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("executor.testnet", 69);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account bob.testnet , is missing: proposer role"#)]
+    fn only_proposer_can_schedule() {
+        let mut contract = setup();
+
+        at("bob.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account proposer.testnet , is missing: executor role"#)]
+    fn only_executor_can_execute() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        at("proposer.testnet", 70);
+
+        contract.set_data(String::from("NEW DATA"));
+    }
+
+    #[test]
+    fn should_cancel_operation() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        let id = contract.schedule(
+            String::from("set_data"),
+            String::from(r#"{ "data": "NEW DATA" }"#),
+        );
+
+        contract.cancel(id.clone());
+
+        assert_eq!(contract.operation(id), None);
+
+        // The call is scheduled again instead of being executed
+        at("proposer.testnet", 100);
+
+        contract.set_data(String::from("NEW DATA"));
+
+        assert_eq!(contract.get_data(), String::from("SOME DATA"));
+        assert_eq!(contract.operations(0, 10)[0].1.ready_at, 160 * SECOND);
+    }
+
+    #[test]
+    fn should_execute_scheduled_operation() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        // Arguments are normalized, so the id matches the one of the timelocked method
+        let id = contract.schedule(String::from("set_fee"), String::from(r#"{ "fee": 5 }"#));
+
+        assert_eq!(
+            contract.operation(id.clone()).unwrap().ready_at,
+            70 * SECOND
+        );
+
+        at("executor.testnet", 70);
+
+        contract.execute(id.clone());
+
+        // The promise calls set_fee from the contract account. set_fee has a longer delay than the module
+        at("alice.testnet", 3610);
+
+        contract.set_fee(5);
+
+        assert_eq!(contract.get_fee(), 5);
+        assert_eq!(contract.operation(id), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not ready until 3610000000000"#)]
+    fn method_delay_applies_to_scheduled_operations() {
+        let mut contract = setup();
+
+        at("proposer.testnet", 10);
+
+        contract.schedule(String::from("set_fee"), String::from(r#"{"fee": 5}"#));
+
+        at("alice.testnet", 70);
+
+        contract.set_fee(5);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is being executed"#)]
+    fn should_not_execute_twice_in_flight() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        contract.execute(id);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"is not scheduled"#)]
+    fn should_not_execute_twice() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        callback(70, PromiseResult::Successful(vec![]));
+        assert!(contract.timelock_on_execute(id.clone()));
+        assert_eq!(contract.operation(id.clone()), None);
+
+        at("executor.testnet", 80);
+
+        contract.execute(id);
+    }
+
+    #[test]
+    fn should_restore_failed_execution() {
+        let mut contract = setup();
+
+        let id = schedule_grant_role(&mut contract);
+
+        callback(70, PromiseResult::Failed);
+        assert!(!contract.timelock_on_execute(id.clone()));
+        assert!(!contract.operation(id.clone()).unwrap().executing);
+
+        at("executor.testnet", 80);
+
+        contract.execute(id.clone());
+
+        assert!(contract.operation(id).unwrap().executing);
+    }
+}
@@ -0,0 +1,18 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"owner": "timurguvenkaya.testnet", "stager": "app.timurguvenkaya.testnet", "deployer": "timurguvenkaya.testnet", "data": "SOME_DATA"}' --accountId $ACCOUNT
+
+# Stage the same code again
+near call $ACCOUNT stage_code "{\"code\": \"$(base64 -w 0 ./res/status_message.wasm)\"}" --accountId app.timurguvenkaya.testnet --gas 300000000000000
+
+near view $ACCOUNT staged_code_hash
+
+# Should fail because the delay has not passed yet
+near call $ACCOUNT deploy_code '{}' --accountId timurguvenkaya.testnet --gas 300000000000000
+
+sleep 60
+
+near call $ACCOUNT deploy_code '{}' --accountId timurguvenkaya.testnet --gas 300000000000000
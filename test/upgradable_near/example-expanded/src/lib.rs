@@ -0,0 +1,458 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const CODE_STAGER: &str = "code_stager";
+const CODE_DEPLOYER: &str = "code_deployer";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    staged_code: near_sdk::collections::LazyOption<std::vec::Vec<u8>>,
+    staged_code_hash: Option<String>,
+    staged_at: Option<u64>,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyUpgradable {
+    StagedCode,
+}
+
+impl StorageKeyUpgradable {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyUpgradable::StagedCode => "upc".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+pub trait Upgradable {
+    fn stage_code(&mut self, code: near_sdk::json_types::Base64VecU8);
+    fn unstage_code(&mut self);
+    fn deploy_code(&mut self) -> near_sdk::Promise;
+    fn staged_code_hash(&self) -> Option<String>;
+    fn staged_at(&self) -> Option<u64>;
+    fn upgrade_delay(&self) -> u64;
+}
+
+#[near_bindgen]
+impl Upgradable for StatusMessage {
+    fn stage_code(&mut self, code: near_sdk::json_types::Base64VecU8) {
+        self.assert_role(&"code_stager".to_string());
+        let code: std::vec::Vec<u8> = code.into();
+        if code.is_empty() {
+            near_sdk::env::panic(b"Staged code cannot be empty")
+        }
+        let hash: String = near_sdk::env::sha256(&code)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        near_sdk::env::log(
+            format!(
+                "Code with hash {} is staged by {} at {}",
+                hash,
+                near_sdk::env::predecessor_account_id(),
+                near_sdk::env::block_timestamp()
+            )
+            .as_bytes(),
+        );
+        self.staged_code.set(&code);
+        self.staged_code_hash = Some(hash);
+        self.staged_at = Some(near_sdk::env::block_timestamp());
+    }
+
+    fn unstage_code(&mut self) {
+        self.assert_role(&"code_stager".to_string());
+        if self.staged_code_hash.is_none() {
+            near_sdk::env::panic(b"No code is staged")
+        }
+        near_sdk::env::log(
+            format!(
+                "Code with hash {} is unstaged",
+                self.staged_code_hash.as_ref().unwrap()
+            )
+            .as_bytes(),
+        );
+        self.staged_code.remove();
+        self.staged_code_hash = None;
+        self.staged_at = None;
+    }
+
+    fn deploy_code(&mut self) -> near_sdk::Promise {
+        self.assert_role(&"code_deployer".to_string());
+        let staged_at = self
+            .staged_at
+            .unwrap_or_else(|| near_sdk::env::panic(b"No code is staged"));
+        if near_sdk::env::block_timestamp() < staged_at.saturating_add(60000000000u64) {
+            near_sdk::env::panic(
+                format!(
+                    "Staged code cannot be deployed before {}",
+                    staged_at.saturating_add(60000000000u64)
+                )
+                .as_bytes(),
+            )
+        }
+        let code = self.staged_code.get().unwrap();
+        near_sdk::env::log(
+            format!(
+                "Code with hash {} is deployed by {}",
+                self.staged_code_hash.as_ref().unwrap(),
+                near_sdk::env::predecessor_account_id()
+            )
+            .as_bytes(),
+        );
+        self.staged_code.remove();
+        self.staged_code_hash = None;
+        self.staged_at = None;
+        near_sdk::Promise::new(near_sdk::env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".as_bytes().to_vec(),
+                std::vec::Vec::new(),
+                0,
+                near_sdk::env::prepaid_gas()
+                    .saturating_sub(near_sdk::env::used_gas())
+                    .saturating_sub(20_000_000_000_000),
+            )
+    }
+
+    fn staged_code_hash(&self) -> Option<String> {
+        self.staged_code_hash.clone()
+    }
+
+    fn staged_at(&self) -> Option<u64> {
+        self.staged_at
+    }
+
+    fn upgrade_delay(&self) -> u64 {
+        60000000000u64
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, stager: AccountId, deployer: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: data,
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            staged_code: near_sdk::collections::LazyOption::new(
+                StorageKeyUpgradable::StagedCode.into_bytes(),
+                None,
+            ),
+            staged_code_hash: None,
+            staged_at: None,
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&CODE_STAGER.to_string(), &stager);
+        constructor.setup_account_role(&CODE_DEPLOYER.to_string(), &deployer);
+
+        constructor
+    }
+
+    // Called by the new code right after deploy_code
+    #[private]
+    pub fn migrate(&mut self) {
+        env::log(b"Migrated");
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, block_timestamp: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage: 0,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn get_contract() -> StatusMessage {
+        testing_env!(get_context(String::from("alice.testnet"), 0));
+
+        StatusMessage::new(
+            String::from("timur.testnet"),
+            String::from("mike.testnet"),
+            String::from("jane.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn stage_and_deploy_works() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        assert_eq!(
+            contract.staged_code_hash(),
+            Some(String::from(
+                "cd5d4935a48c0672cb06407bb443bc0087aff947c6b864bac886982c73b3027f"
+            ))
+        );
+        assert_eq!(contract.staged_at(), Some(1_000));
+        assert_eq!(contract.upgrade_delay(), 60_000_000_000);
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+
+        assert_eq!(contract.staged_code_hash(), None);
+        assert_eq!(contract.staged_at(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Staged code cannot be deployed before 60000001000"#)]
+    fn deploy_before_delay_fails() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_000_999));
+
+        contract.deploy_code();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account jane.testnet , is missing: code_stager role"#)]
+    fn only_stager_can_stage() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("jane.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mike.testnet , is missing: code_deployer role"#)]
+    fn only_deployer_can_deploy() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        testing_env!(get_context(String::from("mike.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"No code is staged"#)]
+    fn unstage_works() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+        contract.unstage_code();
+
+        assert_eq!(contract.staged_code_hash(), None);
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+    }
+}
@@ -0,0 +1,162 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const CODE_STAGER: &str = "code_stager";
+const CODE_DEPLOYER: &str = "code_deployer";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Upgradable(delay_secs = 60))]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(owner: AccountId, stager: AccountId, deployer: AccountId, data: String) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!(["access", "upgradable", Self { data: data }]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &owner);
+        constructor.setup_account_role(&CODE_STAGER.to_string(), &stager);
+        constructor.setup_account_role(&CODE_DEPLOYER.to_string(), &deployer);
+
+        constructor
+    }
+
+    // Called by the new code right after deploy_code
+    #[private]
+    pub fn migrate(&mut self) {
+        env::log(b"Migrated");
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context(predecessor_account_id: String, block_timestamp: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage: 0,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn get_contract() -> StatusMessage {
+        testing_env!(get_context(String::from("alice.testnet"), 0));
+
+        StatusMessage::new(
+            String::from("timur.testnet"),
+            String::from("mike.testnet"),
+            String::from("jane.testnet"),
+            String::from("SOME DATA"),
+        )
+    }
+
+    #[test]
+    fn stage_and_deploy_works() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        assert_eq!(
+            contract.staged_code_hash(),
+            Some(String::from(
+                "cd5d4935a48c0672cb06407bb443bc0087aff947c6b864bac886982c73b3027f"
+            ))
+        );
+        assert_eq!(contract.staged_at(), Some(1_000));
+        assert_eq!(contract.upgrade_delay(), 60_000_000_000);
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+
+        assert_eq!(contract.staged_code_hash(), None);
+        assert_eq!(contract.staged_at(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Staged code cannot be deployed before 60000001000"#)]
+    fn deploy_before_delay_fails() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_000_999));
+
+        contract.deploy_code();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account jane.testnet , is missing: code_stager role"#)]
+    fn only_stager_can_stage() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("jane.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Account mike.testnet , is missing: code_deployer role"#)]
+    fn only_deployer_can_deploy() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+
+        testing_env!(get_context(String::from("mike.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"No code is staged"#)]
+    fn unstage_works() {
+        let mut contract = get_contract();
+
+        testing_env!(get_context(String::from("mike.testnet"), 1_000));
+
+        contract.stage_code(vec![0, 97, 115, 109].into());
+        contract.unstage_code();
+
+        assert_eq!(contract.staged_code_hash(), None);
+
+        testing_env!(get_context(String::from("jane.testnet"), 60_000_001_000));
+
+        contract.deploy_code();
+    }
+}
@@ -0,0 +1,15 @@
+#!/bin/bash
+rm -rf ~/.near-credentials/testnet/dev-*
+
+ACCOUNT=$(near dev-deploy | sed -n '5,1p' | grep -o -E "dev-\d+-\d+")
+
+near call $ACCOUNT new '{"admin": "timurguvenkaya.testnet", "token_id": null}' --accountId $ACCOUNT
+
+# Vests 1 NEAR starting now, with a cliff of a minute, over an hour
+near call $ACCOUNT create_vesting "{\"account_id\": \"app.timurguvenkaya.testnet\", \"total\": \"1000000000000000000000000\", \"start_secs\": $(date +%s), \"cliff_secs\": 60, \"duration_secs\": 3600}" --accountId timurguvenkaya.testnet --deposit 1
+
+sleep 120
+
+near view $ACCOUNT claimable '{"account_id": "app.timurguvenkaya.testnet"}'
+
+near call $ACCOUNT claim '{}' --accountId app.timurguvenkaya.testnet --gas 50000000000000
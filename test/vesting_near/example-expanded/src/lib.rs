@@ -0,0 +1,768 @@
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const VESTING_ADMIN: &str = "vesting_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault, near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct StatusMessage {
+    data: String,
+    access_control_roles: near_sdk::collections::UnorderedMap<String, AccessControlRoleData>,
+    vesting_schedules: near_sdk::collections::LookupMap<near_sdk::AccountId, VestingSchedule>,
+    vesting_token: Option<near_sdk::AccountId>,
+    vesting_unallocated: near_sdk::Balance,
+}
+
+pub enum StorageKeyAccessControl {
+    Roles,
+    AdminRole(String),
+    RoleData(String),
+}
+
+impl StorageKeyAccessControl {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyAccessControl::Roles => "rol".to_string(),
+            StorageKeyAccessControl::AdminRole(adm) => format!("{}adm", adm),
+            StorageKeyAccessControl::RoleData(data) => format!("{}data", data),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(near_sdk::borsh::BorshDeserialize, near_sdk::borsh::BorshSerialize)]
+pub struct AccessControlRoleData {
+    members: near_sdk::collections::LookupSet<near_sdk::AccountId>,
+    admin_role: near_sdk::collections::LookupMap<String, String>,
+}
+
+pub trait AccessControl {
+    fn add_role(&mut self, role: &String);
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool;
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId);
+    fn assert_role(&self, role: &String);
+    fn get_role_admin(&self, role: &String) -> String;
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> Vec<String>;
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId);
+    fn set_admin_role(&mut self, role: &String, admin_role: &String);
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId);
+}
+
+#[near_bindgen]
+impl AccessControl for StatusMessage {
+    #[private]
+    fn add_role(&mut self, role: &String) {
+        if self.access_control_roles.get(role).is_none() {
+            let mut role_data = AccessControlRoleData {
+                members: near_sdk::collections::LookupSet::new(
+                    StorageKeyAccessControl::RoleData(role.to_string()).into_bytes(),
+                ),
+                admin_role: near_sdk::collections::LookupMap::new(
+                    StorageKeyAccessControl::AdminRole(role.to_string()).into_bytes(),
+                ),
+            };
+            role_data
+                .admin_role
+                .insert(role, &"default_admin".to_string());
+            self.access_control_roles.insert(role, &role_data);
+            near_sdk::env::log(format!("Role {} is added", role).as_bytes())
+        }
+    }
+
+    fn has_role(&self, role: &String, account: &near_sdk::AccountId) -> bool {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.members.contains(account),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn check_role(&self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            env::panic(format!("Account {} , is missing: {} role", account, role).as_bytes());
+        }
+    }
+
+    fn assert_role(&self, role: &String) {
+        self.check_role(role, &near_sdk::env::predecessor_account_id())
+    }
+
+    fn get_role_admin(&self, role: &String) -> String {
+        let role_data = self.access_control_roles.get(role);
+        match role_data {
+            Some(r) => r.admin_role.get(role).unwrap().to_string(),
+            None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+        }
+    }
+
+    fn get_account_roles(&self, account: &near_sdk::AccountId) -> std::vec::Vec<String> {
+        let mut found_role = std::vec::Vec::new();
+        for role in self.access_control_roles.keys() {
+            if self.has_role(&role, account) {
+                found_role.push(role);
+            }
+        }
+        found_role
+    }
+
+    fn grant_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        self.add_role_member(role, account);
+    }
+
+    #[private]
+    fn setup_account_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.add_role(role);
+        self.add_role_member(role, account);
+    }
+
+    fn revoke_role(&mut self, role: &String, account: &near_sdk::AccountId) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.members.remove(account);
+                    near_sdk::env::log(
+                        format!("Role {} is revoked from {}", role, account).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    fn set_admin_role(&mut self, role: &String, admin_role: &String) {
+        self.assert_role(&self.get_role_admin(role));
+        if self.get_role_admin(role) != *admin_role {
+            let role_data = self.access_control_roles.get(role);
+            match role_data {
+                Some(mut r) => {
+                    r.admin_role.get(role).unwrap().clear();
+                    r.admin_role.insert(role, &admin_role.to_string());
+                    near_sdk::env::log(
+                        format!(
+                            "Changed admin role from: {}. To: {}",
+                            r.admin_role.get(role).unwrap(),
+                            admin_role
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+
+    #[private]
+    fn add_role_member(&mut self, role: &String, account: &near_sdk::AccountId) {
+        if !self.has_role(role, account) {
+            let role_data = self.access_control_roles.get(role);
+            near_sdk::env::log(format!("Setting role: {}. To: {}", role, account).as_bytes());
+            match role_data {
+                Some(mut r) => {
+                    r.members.insert(account);
+                    near_sdk::env::log(
+                        format!("Account {} is added to {}", account, role).as_bytes(),
+                    )
+                }
+                None => near_sdk::env::panic(format!("Role: {} does not exist", role).as_bytes()),
+            }
+        }
+    }
+}
+
+pub enum StorageKeyVesting {
+    Schedules,
+}
+
+impl StorageKeyVesting {
+    pub fn to_string(&self) -> String {
+        match self {
+            StorageKeyVesting::Schedules => "vst".to_string(),
+        }
+    }
+
+    pub fn into_bytes(&self) -> std::vec::Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(
+    near_sdk::borsh::BorshDeserialize,
+    near_sdk::borsh::BorshSerialize,
+    near_sdk::serde::Serialize,
+    near_sdk::serde::Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingSchedule {
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub total: near_sdk::json_types::U128,
+    pub claimed: near_sdk::json_types::U128,
+    pub revoked: bool,
+}
+
+pub trait Vesting {
+    fn create_vesting(
+        &mut self,
+        account_id: near_sdk::AccountId,
+        total: near_sdk::json_types::U128,
+        start_secs: u64,
+        cliff_secs: u64,
+        duration_secs: u64,
+    );
+    fn revoke_vesting(&mut self, account_id: near_sdk::AccountId) -> near_sdk::Promise;
+    fn vesting_on_revoke(&mut self, amount: near_sdk::json_types::U128) -> bool;
+    fn claim(&mut self) -> near_sdk::Promise;
+    fn vesting_on_claim(
+        &mut self,
+        account_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+    ) -> bool;
+    fn claimable(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128;
+    fn vesting_schedule(&self, account_id: near_sdk::AccountId) -> Option<VestingSchedule>;
+    fn vesting_token(&self) -> Option<near_sdk::AccountId>;
+    fn vesting_unallocated(&self) -> near_sdk::json_types::U128;
+}
+
+#[near_bindgen]
+impl Vesting for StatusMessage {
+    #[payable]
+    fn create_vesting(
+        &mut self,
+        account_id: near_sdk::AccountId,
+        total: near_sdk::json_types::U128,
+        start_secs: u64,
+        cliff_secs: u64,
+        duration_secs: u64,
+    ) {
+        self.assert_role(&"vesting_admin".to_string());
+        if !near_sdk::env::is_valid_account_id(account_id.as_bytes()) {
+            near_sdk::env::panic(format!("Account {} is invalid", account_id).as_bytes())
+        }
+        if self.vesting_schedules.get(&account_id).is_some() {
+            near_sdk::env::panic(
+                format!("Account {} already has a vesting schedule", account_id).as_bytes(),
+            )
+        }
+        if total.0 == 0 {
+            near_sdk::env::panic(b"The amount should be a positive number")
+        }
+        if duration_secs == 0 || cliff_secs > duration_secs {
+            near_sdk::env::panic(b"duration_secs has to be positive and not less than cliff_secs")
+        }
+        let deposit = near_sdk::env::attached_deposit();
+        match &self.vesting_token {
+            None if deposit != total.0 => near_sdk::env::panic(
+                format!("Requires attached deposit of {} yoctoNEAR", total.0).as_bytes(),
+            ),
+            Some(_) if deposit != 0 => {
+                near_sdk::env::panic(b"Token schedules do not accept deposits")
+            }
+            Some(_) if total.0 > self.vesting_unallocated => near_sdk::env::panic(
+                format!(
+                    "Only {} tokens are funded and not allocated",
+                    self.vesting_unallocated
+                )
+                .as_bytes(),
+            ),
+            Some(_) => self.vesting_unallocated -= total.0,
+            None => {}
+        }
+        let start = start_secs
+            .checked_mul(1_000_000_000)
+            .unwrap_or_else(|| near_sdk::env::panic(b"start_secs is too large"));
+        let duration = duration_secs
+            .checked_mul(1_000_000_000)
+            .unwrap_or_else(|| near_sdk::env::panic(b"duration_secs is too large"));
+        if start.checked_add(duration).is_none() {
+            near_sdk::env::panic(b"duration_secs is too large")
+        }
+        let cliff = cliff_secs
+            .checked_mul(1_000_000_000)
+            .and_then(|cliff| start.checked_add(cliff))
+            .unwrap_or_else(|| near_sdk::env::panic(b"cliff_secs is too large"));
+        near_sdk::env::log(format!
+        ("Vesting of {} for {} is created, starting at {} with a cliff of {} seconds over {} seconds",
+        total.0, account_id, start_secs, cliff_secs,
+        duration_secs).as_bytes(),);
+        self.vesting_schedules.insert(
+            &account_id,
+            &VestingSchedule {
+                start,
+                cliff,
+                duration,
+                total,
+                claimed: near_sdk::json_types::U128(0),
+                revoked: false,
+            },
+        );
+    }
+
+    fn revoke_vesting(&mut self, account_id: near_sdk::AccountId) -> near_sdk::Promise {
+        self.assert_role(&"vesting_admin".to_string());
+        let mut schedule = self.vesting_schedule_of(&account_id);
+        if schedule.revoked {
+            near_sdk::env::panic(format!("Vesting of {} is already revoked", account_id).as_bytes())
+        }
+        let vested = Self::vesting_vested(&schedule, near_sdk::env::block_timestamp());
+        let unvested = schedule.total.0 - vested;
+        if unvested == 0 {
+            near_sdk::env::panic(
+                format!("Vesting of {} has nothing unvested to revoke", account_id).as_bytes(),
+            )
+        }
+        schedule.total = vested.into();
+        schedule.revoked = true;
+        self.vesting_schedules.insert(&account_id, &schedule);
+        near_sdk::env::log(
+            format!(
+                "Vesting of {} is revoked, {} is unvested",
+                account_id, unvested
+            )
+            .as_bytes(),
+        );
+        self.vesting_transfer(near_sdk::env::predecessor_account_id(), unvested)
+            .then(
+                near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                    b"vesting_on_revoke".to_vec(),
+                    near_sdk::serde_json::json!
+                    ({
+                        "amount" : near_sdk::json_types::U128(unvested),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    0,
+                    Self::VESTING_GAS_FOR_REVOKE_CALLBACK,
+                ),
+            )
+    }
+
+    #[private]
+    fn vesting_on_revoke(&mut self, amount: near_sdk::json_types::U128) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            return true;
+        }
+        if self.vesting_token.is_some() {
+            self.vesting_unallocated = self.vesting_unallocated.saturating_add(amount.0);
+        }
+        near_sdk::env::log(format!("Transfer of {} unvested failed", amount.0).as_bytes());
+        false
+    }
+
+    fn claim(&mut self) -> near_sdk::Promise {
+        let account_id = near_sdk::env::predecessor_account_id();
+        let mut schedule = self.vesting_schedule_of(&account_id);
+        let amount =
+            Self::vesting_vested(&schedule, near_sdk::env::block_timestamp()) - schedule.claimed.0;
+        if amount == 0 {
+            near_sdk::env::panic(format!("Account {} has nothing to claim", account_id).as_bytes())
+        }
+        schedule.claimed = (schedule.claimed.0 + amount).into();
+        self.vesting_schedules.insert(&account_id, &schedule);
+        near_sdk::env::log(format!("Account {} claims {}", account_id, amount).as_bytes());
+        self.vesting_transfer(account_id.clone(), amount).then(
+            near_sdk::Promise::new(near_sdk::env::current_account_id()).function_call(
+                b"vesting_on_claim".to_vec(),
+                near_sdk::serde_json::json!
+                ({
+                    "account_id" : account_id, "amount" : near_sdk::json_types::U128(amount),
+                })
+                .to_string()
+                .into_bytes(),
+                0,
+                Self::VESTING_GAS_FOR_CLAIM_CALLBACK,
+            ),
+        )
+    }
+
+    #[private]
+    fn vesting_on_claim(
+        &mut self,
+        account_id: near_sdk::AccountId,
+        amount: near_sdk::json_types::U128,
+    ) -> bool {
+        if let near_sdk::PromiseResult::Successful(_) = near_sdk::env::promise_result(0) {
+            return true;
+        }
+        let mut schedule = self.vesting_schedule_of(&account_id);
+        schedule.claimed = schedule.claimed.0.saturating_sub(amount.0).into();
+        self.vesting_schedules.insert(&account_id, &schedule);
+        near_sdk::env::log(format!("Claim of {} by {} failed", amount.0, account_id).as_bytes());
+        false
+    }
+
+    fn claimable(&self, account_id: near_sdk::AccountId) -> near_sdk::json_types::U128 {
+        match self.vesting_schedules.get(&account_id) {
+            Some(schedule) => (Self::vesting_vested(&schedule, near_sdk::env::block_timestamp())
+                - schedule.claimed.0)
+                .into(),
+            None => near_sdk::json_types::U128(0),
+        }
+    }
+
+    fn vesting_schedule(&self, account_id: near_sdk::AccountId) -> Option<VestingSchedule> {
+        self.vesting_schedules.get(&account_id)
+    }
+
+    fn vesting_token(&self) -> Option<near_sdk::AccountId> {
+        self.vesting_token.clone()
+    }
+
+    fn vesting_unallocated(&self) -> near_sdk::json_types::U128 {
+        self.vesting_unallocated.into()
+    }
+}
+
+impl StatusMessage {
+    pub const VESTING_GAS_FOR_FT_TRANSFER: near_sdk::Gas = 10_000_000_000_000;
+    pub const VESTING_GAS_FOR_CLAIM_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub const VESTING_GAS_FOR_REVOKE_CALLBACK: near_sdk::Gas = 10_000_000_000_000;
+    pub fn setup_vesting(&mut self, token_id: Option<near_sdk::AccountId>) {
+        if let Some(token_id) = &token_id {
+            if !near_sdk::env::is_valid_account_id(token_id.as_bytes()) {
+                near_sdk::env::panic(format!("Account {} is invalid", token_id).as_bytes())
+            }
+        }
+        self.vesting_token = token_id;
+    }
+
+    pub fn vesting_fund(&mut self, amount: near_sdk::Balance) {
+        let token_id = near_sdk::env::predecessor_account_id();
+        if self.vesting_token.as_ref() != Some(&token_id) {
+            near_sdk::env::panic(format!("Token {} is not vested", token_id).as_bytes())
+        }
+        self.vesting_unallocated = self
+            .vesting_unallocated
+            .checked_add(amount)
+            .unwrap_or_else(|| near_sdk::env::panic(b"Vesting funds overflow"));
+        near_sdk::env::log(format!("{} tokens are added to the vesting funds", amount).as_bytes());
+    }
+
+    fn vesting_schedule_of(&self, account_id: &near_sdk::AccountId) -> VestingSchedule {
+        self.vesting_schedules.get(account_id).unwrap_or_else(|| {
+            near_sdk::env::panic(
+                format!("Account {} has no vesting schedule", account_id).as_bytes(),
+            )
+        })
+    }
+
+    fn vesting_vested(schedule: &VestingSchedule, now: u64) -> near_sdk::Balance {
+        let total = schedule.total.0;
+        if schedule.revoked || now >= schedule.start + schedule.duration {
+            return total;
+        }
+        if now < schedule.cliff {
+            return 0;
+        }
+        let elapsed = (now - schedule.start) as u128;
+        let duration = schedule.duration as u128;
+        total / duration * elapsed + total % duration * elapsed / duration
+    }
+
+    fn vesting_transfer(
+        &self,
+        receiver_id: near_sdk::AccountId,
+        amount: near_sdk::Balance,
+    ) -> near_sdk::Promise {
+        match & self.vesting_token
+        {
+            Some(token_id) => near_sdk::Promise::new(token_id.clone()).function_call(b"ft_transfer".to_vec(),
+            near_sdk::serde_json::json!
+            ({
+                "receiver_id" : receiver_id, "amount" : near_sdk::json_types::U128(amount), "memo" : "Vesting",
+            }).to_string().into_bytes(), 1, Self::VESTING_GAS_FOR_FT_TRANSFER,), None => near_sdk::Promise::new(receiver_id).transfer(amount),
+        }
+    }
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, token_id: Option<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = Self {
+            data: String::from("SOME DATA"),
+            access_control_roles: near_sdk::collections::UnorderedMap::new(
+                StorageKeyAccessControl::Roles.into_bytes(),
+            ),
+            vesting_schedules: near_sdk::collections::LookupMap::new(
+                StorageKeyVesting::Schedules.into_bytes(),
+            ),
+            vesting_token: None,
+            vesting_unallocated: 0,
+        };
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+        constructor.setup_account_role(&VESTING_ADMIN.to_string(), &admin);
+
+        // `None` vests native NEAR
+        constructor.setup_vesting(token_id);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Transferred tokens fund new schedules
+    #[allow(unused_variables)]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.vesting_fund(amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+    const START: u64 = 1_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn claimable(contract: &StatusMessage) -> u128 {
+        contract.claimable(String::from("carol.testnet")).0
+    }
+
+    // 1000 yoctoNEAR for carol with a cliff of 100 seconds over 1000 seconds
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"), None);
+
+        at("bob.testnet", 0, 1000);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        contract
+    }
+
+    #[test]
+    fn should_vest_linearly_after_cliff() {
+        let contract = setup();
+
+        at("carol.testnet", START + 99, 0);
+        assert_eq!(claimable(&contract), 0);
+
+        at("carol.testnet", START + 100, 0);
+        assert_eq!(claimable(&contract), 100);
+
+        at("carol.testnet", START + 500, 0);
+        assert_eq!(claimable(&contract), 500);
+
+        at("carol.testnet", START + 5000, 0);
+        assert_eq!(claimable(&contract), 1000);
+    }
+
+    #[test]
+    fn should_claim() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 300, 0);
+        contract.claim();
+
+        assert_eq!(claimable(&contract), 0);
+
+        at("carol.testnet", START + 400, 0);
+        assert_eq!(claimable(&contract), 100);
+        assert_eq!(
+            contract
+                .vesting_schedule(String::from("carol.testnet"))
+                .unwrap()
+                .claimed,
+            U128(300)
+        );
+    }
+
+    #[test]
+    fn should_roll_back_failed_claim() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 300, 0);
+        contract.claim();
+
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.vesting_on_claim(String::from("carol.testnet"), U128(300)));
+
+        at("carol.testnet", START + 300, 0);
+        assert_eq!(claimable(&contract), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.testnet has nothing to claim")]
+    fn should_panic_before_cliff() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 50, 0);
+        contract.claim();
+    }
+
+    #[test]
+    fn should_revoke_unvested_amount() {
+        let mut contract = setup();
+
+        at("bob.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+
+        at("carol.testnet", START + 5000, 0);
+        assert_eq!(claimable(&contract), 250);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: vesting_admin role")]
+    fn should_panic_on_revoke_without_role() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = "duration_secs is too large")]
+    fn should_panic_on_duration_overflow() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0, 1000);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 0, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of 1000 yoctoNEAR")]
+    fn should_panic_without_funding() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0, 10);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 100, 1000);
+    }
+
+    fn setup_token() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            Some(String::from("token.testnet")),
+        )
+    }
+
+    #[test]
+    fn should_allocate_funded_tokens() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        assert_eq!(contract.vesting_unallocated(), U128(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only 500 tokens are funded and not allocated")]
+    fn should_panic_without_token_funding() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 100, 1000);
+    }
+
+    #[test]
+    fn should_refund_failed_revoke_to_funds() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1000), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        at("bob.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+
+        assert_eq!(contract.vesting_unallocated(), U128(0));
+
+        // e.g. bob is not registered with the token
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.vesting_on_revoke(U128(750)));
+        assert_eq!(contract.vesting_unallocated(), U128(750));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token fake.testnet is not vested")]
+    fn should_only_fund_with_vested_token() {
+        let mut contract = setup_token();
+
+        at("fake.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+    }
+}
@@ -0,0 +1,289 @@
+use near_macros::{init_macro, require};
+use near_sdk::borsh;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, PanicOnDefault, PromiseOrValue};
+
+setup_alloc!();
+
+const DEFAULT_ADMIN: &str = "default_admin";
+const VESTING_ADMIN: &str = "vesting_admin";
+
+#[near_bindgen]
+#[derive(PanicOnDefault)]
+#[require(Access, Vesting)]
+pub struct StatusMessage {
+    data: String,
+}
+
+#[near_bindgen]
+impl StatusMessage {
+    #[init]
+    pub fn new(admin: AccountId, token_id: Option<AccountId>) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized.");
+
+        let mut constructor = init_macro!([
+            "access",
+            "vesting",
+            Self {
+                data: String::from("SOME DATA")
+            }
+        ]);
+
+        constructor.setup_account_role(&DEFAULT_ADMIN.to_string(), &admin);
+        constructor.setup_account_role(&VESTING_ADMIN.to_string(), &admin);
+
+        // `None` vests native NEAR
+        constructor.setup_vesting(token_id);
+
+        constructor
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+
+    // Transferred tokens fund new schedules
+    #[allow(unused_variables)]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.vesting_fund(amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, Balance, PromiseResult, VMContext};
+
+    const SECOND: u64 = 1_000_000_000;
+    const START: u64 = 1_000;
+
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    fn at(predecessor_account_id: &str, seconds: u64, attached_deposit: Balance) {
+        let mut context = get_context(String::from(predecessor_account_id), 0);
+        context.block_timestamp = seconds * SECOND;
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    fn claimable(contract: &StatusMessage) -> u128 {
+        contract.claimable(String::from("carol.testnet")).0
+    }
+
+    // 1000 yoctoNEAR for carol with a cliff of 100 seconds over 1000 seconds
+    fn setup() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        let mut contract = StatusMessage::new(String::from("bob.testnet"), None);
+
+        at("bob.testnet", 0, 1000);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        contract
+    }
+
+    #[test]
+    fn should_vest_linearly_after_cliff() {
+        let contract = setup();
+
+        at("carol.testnet", START + 99, 0);
+        assert_eq!(claimable(&contract), 0);
+
+        at("carol.testnet", START + 100, 0);
+        assert_eq!(claimable(&contract), 100);
+
+        at("carol.testnet", START + 500, 0);
+        assert_eq!(claimable(&contract), 500);
+
+        at("carol.testnet", START + 5000, 0);
+        assert_eq!(claimable(&contract), 1000);
+    }
+
+    #[test]
+    fn should_claim() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 300, 0);
+        contract.claim();
+
+        assert_eq!(claimable(&contract), 0);
+
+        at("carol.testnet", START + 400, 0);
+        assert_eq!(claimable(&contract), 100);
+        assert_eq!(
+            contract
+                .vesting_schedule(String::from("carol.testnet"))
+                .unwrap()
+                .claimed,
+            U128(300)
+        );
+    }
+
+    #[test]
+    fn should_roll_back_failed_claim() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 300, 0);
+        contract.claim();
+
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.vesting_on_claim(String::from("carol.testnet"), U128(300)));
+
+        at("carol.testnet", START + 300, 0);
+        assert_eq!(claimable(&contract), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.testnet has nothing to claim")]
+    fn should_panic_before_cliff() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 50, 0);
+        contract.claim();
+    }
+
+    #[test]
+    fn should_revoke_unvested_amount() {
+        let mut contract = setup();
+
+        at("bob.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+
+        at("carol.testnet", START + 5000, 0);
+        assert_eq!(claimable(&contract), 250);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing: vesting_admin role")]
+    fn should_panic_on_revoke_without_role() {
+        let mut contract = setup();
+
+        at("carol.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+    }
+
+    #[test]
+    #[should_panic(expected = "duration_secs is too large")]
+    fn should_panic_on_duration_overflow() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0, 1000);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 0, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of 1000 yoctoNEAR")]
+    fn should_panic_without_funding() {
+        let mut contract = setup();
+
+        at("bob.testnet", 0, 10);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 100, 1000);
+    }
+
+    fn setup_token() -> StatusMessage {
+        at("alice.testnet", 0, 0);
+
+        StatusMessage::new(
+            String::from("bob.testnet"),
+            Some(String::from("token.testnet")),
+        )
+    }
+
+    #[test]
+    fn should_allocate_funded_tokens() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        assert_eq!(contract.vesting_unallocated(), U128(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only 500 tokens are funded and not allocated")]
+    fn should_panic_without_token_funding() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+        contract.create_vesting(String::from("dave.testnet"), U128(1000), START, 100, 1000);
+    }
+
+    #[test]
+    fn should_refund_failed_revoke_to_funds() {
+        let mut contract = setup_token();
+
+        at("token.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1000), String::new());
+
+        at("bob.testnet", 0, 0);
+        contract.create_vesting(String::from("carol.testnet"), U128(1000), START, 100, 1000);
+
+        at("bob.testnet", START + 250, 0);
+        contract.revoke_vesting(String::from("carol.testnet"));
+
+        assert_eq!(contract.vesting_unallocated(), U128(0));
+
+        // e.g. bob is not registered with the token
+        testing_env!(
+            get_context(String::from("alice.testnet"), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        assert!(!contract.vesting_on_revoke(U128(750)));
+        assert_eq!(contract.vesting_unallocated(), U128(750));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token fake.testnet is not vested")]
+    fn should_only_fund_with_vested_token() {
+        let mut contract = setup_token();
+
+        at("fake.testnet", 0, 0);
+        contract.ft_on_transfer(String::from("bob.testnet"), U128(1500), String::new());
+    }
+}